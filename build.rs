@@ -5,10 +5,35 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
-use std::fs::File;
 use std::hash::{Hash, Hasher};
 use std::io::Write;
 
+#[path = "src/activity/json_types.rs"]
+#[allow(dead_code, clippy::upper_case_acronyms)]
+mod activity_json_types;
+#[path = "src/enemies/json_types.rs"]
+#[allow(dead_code, clippy::upper_case_acronyms)]
+mod enemies_json_types;
+#[path = "src/formulas/json_types.rs"]
+mod json_types;
+#[path = "src/formulas/validate.rs"]
+mod validate;
+//same module paths as the library so the shared files can refer to each other
+mod activity {
+    pub(crate) use super::activity_json_types as json_types;
+}
+mod enemies {
+    pub(crate) use super::enemies_json_types as json_types;
+}
+
+use activity::json_types::{ActivityPresetJson, ArmorStatData, DifficultyTableJson};
+use enemies::json_types::{ChampionProfileJson, ChampionStunJson, ChampionsJson};
+
+use json_types::{
+    AmmoJson, BlastJson, BowFrameJson, DamageModsJson, GlaiveFrameJson, HandlingJson, RangeJson,
+    ReloadJson, ReserveJson, StatQuadraticJson, SubFamJson, SwordFrameJson, WeaponFormulaJson,
+};
+
 /*const ID_TO_NAME: PhfMap<i32, &'static str> = phf_map! {
     6i32 => "Auto Rifle",
     31i32 => "Combat Bow",
@@ -24,6 +49,7 @@ use std::io::Write;
     12i32 => "Sniper Rifle",
     24i32 => "Submachine Gun",
     33i32 => "Glaive",
+    18i32 => "Sword",
     25i32 => "Trace Rifle",
     17i32 => "Sidearm",
 };*/
@@ -43,6 +69,7 @@ const NAME_TO_ID: PhfMap<&'static str, i32> = phf_map! {
     "Sniper Rifle"=>       12i32,
     "Submachine Gun" =>    24i32,
     "Glaive"=>             33i32,
+    "Sword"=>              18i32,
     "Trace Rifle"=>        25i32,
     "Sidearm"=>            17i32,
 };
@@ -61,7 +88,9 @@ const INTRINSIC_MAP: PhfMap<u32, &'static [&'static str]> = phf_map! {
 913u32 => &["Area Denial Frame"],
 914u32 => &["Rocket-Assisted Frame"],
 915u32 => &["Shot Package"],
-916u32 => &["Pinpoint Slug Frame"]
+916u32 => &["Pinpoint Slug Frame"],
+917u32 => &["Vortex Frame"],
+918u32 => &["Caster Frame"]
 };
 
 fn calculate_hash<T: Hash>(t: &T) -> u64 {
@@ -78,15 +107,50 @@ impl PartialHash for f64 {
     }
 }
 
+//how old the vendored manifest snapshot can get before offline builds complain
+const SNAPSHOT_STALE_DAYS: u64 = 30;
+
+fn env_is_true(name: &str) -> bool {
+    matches!(std::env::var(name), Ok(val) if val == "true")
+}
+
+//unix time the build considers "now", honors SOURCE_DATE_EPOCH for reproducible builds.
+//offline builds must not depend on the clock, so they fall back to the snapshot's date
+fn build_epoch(offline: bool, snapshot_epoch: Option<u64>) -> u64 {
+    if let Some(epoch) = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|x| x.parse::<u64>().ok())
+    {
+        return epoch;
+    }
+    if offline {
+        return snapshot_epoch.expect(
+            "cargo:warning=the manifest snapshot has no date, set SOURCE_DATE_EPOCH for offline builds",
+        );
+    }
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct CachedBuildData {
     last_manifest_version: String,
+    #[serde(default)]
+    last_manifest_fetch: u64,
     dim_perk_mappings: Vec<(u32, u32)>,
     procedural_intrinsic_mappings: Vec<(u32, u32)>,
+    #[serde(default)]
+    weapon_items: Vec<CachedItem>,
+    #[serde(default)]
+    stat_groups: Vec<CachedStatGroup>,
 
     perk_timestamps: BTreeMap<u64, u64>,
     #[serde(skip_serializing, default)]
     current_timestamps: HashSet<u64>,
+    #[serde(skip)]
+    build_epoch: u64,
 }
 
 impl CachedBuildData {
@@ -100,6 +164,64 @@ impl CachedBuildData {
     fn sort(&mut self) {
         self.dim_perk_mappings.sort();
         self.procedural_intrinsic_mappings.sort();
+        self.weapon_items.sort_by_key(|x| x.hash);
+        self.stat_groups.sort_by_key(|x| x.hash);
+    }
+
+    //manifest versions look like "233448.25.04.26.1930-2-bnet.59695", the
+    //second through fifth fields are the utc publish date as yy.mm.dd.hhmm
+    fn manifest_version_epoch(&self) -> Option<u64> {
+        let mut fields = self.last_manifest_version.split('.').skip(1);
+        let year = 2000 + fields.next()?.parse::<i64>().ok()?;
+        let month = fields.next()?.parse::<i64>().ok()?;
+        let day = fields.next()?.parse::<i64>().ok()?;
+        let time = fields.next()?.split('-').next()?.parse::<i64>().ok()?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        //days since 1970-01-01 for a proleptic gregorian date
+        let (y, m) = if month <= 2 {
+            (year - 1, month + 9)
+        } else {
+            (year, month - 3)
+        };
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * m + 2) / 5 + day - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+        u64::try_from(days * 86400 + (time / 100) * 3600 + (time % 100) * 60).ok()
+    }
+
+    //when the snapshot was fetched, older snapshots only carry the manifest publish date
+    fn snapshot_epoch(&self) -> Option<u64> {
+        match self.last_manifest_fetch {
+            0 => self.manifest_version_epoch(),
+            fetched => Some(fetched),
+        }
+    }
+
+    //measured against the wall clock, the build epoch is pinned for reproducibility
+    //and says nothing about how old the snapshot really is
+    fn report_staleness(&self) {
+        let Some(fetched) = self.snapshot_epoch() else {
+            println!(
+                "cargo:warning=manifest snapshot {} has no fetch date, run an online build to refresh it",
+                self.last_manifest_version
+            );
+            return;
+        };
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let age_days = now.saturating_sub(fetched) / 86400;
+        if age_days > SNAPSHOT_STALE_DAYS {
+            println!(
+                "cargo:warning=manifest snapshot {} is {} days old, run an online build to refresh it",
+                self.last_manifest_version, age_days
+            );
+        }
     }
 
     fn clean_timestamps(&mut self) {
@@ -116,10 +238,7 @@ impl CachedBuildData {
     fn get_timestamp(&mut self, formula: &impl Hash) -> u64 {
         // get current unix time
         let timestamps = &mut self.perk_timestamps;
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let now = self.build_epoch;
         let hash = calculate_hash(&formula);
         self.current_timestamps.insert(hash);
 
@@ -133,18 +252,153 @@ impl CachedBuildData {
 #[derive(Debug, Clone, Copy, Hash)]
 struct WeaponPath(u32, u32);
 
+//socket category holding a weapons frame/intrinsic
+const INTRINSIC_SOCKET_CATEGORY: u32 = 3956125808;
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+struct CachedItem {
+    hash: u32,
+    weapon_type: u8,
+    intrinsic_hash: u32,
+    ammo_type: u32,
+    damage_type: u32,
+    stat_group: u32,
+    investment_stats: Vec<(u32, i32)>,
+    display_stats: Vec<(u32, i32)>,
+}
+impl CachedItem {
+    //pulls what setWeapon needs out of a DestinyInventoryItemDefinition, None if not a weapon
+    fn from_manifest(hash: u32, value: &Value) -> Option<CachedItem> {
+        if value["itemType"].as_u64()? != 3 {
+            return None;
+        }
+        let sockets = &value["sockets"];
+        let intrinsic_hash = sockets["socketCategories"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|x| x["socketCategoryHash"].as_u64() == Some(INTRINSIC_SOCKET_CATEGORY as u64))
+            .and_then(|x| x["socketIndexes"][0].as_u64())
+            .and_then(|i| sockets["socketEntries"][i as usize]["singleInitialItemHash"].as_u64())
+            .unwrap_or_default() as u32;
+
+        let mut investment_stats: Vec<(u32, i32)> = value["investmentStats"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|x| !x["isConditionallyActive"].as_bool().unwrap_or_default())
+            .filter_map(|x| {
+                Some((
+                    x["statTypeHash"].as_u64()? as u32,
+                    x["value"].as_i64()? as i32,
+                ))
+            })
+            .collect();
+        investment_stats.sort();
+        let mut display_stats: Vec<(u32, i32)> = value["stats"]["stats"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(_, x)| {
+                Some((x["statHash"].as_u64()? as u32, x["value"].as_i64()? as i32))
+            })
+            .collect();
+        display_stats.sort();
+
+        Some(CachedItem {
+            hash,
+            weapon_type: value["itemSubType"].as_u64()? as u8,
+            intrinsic_hash,
+            ammo_type: value["equippingBlock"]["ammoType"]
+                .as_u64()
+                .unwrap_or_default() as u32,
+            damage_type: value["defaultDamageTypeHash"].as_u64().unwrap_or_default() as u32,
+            stat_group: value["stats"]["statGroupHash"].as_u64().unwrap_or_default() as u32,
+            investment_stats,
+            display_stats,
+        })
+    }
+}
+#[derive(Clone, Serialize, Deserialize, Default)]
+struct CachedScaledStat {
+    stat_hash: u32,
+    maximum_value: i32,
+    interpolation: Vec<(i32, i32)>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Default)]
+struct CachedStatGroup {
+    hash: u32,
+    scaled_stats: Vec<CachedScaledStat>,
+}
+impl CachedStatGroup {
+    //keeps DestinyStatGroupDefinition.scaledStats as (value, weight) pairs
+    fn from_manifest(hash: u32, value: &Value) -> CachedStatGroup {
+        let mut scaled_stats: Vec<CachedScaledStat> = value["scaledStats"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|x| {
+                Some(CachedScaledStat {
+                    stat_hash: x["statHash"].as_u64()? as u32,
+                    maximum_value: x["maximumValue"].as_i64()? as i32,
+                    interpolation: x["displayInterpolation"]
+                        .as_array()?
+                        .iter()
+                        .filter_map(|p| {
+                            Some((p["value"].as_i64()? as i32, p["weight"].as_i64()? as i32))
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+        scaled_stats.sort_by_key(|x| x.stat_hash);
+        CachedStatGroup { hash, scaled_stats }
+    }
+}
+impl Debug for CachedScaledStat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ScaledStat {{ stat_hash: {}, maximum_value: {}, interpolation: &{:?} }}",
+            self.stat_hash, self.maximum_value, self.interpolation
+        )
+    }
+}
+impl Debug for CachedStatGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "StatGroup {{ hash: {}, scaled_stats: &{:?} }}",
+            self.hash, self.scaled_stats
+        )
+    }
+}
+
+//these are written as literals since the runtime types use static slices
+impl Debug for CachedItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ItemData {{ hash: {}, weapon_type: {}, intrinsic_hash: {}, ammo_type: {}, damage_type: {}, stat_group: {}, investment_stats: &{:?}, display_stats: &{:?} }}",
+            self.hash,
+            self.weapon_type,
+            self.intrinsic_hash,
+            self.ammo_type,
+            self.damage_type,
+            self.stat_group,
+            self.investment_stats,
+            self.display_stats
+        )
+    }
+}
+
 fn find_uuid<T: Hash>(vec: &[T], uuid: &T) -> Option<usize> {
     vec.iter()
         .position(|x| calculate_hash(&x) == calculate_hash(uuid))
 }
 
-fn write_variable(
-    writer: &mut std::fs::File,
-    name: &str,
-    datatype: &str,
-    value: String,
-    doc: &str,
-) {
+fn write_variable(writer: &mut impl Write, name: &str, datatype: &str, value: String, doc: &str) {
     let res = writeln!(
         writer,
         "#[doc=r#\"{}\"#]\n#[allow(dead_code,clippy::approx_constant)]\npub const {}: {} = {};",
@@ -155,88 +409,226 @@ fn write_variable(
     }
 }
 
-fn main() {
-    let mut opts = built::Options::default();
-    opts.set_dependencies(true);
-
-    let src = std::env::var("CARGO_MANIFEST_DIR").unwrap();
-    let built_dst = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("built.rs");
-    let formula_dst = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("formulas.rs");
-
-    built::write_built_file_with_opts(&opts, src.as_ref(), &built_dst)
-        .expect("Failed to acquire build-time information");
+fn load_cached_data(build_cache_path: &std::path::Path) -> CachedBuildData {
+    if !build_cache_path.exists() {
+        println!("cargo:warning=no cached build file found");
+        return CachedBuildData::default();
+    }
+    match std::fs::read_to_string(build_cache_path) {
+        Ok(raw) => ron::de::from_str(&raw).expect("cargo:warning=error reading cached build file"),
+        Err(err) => {
+            println!("cargo:warning=error opening cached build file: {}", err);
+            CachedBuildData::default()
+        }
+    }
+}
 
-    let mut formula_file = std::fs::File::create(formula_dst).unwrap();
+fn generate_formulas(cached: &mut CachedBuildData, offline: bool) -> Vec<u8> {
+    let mut formula_file: Vec<u8> = Vec::new();
 
     //write imports in file
     let res = writeln!(
         formula_file,
-        "use crate::types::rs_types::{{StatQuadraticFormula, RangeFormula, HandlingFormula, ReloadFormula, DamageMods, AmmoFormula, DataPointers, FiringData, WeaponPath}};");
+        "use crate::types::rs_types::{{StatQuadraticFormula, RangeFormula, HandlingFormula, ReloadFormula, DamageMods, AmmoFormula, DataPointers, FiringData, WeaponPath, ItemData, StatGroup, ScaledStat, ReserveFormula, DifficultyTable, ActivityPreset, SwordFrame, GlaiveFrame, BlastFormula, BowFrame}};\nuse crate::{{activity::{{damage_calc::DifficultyOptions, armor_stats::ArmorStatData}}, enemies::{{EnemyType, champions::{{ChampionType, ChampionProfile}}}}}};\nuse std::borrow::Cow;");
     if res.is_err() {
         panic!("cargo:warning=error writing imports");
     }
 
-    let build_cache_path = std::path::Path::new("./build_resources/cached_build.ron");
-    let mut cached_data: CachedBuildData;
-    if !build_cache_path.exists() {
-        println!("cargo:warning=no cached build file found");
-        cached_data = CachedBuildData::default();
-    } else {
-        let file_res = std::fs::File::open(build_cache_path);
-        if file_res.is_err() {
-            println!(
-                "cargo:warning=error opening cached build file: {}",
-                file_res.err().unwrap()
+    construct_enhance_perk_mapping(&mut formula_file, cached, offline);
+    construct_weapon_formulas(&mut formula_file, cached);
+    construct_item_table(&mut formula_file, cached);
+    construct_stat_groups(&mut formula_file, cached);
+    construct_difficulty_tables(&mut formula_file);
+    construct_activity_presets(&mut formula_file);
+    construct_champions(&mut formula_file);
+    construct_armor_stats(&mut formula_file);
+    formula_file
+}
+
+//compares this run against the last one recorded in OUT_DIR,
+//identical inputs must always produce identical formulas
+fn check_fingerprint(
+    fingerprint_dst: &std::path::Path,
+    inputs: &[&std::path::Path],
+    epoch: u64,
+    formulas: &[u8],
+) {
+    let mut input_bytes: Vec<u8> = epoch.to_le_bytes().to_vec();
+    for input in inputs {
+        input_bytes.extend(std::fs::read(input).unwrap_or_default());
+    }
+    let fingerprint = format!(
+        "{:x} {:x}",
+        calculate_hash(&input_bytes),
+        calculate_hash(&formulas)
+    );
+    if let Ok(previous) = std::fs::read_to_string(fingerprint_dst) {
+        let (prev_inputs, prev_output) = previous.split_once(' ').unwrap_or_default();
+        let (cur_inputs, cur_output) = fingerprint.split_once(' ').unwrap();
+        if prev_inputs == cur_inputs && prev_output != cur_output {
+            panic!(
+                "cargo:warning=formulas.rs differs from the previous build with identical inputs"
             );
-            cached_data = CachedBuildData::default();
-        } else {
-            let file = file_res.unwrap();
-            let res = ron::de::from_reader(file);
-            if res.is_err() {
-                panic!("cargo:warning=error reading cached build file");
-            } else {
-                cached_data = res.unwrap();
-            }
         }
     }
+    std::fs::write(fingerprint_dst, fingerprint).expect("cargo:warning=error writing fingerprint");
+}
+
+fn main() {
+    let offline = env_is_true("ORACLE_OFFLINE");
+    if offline {
+        //the default rerun behavior is fine online, offline builds should only
+        //rerun when the snapshot or the environment actually changes
+        println!("cargo:rerun-if-changed=build.rs");
+        println!("cargo:rerun-if-changed=build_resources");
+        println!("cargo:rerun-if-env-changed=ORACLE_OFFLINE");
+        println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+        println!("cargo:rerun-if-env-changed=IS_RA");
+    }
+    println!("cargo:rerun-if-env-changed=ORACLE_UPDATE_SNAPSHOT");
 
-    construct_enhance_perk_mapping(&mut formula_file, &mut cached_data);
-    construct_weapon_formulas(&mut formula_file, &mut cached_data);
+    let mut opts = built::Options::default();
+    opts.set_dependencies(true);
+    if offline {
+        //git and the clock are not reproducible inputs
+        opts.set_git(false);
+        opts.set_time(false);
+    }
+
+    let src = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+    let built_dst = out_dir.join("built.rs");
+    let formula_dst = out_dir.join("formulas.rs");
+
+    built::write_built_file_with_opts(&opts, src.as_ref(), &built_dst)
+        .expect("Failed to acquire build-time information");
+    if offline {
+        let mut built_file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&built_dst)
+            .unwrap();
+        writeln!(
+            built_file,
+            "#[allow(dead_code)]\npub const BUILT_TIME_UTC: &str = \"offline\";\n\
+             #[allow(dead_code)]\npub const GIT_COMMIT_HASH: Option<&str> = None;\n\
+             #[allow(dead_code)]\npub const GIT_HEAD_REF: Option<&str> = None;"
+        )
+        .expect("cargo:warning=error writing offline build info");
+    }
+
+    let src_path = std::path::Path::new(&src);
+    let build_cache_path = src_path.join("build_resources/cached_build.ron");
+    let mut cached_data = load_cached_data(&build_cache_path);
+    let epoch = build_epoch(offline, cached_data.snapshot_epoch());
+    cached_data.build_epoch = epoch;
+    let original_cache =
+        ron::ser::to_string_pretty(&cached_data, ron::ser::PrettyConfig::default())
+            .expect("cargo:warning=error initializing ron serializer");
+
+    let formulas = if offline {
+        let mut second_pass = cached_data.clone();
+        let first = generate_formulas(&mut cached_data, offline);
+        let second = generate_formulas(&mut second_pass, offline);
+        if first != second {
+            panic!("cargo:warning=formulas.rs is not reproducible between runs");
+        }
+        check_fingerprint(
+            &out_dir.join("formulas.fingerprint"),
+            &[
+                &src_path.join("build.rs"),
+                &src_path.join("build_resources/weapon_formulas.json"),
+                &src_path.join("build_resources/difficulty_tables.json"),
+                &src_path.join("build_resources/activity_presets.json"),
+                &src_path.join("build_resources/champions.json"),
+                &src_path.join("build_resources/armor_stats.json"),
+                &build_cache_path,
+            ],
+            epoch,
+            &first,
+        );
+        first
+    } else {
+        generate_formulas(&mut cached_data, offline)
+    };
+    std::fs::write(formula_dst, formulas).expect("cargo:warning=error writing formulas");
 
     cached_data.clean_timestamps();
     cached_data.sort();
+    if offline {
+        cached_data.report_staleness();
+        //the snapshot is vendored, offline builds never write back to it
+        return;
+    }
     let is_rust_analyzer = std::env::var("IS_RA");
     if is_rust_analyzer.is_ok() && is_rust_analyzer.unwrap() == "true" {
         println!("cargo:warning=running in rust-analyzer");
         return;
     }
 
-    let file_res = std::fs::File::create("./build_resources/cached_build.ron");
-    if file_res.is_err() {
-        println!("cargo:warning=error writing cached build file");
+    let res = ron::ser::to_string_pretty(&cached_data, ron::ser::PrettyConfig::default())
+        .expect("cargo:warning=error initializing ron serializer");
+    let _: CachedBuildData = ron::de::from_str(&res).expect("cargo:warning=error deserializing");
+    if res == original_cache {
+        return;
+    }
+    //the vendored snapshot is tracked, only rewrite it when asked to
+    let refreshed_dst = if env_is_true("ORACLE_UPDATE_SNAPSHOT") {
+        build_cache_path
     } else {
-        let mut file = file_res.unwrap();
-        let res = ron::ser::to_string_pretty(&cached_data, ron::ser::PrettyConfig::default());
-        if res.is_err() {
-            panic!("cargo:warning=error initializing ron serializer");
-        } else {
-            #[allow(unused_variables)]
-            let cd: CachedBuildData = ron::de::from_str(&res.clone().unwrap())
-                .expect("cargo:warning=error deserializing");
-        }
-        file.write_all(res.unwrap().as_bytes())
-            .expect("cargo:warning=error writing cached build file");
+        println!(
+            "cargo:warning=manifest snapshot changed, refreshed copy written to {}",
+            out_dir.join("cached_build.ron").display()
+        );
+        out_dir.join("cached_build.ron")
+    };
+    std::fs::write(refreshed_dst, res.as_bytes())
+        .expect("cargo:warning=error writing cached build file");
+}
+
+fn construct_stat_groups(formula_file: &mut impl Write, cached: &CachedBuildData) {
+    let mut groups = cached.stat_groups.clone();
+    groups.sort_by_key(|x| x.hash);
+    write_variable(
+        formula_file,
+        "STAT_GROUP_DATA",
+        &format!("[StatGroup; {}]", groups.len()),
+        format!("{:?}", groups),
+        "Stat group interpolation tables used by weapons, sorted by hash",
+    );
+}
+
+fn construct_item_table(formula_file: &mut impl Write, cached: &CachedBuildData) {
+    if cached.weapon_items.is_empty() {
+        println!("cargo:warning=no weapon items in manifest snapshot, item lookups will fail");
     }
+    let mut items = cached.weapon_items.clone();
+    items.sort_by_key(|x| x.hash);
+    write_variable(
+        formula_file,
+        "ITEM_DATA",
+        &format!("[ItemData; {}]", items.len()),
+        format!("{:?}", items),
+        "Weapon item definitions from the manifest, sorted by hash",
+    );
 }
 
 //fn set_data(val: Value, weapon_def: Value, weapon_hash: u32, weapon_id: String) {]
 
-fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildData) {
+fn construct_weapon_formulas(formula_file: &mut impl Write, cached: &mut CachedBuildData) {
     let jdata_path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
         .join("build_resources/weapon_formulas.json");
 
-    let jdata: WeaponFormulaJson =
-        serde_json::from_str(&std::fs::read_to_string(jdata_path).unwrap()).unwrap();
+    let raw = std::fs::read_to_string(jdata_path).unwrap();
+    let diagnostics = validate::validate_weapon_formulas(&raw);
+    for diagnostic in diagnostics.iter() {
+        println!("cargo:warning=weapon_formulas.json {}", diagnostic);
+    }
+    if validate::has_errors(&diagnostics) {
+        panic!("weapon_formulas.json failed validation, run `cargo run --bin validate_formulas` for details");
+    }
+
+    let jdata: WeaponFormulaJson = serde_json::from_str(&raw)
+        .unwrap_or_else(|err| panic!("error parsing weapon_formulas.json: {}", err));
 
     let mut handling_data: Vec<HandlingFormula> = Vec::new();
     let mut range_data: Vec<RangeFormula> = Vec::new();
@@ -247,15 +639,23 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
 
     let mut updated_weapon_defs: Vec<(WeaponPath, DataPointers)> = Vec::new();
 
+    //sorted by id so the library can binary search it
+    let mut reserve_data: Vec<(u32, ReserveLiteral)> = jdata
+        .reserves
+        .iter()
+        .map(|(id, reserve)| (id.parse::<u32>().unwrap(), ReserveLiteral(reserve)))
+        .collect();
+    reserve_data.sort_by_key(|(id, _)| *id);
+
     for (weapon_family, val) in jdata.types {
         for (weapon_hash, weapon_def) in val.intrinsics {
             let mut data = DataPointers::default();
             //add error handling
-            let cat: Category = *val.cat.get(&weapon_def.cat).unwrap();
-            let mag: AmmoFormula = *val.mag_prof.get(&weapon_def.mag_prof).unwrap();
-            let fam: FiringData = *val.sub_fam.get(&weapon_def.sub_fam).unwrap();
+            let cat = *val.cat.get(&weapon_def.cat).unwrap();
+            let mag: AmmoFormula = (*val.mag_prof.get(&weapon_def.mag_prof).unwrap()).into();
+            let fam: FiringData = (*val.sub_fam.get(&weapon_def.sub_fam).unwrap()).into();
 
-            let mut reload: ReloadFormula = cat.reload;
+            let mut reload: ReloadFormula = cat.reload.into();
 
             let index_option = find_uuid(&reload_data, &reload);
             if let Some(index) = index_option {
@@ -266,7 +666,7 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
                 reload_data.push(reload);
             }
 
-            let mut range: RangeFormula = cat.range;
+            let mut range: RangeFormula = cat.range.into();
             let index_option = find_uuid(&range_data, &range);
             if let Some(index) = index_option {
                 data.r = index;
@@ -276,7 +676,7 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
                 range_data.push(range);
             }
 
-            let mut handling: HandlingFormula = cat.handling;
+            let mut handling: HandlingFormula = cat.handling.into();
 
             let index_option = find_uuid(&handling_data, &handling);
             if let Some(index) = index_option {
@@ -287,8 +687,7 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
                 handling_data.push(handling);
             }
 
-            let mut scalar: DamageMods = cat.combatant_scalars;
-            scalar.pve = weapon_def.pve;
+            let mut scalar = DamageMods::from_json(cat.combatant_scalars, weapon_def.pve);
 
             let index_option = find_uuid(&scalar_data, &scalar);
             if let Some(index) = index_option {
@@ -383,17 +782,296 @@ fn construct_weapon_formulas(formula_file: &mut File, cached: &mut CachedBuildDa
         format!("{:?}", ammo_data),
         "Array of ammo formulas",
     );
+    write_variable(
+        formula_file,
+        "RESERVE_DATA",
+        &format!("[(u32, ReserveFormula); {}]", reserve_data.len()),
+        format!("{:?}", reserve_data),
+        "Reserve formulas sorted by reserve id",
+    );
+
+    //sorted by weapon type id so the library can binary search it
+    let mut type_scalar_data: Vec<(u32, DamageMods)> = jdata
+        .weapon_type_scalars
+        .iter()
+        .map(|(family, scalars)| {
+            let mut scalar = DamageMods::from_json(*scalars, 1.0);
+            scalar.timestamp = cached.get_timestamp(&scalar);
+            (*NAME_TO_ID.get(family.as_str()).unwrap() as u32, scalar)
+        })
+        .collect();
+    type_scalar_data.sort_by_key(|(id, _)| *id);
+    write_variable(
+        formula_file,
+        "WEAPON_TYPE_SCALAR_DATA",
+        &format!("[(u32, DamageMods); {}]", type_scalar_data.len()),
+        format!("{:?}", type_scalar_data),
+        "Combatant multipliers shared by a whole weapon type, sorted by weapon type id",
+    );
+
+    let mut sword_frame_data: Vec<(u32, SwordFrame)> = jdata
+        .sword_frames
+        .iter()
+        .map(|(hash, frame)| {
+            let mut frame = SwordFrame::from(*frame);
+            frame.timestamp = cached.get_timestamp(&frame);
+            (hash.parse::<u32>().unwrap(), frame)
+        })
+        .collect();
+    sword_frame_data.sort_by_key(|(hash, _)| *hash);
+    write_variable(
+        formula_file,
+        "SWORD_FRAME_DATA",
+        &format!("[(u32, SwordFrame); {}]", sword_frame_data.len()),
+        format!("{:?}", sword_frame_data),
+        "Sword frame data sorted by intrinsic hash",
+    );
+
+    let mut glaive_frame_data: Vec<(u32, GlaiveFrame)> = jdata
+        .glaive_frames
+        .iter()
+        .map(|(hash, frame)| {
+            let mut frame = GlaiveFrame::from(*frame);
+            frame.timestamp = cached.get_timestamp(&frame);
+            (hash.parse::<u32>().unwrap(), frame)
+        })
+        .collect();
+    glaive_frame_data.sort_by_key(|(hash, _)| *hash);
+    write_variable(
+        formula_file,
+        "GLAIVE_FRAME_DATA",
+        &format!("[(u32, GlaiveFrame); {}]", glaive_frame_data.len()),
+        format!("{:?}", glaive_frame_data),
+        "Glaive frame data sorted by intrinsic hash",
+    );
+
+    //sorted by weapon type id like the weapon type scalars
+    let mut blast_data: Vec<(u32, BlastFormula)> = jdata
+        .blast_radii
+        .iter()
+        .map(|(family, blast)| {
+            let mut blast = BlastFormula::from(*blast);
+            blast.timestamp = cached.get_timestamp(&blast);
+            (*NAME_TO_ID.get(family.as_str()).unwrap() as u32, blast)
+        })
+        .collect();
+    blast_data.sort_by_key(|(id, _)| *id);
+    write_variable(
+        formula_file,
+        "BLAST_DATA",
+        &format!("[(u32, BlastFormula); {}]", blast_data.len()),
+        format!("{:?}", blast_data),
+        "Explosion reach shared by a whole weapon type, sorted by weapon type id",
+    );
+
+    let mut cleave_data: Vec<(u32, f64)> = jdata
+        .cleave_radii
+        .iter()
+        .map(|(hash, cleave)| (hash.parse::<u32>().unwrap(), cleave.radius))
+        .collect();
+    cleave_data.sort_by_key(|(hash, _)| *hash);
+    write_variable(
+        formula_file,
+        "CLEAVE_DATA",
+        &format!("[(u32, f64); {}]", cleave_data.len()),
+        format!("{:?}", cleave_data),
+        "Meters extra damage perks cleave around the target that was shot, sorted by perk hash",
+    );
+
+    let mut brick_data: Vec<(u32, u32)> = jdata
+        .brick_sizes
+        .iter()
+        .map(|(family, rounds)| (*NAME_TO_ID.get(family.as_str()).unwrap() as u32, *rounds))
+        .collect();
+    brick_data.sort_by_key(|(id, _)| *id);
+    write_variable(
+        formula_file,
+        "BRICK_DATA",
+        &format!("[(u32, u32); {}]", brick_data.len()),
+        format!("{:?}", brick_data),
+        "Rounds in one ammo brick for a whole weapon type, sorted by weapon type id",
+    );
+
+    let mut bow_frame_data: Vec<(u32, BowFrame)> = jdata
+        .bow_frames
+        .iter()
+        .map(|(hash, frame)| {
+            let mut frame = BowFrame::from(*frame);
+            frame.timestamp = cached.get_timestamp(&frame);
+            (hash.parse::<u32>().unwrap(), frame)
+        })
+        .collect();
+    bow_frame_data.sort_by_key(|(hash, _)| *hash);
+    write_variable(
+        formula_file,
+        "BOW_FRAME_DATA",
+        &format!("[(u32, BowFrame); {}]", bow_frame_data.len()),
+        format!("{:?}", bow_frame_data),
+        "Bow draw data sorted by intrinsic hash",
+    );
+
+    let mut bow_string_data: Vec<(u32, f64)> = jdata
+        .bow_strings
+        .iter()
+        .map(|(hash, string)| (hash.parse::<u32>().unwrap(), string.draw_time_add))
+        .collect();
+    bow_string_data.sort_by_key(|(hash, _)| *hash);
+    write_variable(
+        formula_file,
+        "BOW_STRING_DATA",
+        &format!("[(u32, f64); {}]", bow_string_data.len()),
+        format!("{:?}", bow_string_data),
+        "Seconds a bow string adds to the draw, sorted by perk hash",
+    );
 }
 
-fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBuildData) {
-    let ping = reqwest::blocking::get("https://www.bungie.net");
-    let has_internet = if let Ok(ping) = ping {
+fn construct_difficulty_tables(formula_file: &mut impl Write) {
+    let path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("build_resources/difficulty_tables.json");
+    let raw = std::fs::read_to_string(path).unwrap();
+    let mut tables: Vec<DifficultyTableJson> = serde_json::from_str(&raw)
+        .unwrap_or_else(|err| panic!("error parsing difficulty_tables.json: {}", err));
+    tables.sort_by_key(|x| x.id);
+    for (i, table) in tables.iter().enumerate() {
+        if let Some(err) = table.check() {
+            panic!("difficulty_tables.json \"{}\": {}", table.name, err);
+        }
+        if tables[..i].iter().any(|x| x.id == table.id) {
+            panic!("difficulty_tables.json: duplicate id {}", table.id);
+        }
+    }
+    write_variable(
+        formula_file,
+        "DIFFICULTY_DATA",
+        &format!("[DifficultyTable; {}]", tables.len()),
+        format!(
+            "{:?}",
+            tables.iter().map(DifficultyLiteral).collect::<Vec<_>>()
+        ),
+        "Power delta tables for each difficulty sorted by id",
+    );
+}
+
+fn construct_activity_presets(formula_file: &mut impl Write) {
+    let path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("build_resources/activity_presets.json");
+    let raw = std::fs::read_to_string(path).unwrap();
+    let mut presets: Vec<ActivityPresetJson> = serde_json::from_str(&raw)
+        .unwrap_or_else(|err| panic!("error parsing activity_presets.json: {}", err));
+    //sorted by id so the library can binary search it
+    presets.sort_by(|a, b| a.id.cmp(&b.id));
+    for (i, preset) in presets.iter().enumerate() {
+        if i > 0 && presets[i - 1].id == preset.id {
+            panic!("activity_presets.json: duplicate id \"{}\"", preset.id);
+        }
+        if let Some(err) = preset.check() {
+            panic!("activity_presets.json \"{}\": {}", preset.id, err);
+        }
+    }
+    write_variable(
+        formula_file,
+        "ACTIVITY_PRESETS",
+        &format!("[ActivityPreset; {}]", presets.len()),
+        format!(
+            "{:?}",
+            presets.iter().map(PresetLiteral).collect::<Vec<_>>()
+        ),
+        "Named activities sorted by id",
+    );
+}
+
+fn construct_champions(formula_file: &mut impl Write) {
+    let path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("build_resources/champions.json");
+    let raw = std::fs::read_to_string(path).unwrap();
+    let mut champions: ChampionsJson = serde_json::from_str(&raw)
+        .unwrap_or_else(|err| panic!("error parsing champions.json: {}", err));
+    for stun in champions.stuns.iter() {
+        if let Some(err) = stun.check() {
+            panic!("champions.json \"{}\": {}", stun.name, err);
+        }
+    }
+    champions.stuns.sort_by_key(|x| x.hash);
+    champions.profiles.sort_by_key(|x| x.champion as u8);
+    for (i, profile) in champions.profiles.iter().enumerate() {
+        if i > 0 && champions.profiles[i - 1].champion == profile.champion {
+            panic!(
+                "champions.json: duplicate profile for {:?}",
+                profile.champion
+            );
+        }
+        if let Some(err) = profile.check() {
+            panic!("champions.json {:?}: {}", profile.champion, err);
+        }
+    }
+    write_variable(
+        formula_file,
+        "CHAMPION_STUNS",
+        &format!("[(u32, ChampionType); {}]", champions.stuns.len()),
+        format!(
+            "{:?}",
+            champions.stuns.iter().map(StunLiteral).collect::<Vec<_>>()
+        ),
+        "Weapons, intrinsics and perks that stun a champion type sorted by hash",
+    );
+    write_variable(
+        formula_file,
+        "CHAMPION_PROFILES",
+        &format!(
+            "[(ChampionType, ChampionProfile); {}]",
+            champions.profiles.len()
+        ),
+        format!(
+            "{:?}",
+            champions
+                .profiles
+                .iter()
+                .map(ProfileLiteral)
+                .collect::<Vec<_>>()
+        ),
+        "How each champion type fights until it's stunned",
+    );
+}
+
+fn construct_armor_stats(formula_file: &mut impl Write) {
+    let path = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("build_resources/armor_stats.json");
+    let raw = std::fs::read_to_string(path).unwrap();
+    let data: ArmorStatData = serde_json::from_str(&raw)
+        .unwrap_or_else(|err| panic!("error parsing armor_stats.json: {}", err));
+    if let Some(err) = data.check() {
+        panic!("armor_stats.json: {}", err);
+    }
+    write_variable(
+        formula_file,
+        "ARMOR_STAT_DATA",
+        "ArmorStatData",
+        format!("{:?}", data),
+        "Per tier tables and tier 0 cooldowns for armor stats",
+    );
+}
+
+fn construct_enhance_perk_mapping(
+    formula_file: &mut impl Write,
+    cached: &mut CachedBuildData,
+    offline: bool,
+) {
+    let has_internet = if offline {
+        false
+    } else if let Ok(ping) = reqwest::blocking::get("https://www.bungie.net") {
         ping.status().is_success()
     } else {
         false
     };
 
-    if !has_internet {
+    if offline {
+        println!("cargo:warning=offline build, using vendored manifest snapshot");
+        if !cached.has_data() {
+            panic!(
+                "cargo:warning=offline build requires a populated build_resources/cached_build.ron"
+            );
+        }
+    } else if !has_internet {
         println!("cargo:warning=no internet connection");
     }
 
@@ -401,22 +1079,24 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
     if has_internet {
         let json_file = reqwest::blocking::get(
             "https://raw.githubusercontent.com/DestinyItemManager/d2-additional-info/master/output/trait-to-enhanced-trait.json");
-        if json_file.is_ok() {
-            let json_file = json_file.unwrap();
-            let dct = json_file.json::<HashMap<String, u32>>();
-            if dct.is_ok() {
-                let dct = dct.unwrap();
-                for i in dct {
-                    perk_mappings.push((i.1, i.0.parse::<u32>().unwrap()));
+        match json_file {
+            Ok(json_file) => match json_file.json::<HashMap<String, u32>>() {
+                Ok(dct) => {
+                    for i in dct {
+                        perk_mappings.push((i.1, i.0.parse::<u32>().unwrap()));
+                    }
+                    perk_mappings.sort();
+                    cached.dim_perk_mappings = perk_mappings.clone();
                 }
-                cached.dim_perk_mappings = perk_mappings.clone();
-            } else {
-                println!("cargo:warning=dim enhanced mapping could not be parsed");
+                Err(_) => {
+                    println!("cargo:warning=dim enhanced mapping could not be parsed");
+                    return;
+                }
+            },
+            Err(_) => {
+                println!("cargo:warning=dim enhanced mapping not found");
                 return;
             }
-        } else {
-            println!("cargo:warning=dim enhanced mapping not found");
-            return;
         }
     } else if cached.has_data() {
         println!("cargo:warning=using cached dim enhanced mapping");
@@ -427,29 +1107,24 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
     }
 
     if has_internet {
-        let mut manifest_secured: bool;
-        let manifest_raw =
-            reqwest::blocking::get("https://www.bungie.net/Platform/Destiny2/Manifest/");
-        let manifest_text: String;
-        if manifest_raw.is_ok() {
-            manifest_text = manifest_raw.unwrap().text().unwrap();
-            manifest_secured = true;
-        } else {
-            manifest_text = String::from("");
-            println!("cargo:warning=bungie manifest raw error");
-            manifest_secured = false;
-        }
-        let manifest_json: Value;
-        if manifest_secured {
-            manifest_json = serde_json::from_str(&manifest_text).unwrap();
-            if manifest_json["ErrorCode"].as_i64().unwrap_or_default() as i32 != 1_i32 {
-                println!("cargo:warning=bungie manifest error code");
-                manifest_secured = false;
-            }
-        } else {
-            println!("cargo:warning=bungie manifest error");
-            manifest_json = Value::Null;
-        }
+        let manifest_json: Value =
+            match reqwest::blocking::get("https://www.bungie.net/Platform/Destiny2/Manifest/") {
+                Ok(manifest_raw) => {
+                    let manifest_json: Value =
+                        serde_json::from_str(&manifest_raw.text().unwrap()).unwrap();
+                    if manifest_json["ErrorCode"].as_i64().unwrap_or_default() as i32 != 1_i32 {
+                        println!("cargo:warning=bungie manifest error code");
+                        Value::Null
+                    } else {
+                        manifest_json
+                    }
+                }
+                Err(_) => {
+                    println!("cargo:warning=bungie manifest raw error");
+                    Value::Null
+                }
+            };
+        let manifest_secured = !manifest_json.is_null();
 
         if !manifest_secured && cached.has_data() {
             println!("cargo:warning=using cached manifest");
@@ -460,7 +1135,11 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
         }
 
         if manifest_secured {
-            if manifest_json["Response"]["version"] == cached.last_manifest_version {
+            //older snapshots predate the item and stat group tables, refetch once to fill them in
+            if manifest_json["Response"]["version"] == cached.last_manifest_version
+                && !cached.weapon_items.is_empty()
+                && !cached.stat_groups.is_empty()
+            {
                 let mut cached_manifest_mappings = cached.procedural_intrinsic_mappings.clone();
                 perk_mappings.append(&mut cached_manifest_mappings);
             } else {
@@ -468,6 +1147,7 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
                     .as_str()
                     .unwrap()
                     .to_owned();
+                cached.last_manifest_fetch = cached.build_epoch;
                 let content_paths = manifest_json["Response"]["jsonWorldComponentContentPaths"]
                     ["en"]
                     .as_object()
@@ -480,10 +1160,15 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
                 ));
                 println!("cargo:warning=downloaded new manifest");
                 cached.procedural_intrinsic_mappings.clear();
+                cached.weapon_items.clear();
                 let item_data_json: Value =
                     serde_json::from_str(&item_data_raw.unwrap().text().unwrap()).unwrap();
                 for (key, value) in item_data_json.as_object().unwrap() {
                     let hash = key.parse::<u32>().unwrap();
+                    if let Some(item) = CachedItem::from_manifest(hash, value) {
+                        cached.weapon_items.push(item);
+                        continue;
+                    }
                     //does value have a key called itemTypeDisplayName?
                     if !value
                         .as_object()
@@ -508,12 +1193,38 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
                         }
                     }
                 }
+                cached.procedural_intrinsic_mappings.sort();
+
+                let stat_group_raw = reqwest::blocking::get(format!(
+                    "https://www.bungie.net{}",
+                    content_paths["DestinyStatGroupDefinition"]
+                        .as_str()
+                        .unwrap()
+                ));
+                let stat_group_json: Value =
+                    serde_json::from_str(&stat_group_raw.unwrap().text().unwrap()).unwrap();
+                //only keep the groups weapons actually use
+                let used_groups: HashSet<u32> =
+                    cached.weapon_items.iter().map(|x| x.stat_group).collect();
+                cached.stat_groups = stat_group_json
+                    .as_object()
+                    .unwrap()
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let hash = key.parse::<u32>().ok()?;
+                        used_groups
+                            .contains(&hash)
+                            .then(|| CachedStatGroup::from_manifest(hash, value))
+                    })
+                    .collect();
+                cached.stat_groups.sort_by_key(|x| x.hash);
             }
         }
     } else {
         let mut cached_manifest_mappings = cached.procedural_intrinsic_mappings.clone();
         perk_mappings.append(&mut cached_manifest_mappings);
     }
+    perk_mappings.sort();
     write_variable(
         formula_file,
         "ENHANCE_PERK_MAPPING",
@@ -524,9 +1235,8 @@ fn construct_enhance_perk_mapping(formula_file: &mut File, cached: &mut CachedBu
 }
 
 //these types reflect whats in src/types/rs_types.rs
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct StatQuadraticFormula {
-    #[serde(default)]
     pub evpp: f64,
     pub vpp: f64,
     pub offset: f64,
@@ -540,9 +1250,18 @@ impl Hash for StatQuadraticFormula {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+impl From<StatQuadraticJson> for StatQuadraticFormula {
+    fn from(value: StatQuadraticJson) -> Self {
+        StatQuadraticFormula {
+            evpp: value.evpp,
+            vpp: value.vpp,
+            offset: value.offset,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct DamageMods {
-    #[serde(default)]
     pub pve: f64,
     pub minor: f64,
     pub elite: f64,
@@ -550,7 +1269,6 @@ pub struct DamageMods {
     pub champion: f64,
     pub boss: f64,
     pub vehicle: f64,
-    #[serde(default)]
     pub timestamp: u64,
 }
 
@@ -566,17 +1284,151 @@ impl Hash for DamageMods {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
-#[serde(try_from = "RangeJson")]
+impl DamageMods {
+    fn from_json(value: DamageModsJson, pve: f64) -> Self {
+        DamageMods {
+            pve,
+            minor: value.minor,
+            elite: value.elite,
+            miniboss: value.miniboss,
+            champion: value.champion,
+            boss: value.boss,
+            vehicle: value.vehicle,
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SwordFrame {
+    pub combo: i32,
+    pub finisher_scale: f64,
+    pub heavy_scale: f64,
+    pub heavy_time: f64,
+    pub swing_speed: StatQuadraticFormula,
+    pub energy_regen: StatQuadraticFormula,
+    pub guard_resistance: StatQuadraticFormula,
+    pub guard_efficiency: StatQuadraticFormula,
+    pub timestamp: u64,
+}
+
+impl Hash for SwordFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.combo.hash(state);
+        self.finisher_scale.partial_hash(state);
+        self.heavy_scale.partial_hash(state);
+        self.heavy_time.partial_hash(state);
+        self.swing_speed.hash(state);
+        self.energy_regen.hash(state);
+        self.guard_resistance.hash(state);
+        self.guard_efficiency.hash(state);
+    }
+}
+
+impl From<SwordFrameJson> for SwordFrame {
+    fn from(value: SwordFrameJson) -> Self {
+        SwordFrame {
+            combo: value.combo,
+            finisher_scale: value.finisher_scale,
+            heavy_scale: value.heavy_scale,
+            heavy_time: json_types::frames_to_seconds(value.heavy_delay),
+            swing_speed: value.swing_speed.into(),
+            energy_regen: value.energy_regen.into(),
+            guard_resistance: value.guard_resistance.into(),
+            guard_efficiency: value.guard_efficiency.into(),
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlaiveFrame {
+    pub melee_scale: f64,
+    pub melee_time: f64,
+    pub melees_per_shot: i32,
+    pub shield_resistance_pve: f64,
+    pub shield_resistance_pvp: f64,
+    pub timestamp: u64,
+}
+
+impl Hash for GlaiveFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.melee_scale.partial_hash(state);
+        self.melee_time.partial_hash(state);
+        self.melees_per_shot.hash(state);
+        self.shield_resistance_pve.partial_hash(state);
+        self.shield_resistance_pvp.partial_hash(state);
+    }
+}
+
+impl From<GlaiveFrameJson> for GlaiveFrame {
+    fn from(value: GlaiveFrameJson) -> Self {
+        GlaiveFrame {
+            melee_scale: value.melee_scale,
+            melee_time: json_types::frames_to_seconds(value.melee_delay),
+            melees_per_shot: value.melees_per_shot,
+            shield_resistance_pve: value.shield_resistance_pve,
+            shield_resistance_pvp: value.shield_resistance_pvp,
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BowFrame {
+    pub draw_time: StatQuadraticFormula,
+    pub perfect_draw: Option<StatQuadraticFormula>,
+    pub timestamp: u64,
+}
+
+impl Hash for BowFrame {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.draw_time.hash(state);
+        self.perfect_draw.hash(state);
+    }
+}
+
+impl From<BowFrameJson> for BowFrame {
+    fn from(value: BowFrameJson) -> Self {
+        BowFrame {
+            draw_time: value.draw_time.into(),
+            perfect_draw: value.perfect_draw.map(Into::into),
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlastFormula {
+    pub radius: StatQuadraticFormula,
+    pub edge_damage: f64,
+    pub timestamp: u64,
+}
+
+impl Hash for BlastFormula {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.radius.hash(state);
+        self.edge_damage.partial_hash(state);
+    }
+}
+
+impl From<BlastJson> for BlastFormula {
+    fn from(value: BlastJson) -> Self {
+        BlastFormula {
+            radius: value.radius.into(),
+            edge_damage: value.edge_damage,
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct RangeFormula {
     pub start: StatQuadraticFormula,
     pub end: StatQuadraticFormula,
     pub floor_percent: f64,
-    #[serde(default)]
     pub pve_floor_percent: Option<f64>,
-    #[serde(default)]
     pub fusion: bool,
-    #[serde(default)]
     pub timestamp: u64,
 }
 
@@ -612,13 +1464,10 @@ impl From<RangeJson> for RangeFormula {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct ReloadFormula {
-    #[serde(flatten)]
     pub reload_data: StatQuadraticFormula,
-    #[serde(default)]
     pub ammo_percent: f64,
-    #[serde(default)]
     pub timestamp: u64,
 }
 
@@ -629,12 +1478,21 @@ impl Hash for ReloadFormula {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+impl From<ReloadJson> for ReloadFormula {
+    fn from(value: ReloadJson) -> Self {
+        ReloadFormula {
+            reload_data: value.reload_data.into(),
+            ammo_percent: value.ammo_percent,
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct HandlingFormula {
     pub ready: StatQuadraticFormula,
     pub stow: StatQuadraticFormula,
     pub ads: StatQuadraticFormula,
-    #[serde(default)]
     pub timestamp: u64,
 }
 
@@ -646,14 +1504,22 @@ impl Hash for HandlingFormula {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+impl From<HandlingJson> for HandlingFormula {
+    fn from(value: HandlingJson) -> Self {
+        HandlingFormula {
+            ready: value.ready.into(),
+            stow: value.stow.into(),
+            ads: value.ads.into(),
+            timestamp: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct AmmoFormula {
     pub mag: StatQuadraticFormula,
-    #[serde(default)]
     pub round_to: i32,
-    #[serde(default)]
     pub reserve_id: u32,
-    #[serde(default)]
     pub timestamp: u64,
 }
 
@@ -665,12 +1531,86 @@ impl Hash for AmmoFormula {
     }
 }
 
-fn default_i32_1() -> i32 {
-    1
+impl From<AmmoJson> for AmmoFormula {
+    fn from(value: AmmoJson) -> Self {
+        AmmoFormula {
+            mag: value.mag.into(),
+            round_to: value.round_to,
+            reserve_id: value.reserve_id,
+            timestamp: 0,
+        }
+    }
+}
+
+//written as a ReserveFormula literal, tables become borrowed static slices
+struct ReserveLiteral<'a>(&'a ReserveJson);
+impl Debug for ReserveLiteral<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            ReserveJson::Constant { value } => {
+                write!(f, "ReserveFormula::Constant {{ value: {:?} }}", value)
+            }
+            ReserveJson::Linear {
+                vpp,
+                offset,
+                full_mag,
+            } => write!(
+                f,
+                "ReserveFormula::Linear {{ vpp: {:?}, offset: {:?}, full_mag: {:?} }}",
+                vpp,
+                offset,
+                full_mag.map(|x| (x.vpp, x.offset))
+            ),
+            ReserveJson::PerMag { vpp, offset } => write!(
+                f,
+                "ReserveFormula::PerMag {{ vpp: {:?}, offset: {:?} }}",
+                vpp, offset
+            ),
+            ReserveJson::MagTable {
+                offsets,
+                default,
+                inv_mult,
+                bonus,
+            } => write!(
+                f,
+                "ReserveFormula::MagTable {{ offsets: Cow::Borrowed(&{:?}), default: {:?}, inv_mult: {:?}, bonus: {:?} }}",
+                offsets, default, inv_mult, bonus
+            ),
+            ReserveJson::Exact { values, default } => write!(
+                f,
+                "ReserveFormula::Exact {{ values: Cow::Borrowed(&{:?}), default: {:?} }}",
+                values, default
+            ),
+            ReserveJson::Steps { steps, default } => write!(
+                f,
+                "ReserveFormula::Steps {{ steps: Cow::Borrowed(&{:?}), default: {:?} }}",
+                steps, default
+            ),
+            ReserveJson::MagRounding {
+                base,
+                offset,
+                round_scale,
+                mag_stat_scale,
+                inv_mult,
+            } => write!(
+                f,
+                "ReserveFormula::MagRounding {{ base: {:?}, offset: {:?}, round_scale: {:?}, mag_stat_scale: {:?}, inv_mult: {:?} }}",
+                base, offset, round_scale, mag_stat_scale, inv_mult
+            ),
+            ReserveJson::MagStatSteps {
+                steps,
+                default,
+                inv_mult,
+            } => write!(
+                f,
+                "ReserveFormula::MagStatSteps {{ steps: Cow::Borrowed(&{:?}), default: {:?}, inv_mult: {:?} }}",
+                steps, default, inv_mult
+            ),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
-#[serde(from = "SubFamJson")]
+#[derive(Debug, Clone, Copy, Default)]
 pub struct FiringData {
     pub damage: f64,
     pub crit_mult: f64,
@@ -678,13 +1618,9 @@ pub struct FiringData {
     pub pve_crit_mult: f64,
     pub burst_delay: f64,
     pub inner_burst_delay: f64,
-    #[serde(default)]
     pub burst_size: i32,
-    #[serde(default)]
     pub one_ammo: bool,
-    #[serde(default)]
     pub charge: bool,
-    #[serde(default)]
     pub timestamp: u64,
 }
 
@@ -692,11 +1628,11 @@ impl From<SubFamJson> for FiringData {
     fn from(value: SubFamJson) -> Self {
         FiringData {
             damage: value.damage,
-            crit_mult: (value.crit_mult) / 51.0 + 1.5,
+            crit_mult: json_types::crit_mult_from_json(value.crit_mult),
             pve_damage: value.pve_damage,
-            pve_crit_mult: (value.pve_crit_mult) / 51.0 + 1.5,
-            burst_delay: value.burst_delay / 30.0,
-            inner_burst_delay: value.inner_burst_delay / 30.0,
+            pve_crit_mult: json_types::crit_mult_from_json(value.pve_crit_mult),
+            burst_delay: json_types::frames_to_seconds(value.burst_delay),
+            inner_burst_delay: json_types::frames_to_seconds(value.inner_burst_delay),
             burst_size: value.burst_size,
             one_ammo: value.one_ammo.unwrap_or_default(),
             charge: value.charge.unwrap_or_default(),
@@ -727,67 +1663,47 @@ struct DataPointers {
     a: usize,
 }
 
-#[derive(Clone, Deserialize)]
-struct WeaponFormulaJson {
-    #[serde(flatten)]
-    types: HashMap<String, WeaponFormula>,
-}
-
-#[derive(Clone, Deserialize)]
-struct WeaponFormula {
-    #[serde(flatten)]
-    intrinsics: HashMap<String, WeaponIntrinsic>,
-    cat: HashMap<String, Category>,
-    #[serde(rename = "subFam")]
-    sub_fam: HashMap<String, FiringData>,
-    #[serde(rename = "magProf")]
-    mag_prof: HashMap<String, AmmoFormula>,
-}
-
-const fn default_pve() -> f64 {
-    1.0
+//written as an ActivityPreset literal
+struct PresetLiteral<'a>(&'a ActivityPresetJson);
+impl Debug for PresetLiteral<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let preset = self.0;
+        write!(
+            f,
+            "ActivityPreset {{ id: {:?}, name: {:?}, rpl: {}, cap: {}, difficulty: DifficultyOptions::{:?}, enemy_type: EnemyType::{:?}, health: {:?}, estimate: {} }}",
+            preset.id, preset.name, preset.rpl, preset.cap, preset.difficulty, preset.enemy_type, preset.health, preset.estimate
+        )
+    }
 }
 
-#[derive(Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct WeaponIntrinsic {
-    cat: String,
-    sub_fam: String,
-    mag_prof: String,
-    #[serde(default = "default_pve")]
-    pve: f64,
+//written as a (hash, ChampionType) literal
+struct StunLiteral<'a>(&'a ChampionStunJson);
+impl Debug for StunLiteral<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, ChampionType::{:?})", self.0.hash, self.0.champion)
+    }
 }
 
-#[derive(Clone, Copy, Deserialize)]
-struct Category {
-    range: RangeFormula,
-    reload: ReloadFormula,
-    handling: HandlingFormula,
-    combatant_scalars: DamageMods,
+//written as a (ChampionType, ChampionProfile) literal
+struct ProfileLiteral<'a>(&'a ChampionProfileJson);
+impl Debug for ProfileLiteral<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "(ChampionType::{:?}, ChampionProfile {{ resistance: {:?}, stunned_resistance: {:?}, regen: {:?}, immune_thresholds: Cow::Borrowed(&{:?}), immune_time: {:?}, stun_time: {:?} }})",
+            self.0.champion, self.0.resistance, self.0.stunned_resistance, self.0.regen, self.0.immune_thresholds, self.0.immune_time, self.0.stun_time
+        )
+    }
 }
 
-#[derive(Clone, Copy, Deserialize)]
-struct RangeJson {
-    vpp_start: f64,
-    offset_start: f64,
-    vpp_end: f64,
-    offset_end: f64,
-    floor_percent: f64,
-    #[serde(default)]
-    pve_floor_percent: Option<f64>,
-    fusion: Option<bool>,
-}
-
-#[derive(Clone, Copy, Deserialize)]
-struct SubFamJson {
-    damage: f64,
-    crit_mult: f64,
-    pve_damage: f64,
-    pve_crit_mult: f64,
-    burst_delay: f64,
-    #[serde(default = "default_i32_1")]
-    burst_size: i32,
-    inner_burst_delay: f64,
-    one_ammo: Option<bool>,
-    charge: Option<bool>,
+//written as a DifficultyTable literal
+struct DifficultyLiteral<'a>(&'a DifficultyTableJson);
+impl Debug for DifficultyLiteral<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "DifficultyTable {{ id: {}, name: Cow::Borrowed({:?}), estimate: {}, cap: {}, table: Cow::Borrowed(&{:?}) }}",
+            self.0.id, self.0.name, self.0.estimate, self.0.cap, self.0.table
+        )
+    }
 }
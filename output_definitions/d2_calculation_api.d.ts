@@ -1,56 +1,75 @@
-type Hash = number;
-/**
-*/
-export function start(): void;
+// generated by `UPDATE_TS_DEFINITIONS=1 cargo test`, do not edit
 /**
 * @returns {MetaData}
 */
 export function getMetadata(): MetaData;
 /**
-* @returns {string}
-*/
-export function stringifyWeapon(): string;
-/**
-*Returns the weapon as a JSON structure, snake case fields
-* @returns {any}
+* @returns {Weapon}
 */
-export function weaponJSON(): any;
+export function stringifyWeapon(): Weapon;
 /**
-* @param {Hash} _hash
+* @param {number} _hash
 * @param {number} _weapon_type_id
-* @param {Hash} _intrinsic_hash
+* @param {number} _intrinsic_hash
 * @param {number} _ammo_type_id
 * @param {number} _damage_type_id
 */
-export function setWeapon(_hash: Hash, _weapon_type_id: number, _intrinsic_hash: Hash, _ammo_type_id: number, _damage_type_id: number): void;
+export function setWeapon(_hash: number, _weapon_type_id: number, _intrinsic_hash: number, _ammo_type_id: number, _damage_type_id: number): void;
+/**
+*Sets the weapon from its item hash alone, type, intrinsic, ammo, damage type and base stats
+*all come from the manifest snapshot baked in at build time
+* @param {number} _hash
+*/
+export function setWeaponFromItem(_hash: number): void;
+/**
+*Loads a weapon_formulas.json style document on top of the built in formulas,
+*changed formulas are stamped with _timestamp
+* @param {string} _json
+* @param {number} _timestamp
+* @returns {FormulaOverrideReport}
+*/
+export function loadFormulaOverrides(_json: string, _timestamp: number): FormulaOverrideReport;
+/**
+*/
+export function resetFormulaOverrides(): void;
+/**
+* @returns {Map<number, StatValues>}
+*/
+export function getStats(): Map<number, StatValues>;
 /**
-* @returns {Map<Hash, Stat>}
+* @param {Map<number, number>} _stats
 */
-export function getStats(): Map<Hash, Stat>;
+export function setStats(_stats: Map<number, number>): void;
 /**
-* @param {Map<Hash, number>} _stats
+*Takes investment stats instead of displayed ones, the engine handles the stat group interpolation.
+*Trait stat buffs are treated as investment bumps for these stats
+* @param {number} _stat_group
+* @param {Map<number, number>} _stats
 */
-export function setStats(_stats: Map<Hash, number>): void;
+export function setInvestmentStats(_stat_group: number, _stats: Map<number, number>): void;
 /**
-* @param {Map<Hash, number>} _stats
+* @param {Map<number, number>} _stats
 * @param {number} _value
 * @param {number} _hash
 */
-export function addTrait(_stats: Map<Hash, number>, _value: number, _hash: number): void;
+export function addTrait(_stats: Map<number, number>, _value: number, _hash: number): void;
 /**
-* @returns {Array<Hash>}
 */
-export function getTraitHashes(): Array<Hash>;
+export function resetTraits(): void;
+/**
+* @returns {Uint32Array}
+*/
+export function getTraitHashes(): Uint32Array;
 /**
 * @param {number} perk_hash
 * @param {number} new_value
 */
-export function setTraitValue(perk_hash: Hash, new_value: number): void;
+export function setTraitValue(perk_hash: number, new_value: number): void;
 /**
 * @param {Uint32Array} _perks
-* @returns {Map<Hash, PerkOptionData>}
+* @returns {Map<number, PerkOptionData>}
 */
-export function getTraitOptions(_perks: Uint32Array): Map<Hash, PerkOptionData>;
+export function getTraitOptions(_perks: Uint32Array): Map<number, PerkOptionData>;
 /**
 * @param {boolean} _dynamic_traits
 * @param {boolean} _pvp
@@ -76,15 +95,97 @@ export function getWeaponReloadTimes(_dynamic_traits: boolean, _pvp: boolean): R
 */
 export function getWeaponAmmoSizes(_dynamic_traits: boolean, _pvp: boolean): AmmoResponse;
 /**
-* @param {number} _overhsield
-* @returns {TtkResponse}
+*Reload, handling, range and ammo at every stat value from 0 to 100, with and without
+*dynamic traits
+* @param {boolean} _pvp
+* @returns {StatCurveSet}
 */
-export function getWeaponTtk(_overhsield: number): Array<ResillienceTtkSummary>;
+export function getStatCurves(_pvp: boolean): StatCurveSet;
 /**
-* @param {boolean} _use_rpl
-* @returns {DpsResponse}
+*Same as getStatCurves, one row per stat value
+* @param {boolean} _pvp
+* @returns {string}
 */
-export function getWeaponDps(_use_rpl: boolean): DpsResponse;
+export function getStatCurvesCsv(_pvp: boolean): string;
+/**
+*Diffs every calculated value for two or more weapons against the first, using the current
+*encounter for pve damage and dps. The current weapon is untouched
+* @param {Array<WeaponConfig>} _weapons
+* @param {boolean} _dynamic_traits
+* @param {boolean} _pvp
+* @returns {WeaponComparison}
+*/
+export function compareWeapons(_weapons: Array<WeaponConfig>, _dynamic_traits: boolean, _pvp: boolean): WeaponComparison;
+/**
+*Scores every combination of one perk per column on top of the current weapon and its
+*traits, best first
+* @param {Array<Array<PerkConfig>>} _columns
+* @param {RollRankingOptions} _options
+* @returns {RollRanking}
+*/
+export function rankRolls(_columns: Array<Array<PerkConfig>>, _options: RollRankingOptions): RollRanking;
+/**
+*Same as rankRolls, written out as DIM wishlist lines for the current weapon's hash
+* @param {Array<Array<PerkConfig>>} _columns
+* @param {RollRankingOptions} _options
+* @returns {string}
+*/
+export function exportWishlist(_columns: Array<Array<PerkConfig>>, _options: RollRankingOptions): string;
+/**
+*`_distance` is in meters and only matters for projectiles, defaults to point blank.
+*`_damage_resistance` is the target's, like the `shield_damage_resistance` misc stat of a
+*glaive they hold up. `_resilience` narrows the result to the target's tier, every tier
+*from 0 to 10 is returned without it
+* @param {number} _overshield
+* @param {number | undefined} _distance
+* @param {number | undefined} _damage_resistance
+* @param {number | undefined} _resilience
+* @returns {Array<ResillienceTtkSummary>}
+*/
+export function getWeaponTtk(_overshield: number, _distance?: number, _damage_resistance?: number, _resilience?: number): Array<ResillienceTtkSummary>;
+/**
+*Shoots a line of `_count` copies of the current enemy standing `_spacing` meters apart,
+*each with `_health` health
+* @param {number} _count
+* @param {number} _spacing
+* @param {number} _health
+* @returns {GroupDamageResponse}
+*/
+export function getGroupDamage(_count: number, _spacing: number, _health: number): GroupDamageResponse;
+/**
+*Sets the artifact mods that let weapon types stun champions
+* @param {Array<ChampionMod>} _mods
+*/
+export function setChampionMods(_mods: Array<ChampionMod>): void;
+/**
+*Time to kill a champion with `_health` as each champion type that has a profile,
+*with and without stuns
+* @param {number} _health
+* @returns {Array<ChampionTtk>}
+*/
+export function getChampionTtk(_health: number): Array<ChampionTtk>;
+/**
+*Fights the current enemy through an encounter's damage phases until the encounter's health is gone
+* @param {Encounter} _encounter
+* @returns {EncounterResponse}
+*/
+export function simulateEncounter(_encounter: Encounter): EncounterResponse;
+/**
+*Burst and sustained DPS and ammo economy against the current enemy
+* @returns {DpsMetrics}
+*/
+export function getDpsMetrics(): DpsMetrics;
+/**
+*Sets the player's class and armor stat totals
+* @param {PlayerClass} _class
+* @param {ArmorStats} _stats
+*/
+export function setArmorStats(_class: PlayerClass, _stats: ArmorStats): void;
+/**
+*Cooldowns, regen, walk speed and resilience from the player's armor stat tiers
+* @returns {ArmorStatSummary}
+*/
+export function getArmorStats(): ArmorStatSummary;
 /**
 * @param {boolean} _dynamic_traits
 * @param {boolean} _pvp
@@ -93,419 +194,763 @@ export function getWeaponDps(_use_rpl: boolean): DpsResponse;
 */
 export function getWeaponFiringData(_dynamic_traits: boolean, _pvp: boolean, _use_rpl: boolean): FiringResponse;
 /**
-* @param {number} _rpl
+*`_resilience` is a tier, defaults to the one from `setArmorStats`
+* @param {boolean} _dynamic_traits
+* @param {boolean} _pvp
+* @param {number | undefined} _resilience
+* @returns {number}
+*/
+export function getWeaponFlinch(_dynamic_traits: boolean, _pvp: boolean, _resilience?: number): number;
+/**
+* @param {boolean} _dynamic_traits
+* @param {boolean} _pvp
+* @returns {Map<string, number>}
+*/
+export function getMiscData(_dynamic_traits: boolean, _pvp: boolean): Map<string, number>;
+/**
+* @param {number} _recommend_pl
+* @param {number} _player_pl
+* @param {number} _weapon_pl
 * @param {number} _override_cap
-* @param {number} _difficulty
-* @param {number} _enemy_type
+* @param {DifficultyOptions} _difficulty
+* @param {EnemyType} _enemy_type
+* @param {number | undefined} _target_distance
+*/
+export function setEncounter(_recommend_pl: number, _player_pl: number, _weapon_pl: number, _override_cap: number, _difficulty: DifficultyOptions, _enemy_type: EnemyType, _target_distance?: number): void;
+/**
+*Replaces the current enemy's damage resistance with `_damage_resistances` stacked together,
+*like the `shield_damage_resistance` misc stat of a glaive they hold up and a buff
+* @param {Float64Array} _damage_resistances
+*/
+export function setEnemyDamageResistance(_damage_resistances: Float64Array): void;
+/**
+*Runs a corpus of reference values through the calculators, the current weapon is untouched
+* @param {string} _corpus
+* @returns {CorpusReport}
+*/
+export function runReferenceCorpus(_corpus: string): CorpusReport;
+/**
+*Fits observed damage numbers against the current weapon, solving for whichever of
+*_base_damage and _perk_multiplier is left undefined
+* @param {Array<DamageObservation>} _observations
+* @param {number | undefined} _base_damage
+* @param {number | undefined} _perk_multiplier
+* @returns {DamageSolution}
+*/
+export function solveDamage(_observations: Array<DamageObservation>, _base_damage?: number, _perk_multiplier?: number): DamageSolution;
+/**
+*Sets the encounter from a named activity, see getActivityPresets for the ids
+* @param {string} _id
+*/
+export function setActivityPreset(_id: string): void;
+/**
+* @returns {Array<ActivityPreset>}
+*/
+export function getActivityPresets(): Array<ActivityPreset>;
+/**
+*Loads difficulty_tables.json style power delta tables on top of the built in ones,
+*returns the difficulty ids that were loaded
+* @param {string} _json
+* @returns {Uint32Array}
+*/
+export function loadDifficultyTables(_json: string): Uint32Array;
+/**
+*/
+export function resetDifficultyTables(): void;
+/**
+*Loads champions.json style champion profiles on top of the built in ones,
+*returns the champion types that were loaded
+* @param {string} _json
+* @returns {Array<ChampionType>}
+*/
+export function loadChampionProfiles(_json: string): Array<ChampionType>;
+/**
+*/
+export function resetChampionProfiles(): void;
+/**
+*Replaces the built in armor stat data with an armor_stats.json style document
+* @param {string} _json
+*/
+export function loadArmorStatData(_json: string): void;
+/**
+*/
+export function resetArmorStatData(): void;
+/**
+* @param {number} _level
+*/
+export function setLoggingLevel(_level: number): void;
+/**
+* @param {boolean} _dynamic_traits
+* @param {boolean} _pvp
+* @returns {Map<number, ModifierResponseSummary>}
+*/
+export function getModifierResponseSummary(_dynamic_traits: boolean, _pvp: boolean): Map<number, ModifierResponseSummary>;
+/**
+*Breakdown of the pve damage multiplier against the current encounter's combatant type
+* @returns {CombatantScaling}
 */
-export function setEncounter(_rpl: number, _override_cap: number, _difficulty: number, _enemy_type: number): void;
+export function getCombatantScaling(): CombatantScaling;
+/**
+* @param {boolean} _pvp
+* @returns {ScalarResponseSummary}
+*/
+export function getScalarResponseSummary(_pvp: boolean): ScalarResponseSummary;
 /**
 */
 export enum DifficultyOptions {
-  NORMAL,
-  RAID,
-  MASTER,
+  NORMAL = 1,
+  RAID = 2,
+  MASTER = 3,
+  GRANDMASTER = 4,
+  CONTEST = 5,
+  LEGEND = 6,
 }
 /**
 */
 export enum EnemyType {
-  MINOR,
-  ELITE,
-  MINIBOSS,
-  BOSS,
-  VEHICLE,
-  ENCLAVE,
-  PLAYER,
-  CHAMPION,
+  MINOR = 0,
+  ELITE = 1,
+  MINIBOSS = 2,
+  BOSS = 3,
+  VEHICLE = 4,
+  ENCLAVE = 5,
+  PLAYER = 6,
+  CHAMPION = 7,
 }
 /**
 */
-export class AmmoResponse {
-  /**
-  ** Return copy of self without private attributes.
-  */
+export class HandlingResponse {
+  free(): void;
   toJSON(): Object;
-  /**
-  * Return stringified version of self.
-  */
   toString(): string;
-  free(): void;
   /**
   */
-  readonly magSize: number;
-  /**
-  */
-  readonly reserveSize: number;
-}
-/**
-*/
-export class PerkOptionData {
+  readonly readyTime: number;
   /**
   */
-  stacks: Array<number>;
+  readonly stowTime: number;
   /**
   */
-  options: Array<string>;
+  readonly adsTime: number;
   /**
   */
-  optionType: string;
+  readonly timestamp: number;
 }
 /**
 */
-export class OptimalKillData {
+export class RangeResponse {
+  free(): void;
+  toJSON(): Object;
+  toString(): string;
   /**
-  ** Return copy of self without private attributes.
   */
-  toJSON(): Object;
+  readonly hipFalloffStart: number;
   /**
-  * Return stringified version of self.
   */
-  toString(): string;
-  free(): void;
+  readonly hipFalloffEnd: number;
   /**
   */
-  readonly achievableRange: number;
+  readonly adsFalloffStart: number;
   /**
   */
-  readonly bodyshots: number;
+  readonly adsFalloffEnd: number;
   /**
   */
-  readonly headshots: number;
+  readonly floorPercent: number;
   /**
   */
-  readonly timeTaken: number;
+  readonly timestamp: number;
 }
 /**
 */
-export class BodyKillData {
-  /**
-  ** Return copy of self without private attributes.
-  */
+export class ReloadResponse {
+  free(): void;
   toJSON(): Object;
+  toString(): string;
   /**
-  * Return stringified version of self.
   */
-  toString(): string;
-  free(): void;
+  readonly reloadTime: number;
   /**
   */
-  readonly bodyshots: number;
+  readonly ammoTime: number;
   /**
   */
-  readonly timeTaken: number;
+  readonly timestamp: number;
 }
 /**
 */
-export class ResillienceTtkSummary {
-  /**
-  ** Return copy of self without private attributes.
-  */
+export class AmmoResponse {
+  free(): void;
   toJSON(): Object;
-  /**
-  * Return stringified version of self.
-  */
   toString(): string;
-  free(): void;
   /**
   */
-  readonly bodyTtk: BodyKillData;
+  readonly magSize: number;
   /**
   */
-  readonly optimalTtk: OptimalKillData;
+  readonly reserveSize: number;
   /**
   */
-  readonly resillienceValue: number;
+  readonly timestamp: number;
 }
 /**
 */
 export class DpsResponse {
   free(): void;
   /**
-  * Return stringified version of self.
   */
-  toString(): string;
+  readonly totalDamage: number;
+  /**
+  */
+  readonly totalTime: number;
+  /**
+  */
+  readonly totalShots: number;
   /**
-  ** Return copy of self without private attributes.
   */
   toJSON(): string;
   /**
+  *Returns a list of tuples of time and damage
+  */
+  readonly timeDamageData: Array<[number, number]>;
+  /**
   *Returns a list of dps values for each magazine
   */
   readonly dpsPerMag: Array<number>;
+}
+/**
+*/
+export class OptimalKillData {
+  free(): void;
+  toJSON(): Object;
+  toString(): string;
   /**
-  *Returns a list of tuples of time and damage
   */
-  readonly timeDamageData: Array<Array<number>>;
+  headshots: number;
   /**
   */
-  readonly totalDamage: number;
+  bodyshots: number;
   /**
   */
-  readonly totalShots: number;
+  timeTaken: number;
+}
+/**
+*/
+export class BodyKillData {
+  free(): void;
+  toJSON(): Object;
+  toString(): string;
   /**
   */
-  readonly totalTime: number;
+  bodyshots: number;
+  /**
+  */
+  timeTaken: number;
 }
 /**
 */
-export class FiringResponse {
+export class ResillienceSummary {
+  free(): void;
+  toJSON(): Object;
+  toString(): string;
   /**
-  ** Return copy of self without private attributes.
   */
-  toJSON(): Object;
+  resillienceValue: number;
   /**
-  * Return stringified version of self.
   */
-  toString(): string;
+  bodyTtk: BodyKillData;
+  /**
+  */
+  optimalTtk: OptimalKillData;
+}
+/**
+*/
+export class FiringResponse {
   free(): void;
+  toJSON(): Object;
+  toString(): string;
   /**
   */
-  readonly burstDelay: number;
+  readonly pvpImpactDamage: number;
   /**
   */
-  readonly burstSize: number;
+  readonly pvpExplosionDamage: number;
   /**
   */
-  readonly innerBurstDelay: number;
+  readonly pvpCritMult: number;
   /**
   */
-  readonly pveCritMult: number;
+  readonly pveImpactDamage: number;
   /**
   */
   readonly pveExplosionDamage: number;
   /**
   */
-  readonly pveImpactDamage: number;
+  readonly pveCritMult: number;
   /**
   */
-  readonly pvpCritMult: number;
+  readonly burstDelay: number;
   /**
   */
-  readonly pvpExplosionDamage: number;
+  readonly innerBurstDelay: number;
   /**
   */
-  readonly pvpImpactDamage: number;
+  readonly burstSize: number;
+  /**
+  */
+  readonly timestamp: number;
   /**
   */
   readonly rpm: number;
 }
 /**
 */
-export class HandlingResponse {
-  /**
-  ** Return copy of self without private attributes.
-  */
-  toJSON(): Object;
-  /**
-  * Return stringified version of self.
-  */
-  toString(): string;
+export class Stat {
   free(): void;
   /**
   */
-  readonly adsTime: number;
+  baseValue: number;
   /**
   */
-  readonly readyTime: number;
+  partValue: number;
   /**
   */
-  readonly stowTime: number;
+  traitValue: number;
 }
 /**
 */
 export class MetaData {
-  /**
-  ** Return copy of self without private attributes.
-  */
+  free(): void;
   toJSON(): Object;
-  /**
-  * Return stringified version of self.
-  */
   toString(): string;
-  free(): void;
   /**
   */
-  readonly apiGitBranch: string;
-  /**
-  */
-  readonly apiGitCommit: string;
+  readonly apiVersion: string;
   /**
   */
   readonly apiTimestamp: string;
   /**
   */
-  readonly apiVersion: string;
+  readonly apiGitCommit: string;
   /**
   */
-  readonly databaseTimestamp: bigint;
+  readonly apiGitBranch: string;
 }
 /**
 */
-export class RangeResponse {
-  /**
-  ** Return copy of self without private attributes.
-  */
+export class ScalarResponseSummary {
+  free(): void;
   toJSON(): Object;
-  /**
-  * Return stringified version of self.
-  */
   toString(): string;
-  free(): void;
   /**
   */
-  readonly adsFalloffEnd: number;
+  readonly reloadScalar: number;
   /**
   */
-  readonly adsFalloffStart: number;
+  readonly drawScalar: number;
   /**
   */
-  readonly floorPercent: number;
+  readonly adsScalar: number;
   /**
   */
-  readonly hipFalloffEnd: number;
+  readonly stowScalar: number;
   /**
   */
-  readonly hipFalloffStart: number;
-}
-/**
-*/
-export class ReloadResponse {
-  /**
-  ** Return copy of self without private attributes.
-  */
-  toJSON(): Object;
-  /**
-  * Return stringified version of self.
-  */
-  toString(): string;
-  free(): void;
+  readonly globalRangeScalar: number;
   /**
   */
-  readonly ammoTime: number;
+  readonly hipfireRangeScalar: number;
   /**
   */
-  readonly reloadTime: number;
-}
-/**
-*/
-export class Stat {
-  free(): void;
+  readonly adsRangeScalar: number;
   /**
-  * @returns {string}
   */
-  toString(): string;
+  readonly magSizeScalar: number;
   /**
   */
-  readonly baseValue: number;
+  readonly reserveSizeScalar: number;
   /**
+  *pve damage multiplier against the current encounter's combatant, see getCombatantScaling
   */
-  readonly partValue: number;
-  /**
-  */
-  readonly traitValue: number;
+  readonly combatantScalar: number;
 }
-/**
-*/
+export interface Weapon {
+  hash: number;
+  intrinsic_hash: number;
+  perks: Map<number, Perk>;
+  stats: Map<number, WeaponStat>;
+  investment_stats: Map<number, number>;
+  damage_mods: DamageMods;
+  firing_data: FiringData;
+  range_formula: RangeFormula;
+  ammo_formula: AmmoFormula;
+  handling_formula: HandlingFormula;
+  reload_formula: ReloadFormula;
+  weapon_type: WeaponTypeName;
+  damage_type: DamageTypeName;
+  ammo_type: AmmoTypeName;
+}
+export interface Perk {
+  stat_buffs: Map<number, number>;
+  enhanced: boolean;
+  value: number;
+  hash: number;
+  raw_hash: number;
+}
+export interface WeaponStat {
+  base_value: number;
+  part_value: number;
+  perk_value: number;
+}
+export interface DamageMods {
+  pve: number;
+  minor: number;
+  elite: number;
+  miniboss: number;
+  champion: number;
+  boss: number;
+  vehicle: number;
+  timestamp: number;
+}
+export interface FiringData {
+  damage: number;
+  crit_mult: number;
+  pve_damage: number;
+  pve_crit_mult: number;
+  burst_delay: number;
+  inner_burst_delay: number;
+  burst_size: number;
+  one_ammo: boolean;
+  charge: boolean;
+  timestamp: number;
+}
+export interface RangeFormula {
+  start: StatQuadraticFormula;
+  end: StatQuadraticFormula;
+  floor_percent: number;
+  pve_floor_percent?: number;
+  fusion: boolean;
+  timestamp: number;
+}
+export interface StatQuadraticFormula {
+  evpp: number;
+  vpp: number;
+  offset: number;
+}
+export interface AmmoFormula {
+  mag: StatQuadraticFormula;
+  round_to: number;
+  reserve_id: number;
+  timestamp: number;
+}
+export interface HandlingFormula {
+  ready: StatQuadraticFormula;
+  stow: StatQuadraticFormula;
+  ads: StatQuadraticFormula;
+  timestamp: number;
+}
+export interface ReloadFormula {
+  reload_data: StatQuadraticFormula;
+  ammo_percent: number;
+  timestamp: number;
+}
+export type WeaponTypeName = "AUTORIFLE" | "BOW" | "FUSIONRIFLE" | "GLAIVE" | "GRENADELAUNCHER" | "HANDCANNON" | "LINEARFUSIONRIFLE" | "MACHINEGUN" | "PULSERIFLE" | "ROCKET" | "SCOUTRIFLE" | "SHOTGUN" | "SIDEARM" | "SNIPER" | "SUBMACHINEGUN" | "SWORD" | "TRACERIFLE" | "UNKNOWN";
+export type DamageTypeName = "ARC" | "VOID" | "SOLAR" | "STASIS" | "KINETIC" | "STRAND" | "UNKNOWN";
+export type AmmoTypeName = "PRIMARY" | "SPECIAL" | "HEAVY" | "UNKNOWN";
+export interface FormulaOverrideReport {
+  added: Array<WeaponPathChange>;
+  changed: Array<WeaponPathChange>;
+  unchanged: Array<WeaponPathChange>;
+  changedReserves: Array<number>;
+  changedTypeScalars: Array<number>;
+  changedSwordFrames: Array<number>;
+  changedGlaiveFrames: Array<number>;
+  changedBlastRadii: Array<number>;
+  changedCleaveRadii: Array<number>;
+  changedBrickSizes: Array<number>;
+  changedBowFrames: Array<number>;
+  changedBowStrings: Array<number>;
+  warnings: Array<string>;
+}
+export interface WeaponPathChange {
+  weaponTypeId: number;
+  hash: number;
+  components: Array<string>;
+}
+export interface StatValues {
+  baseValue: number;
+  partValue: number;
+  traitValue: number;
+}
+export interface PerkOptionData {
+  stacks: [number, number];
+  options: Array<string>;
+  optionType: PerkValueVariant;
+}
+export type PerkValueVariant = "STATIC" | "TOGGLE" | "SLIDER" | "OPTIONS";
+export interface StatCurveSet {
+  base: StatCurves;
+  dynamic: StatCurves;
+}
+export interface StatCurves {
+  reloadTime: Array<number>;
+  ammoTime: Array<number>;
+  readyTime: Array<number>;
+  stowTime: Array<number>;
+  adsTime: Array<number>;
+  hipFalloffStart: Array<number>;
+  hipFalloffEnd: Array<number>;
+  adsFalloffStart: Array<number>;
+  adsFalloffEnd: Array<number>;
+  magSize: Array<number>;
+  reserveSize: Array<number>;
+}
+export interface WeaponConfig {
+  hash: number;
+  weaponType: number;
+  intrinsicHash: number;
+  ammoType: number;
+  damageType: number;
+  stats?: Map<number, number>;
+  statGroup?: number;
+  perks?: Array<PerkConfig>;
+}
+export interface PerkConfig {
+  hash: number;
+  value?: number;
+  statBuffs?: Map<number, number>;
+}
+export interface WeaponComparison {
+  weapons: number;
+  rows: Array<ComparisonRow>;
+}
+export interface ComparisonRow {
+  section: string;
+  name: string;
+  values: Array<number | undefined>;
+  better: Better;
+  deltas: Array<number | undefined>;
+  percentDeltas: Array<number | undefined>;
+  winner?: number;
+}
+export type Better = "higher" | "lower" | "neither";
+export interface RollRankingOptions {
+  metric: RollMetric;
+  top?: number;
+  pvp?: boolean;
+  dynamicTraits?: boolean;
+  resilience?: number;
+}
+export type RollMetric = "reloadTime" | "readyTime" | "adsTime" | "range" | "magSize" | "reserveSize" | "optimalTtk" | "bodyTtk" | "damage" | "dps";
+export interface RollRanking {
+  metric: RollMetric;
+  combinations: number;
+  rolls: Array<RankedRoll>;
+}
+export interface RankedRoll {
+  perks: Array<number>;
+  score: number;
+  notes: string;
+}
+export interface ResillienceTtkSummary {
+  resillienceValue: number;
+  bodyTtk: BodyTtk;
+  optimalTtk: OptimalTtk;
+}
+export interface BodyTtk {
+  bodyshots: number;
+  timeTaken: number;
+}
+export interface OptimalTtk {
+  headshots: number;
+  bodyshots: number;
+  timeTaken: number;
+}
+export interface GroupDamageResponse {
+  totalDamage: number;
+  targetsKilled: number;
+  clearTime?: number;
+  killTimes: Array<number | undefined>;
+  totalShots: number;
+}
+export interface ChampionMod {
+  champion: ChampionType;
+  weaponType: number;
+}
+export type ChampionType = "barrier" | "overload" | "unstoppable";
+export interface ChampionTtk {
+  champion: ChampionType;
+  canStun: boolean;
+  unstunned?: number;
+  stunned?: number;
+}
+export interface Encounter {
+  health: number;
+  phaseLength: number;
+  immuneWindows?: Array<Downtime>;
+  addWaves?: Array<Downtime>;
+  critExposure?: number;
+  ammoRefill?: number;
+}
+export interface Downtime {
+  start: number;
+  duration: number;
+}
+export interface EncounterResponse {
+  phaseDamage: Array<number>;
+  phasesToKill?: number;
+  killTime?: number;
+}
+export interface DpsMetrics {
+  windowDps: Array<DpsWindow>;
+  sustainedDps: number;
+  damagePerMag: number;
+  damagePerBrick?: number;
+  damagePerReserve: number;
+  timeToEmptyMag: number;
+  timeToEmptyReserves: number;
+  reloadShare: number;
+}
+export interface DpsWindow {
+  seconds: number;
+  dps: number;
+}
+export type PlayerClass = "unknown" | "titan" | "hunter" | "warlock";
+export interface ArmorStats {
+  mobility: number;
+  resilience: number;
+  recovery: number;
+  discipline: number;
+  intellect: number;
+  strength: number;
+}
+export interface ArmorStatSummary {
+  grenadeCooldown?: number;
+  meleeCooldown?: number;
+  classAbilityCooldown?: number;
+  superCooldown?: number;
+  healthRegenTime?: number;
+  walkSpeed?: number;
+  resilienceTier: number;
+  pvpHealth: number;
+  damageResistance: number;
+}
+export interface CorpusReport {
+  cases: Array<CaseResult>;
+  failures: number;
+}
+export interface CaseResult {
+  name: string;
+  kind: string;
+  measured: number;
+  predicted?: number;
+  error?: number;
+  tolerance: number;
+  passed: boolean;
+  message?: string;
+}
+export interface DamageObservation {
+  damage: number;
+  enemyType: EnemyTypeName;
+  rpl: number;
+  power: number;
+  difficulty?: DifficultyName;
+  cap?: number;
+  buff?: number;
+  crit?: boolean;
+  perkActive?: boolean;
+}
+export type EnemyTypeName = "MINOR" | "ELITE" | "MINIBOSS" | "BOSS" | "VEHICLE" | "ENCLAVE" | "PLAYER" | "CHAMPION";
+export type DifficultyName = "NORMAL" | "RAID" | "MASTER" | "GRANDMASTER" | "CONTEST" | "LEGEND";
+export interface DamageSolution {
+  baseDamage: number;
+  perkMultiplier: number;
+  residuals: Array<number>;
+  rmsError: number;
+}
+export interface ActivityPreset {
+  id: string;
+  name: string;
+  rpl: number;
+  cap: number;
+  difficulty: DifficultyName;
+  enemyType: EnemyTypeName;
+  health: number;
+  estimate: boolean;
+}
+export interface ModifierResponseSummary {
+  rmr?: RangeModifierResponse;
+  dmr?: DamageModifierResponse;
+  hmr?: HandlingModifierResponse;
+  fmr?: FiringModifierResponse;
+  flmr?: FlinchModifierResponse;
+  rsmr?: ReloadModifierResponse;
+  mmr?: MagazineModifierResponse;
+  imr?: InventoryModifierResponse;
+  drmr?: DamageResistModifierResponse;
+  statbump?: Map<number, number>;
+}
+export interface RangeModifierResponse {
+  range_stat_add: number;
+  range_all_scale: number;
+  range_hip_scale: number;
+  range_zoom_scale: number;
+}
+export interface DamageModifierResponse {
+  impact_dmg_scale: number;
+  explosive_dmg_scale: number;
+  crit_scale: number;
+}
+export interface HandlingModifierResponse {
+  stat_add: number;
+  stow_add: number;
+  draw_add: number;
+  ads_add: number;
+  stow_scale: number;
+  draw_scale: number;
+  ads_scale: number;
+}
+export interface FiringModifierResponse {
+  burst_delay_scale: number;
+  burst_delay_add: number;
+  inner_burst_scale: number;
+  burst_size_add: number;
+}
+export interface FlinchModifierResponse {
+  flinch_scale: number;
+}
+export interface ReloadModifierResponse {
+  reload_stat_add: number;
+  reload_time_scale: number;
+}
+export interface MagazineModifierResponse {
+  magazine_stat_add: number;
+  magazine_scale: number;
+  magazine_add: number;
+}
+export interface InventoryModifierResponse {
+  inv_stat_add: number;
+  inv_scale: number;
+  inv_add: number;
+}
+export interface DamageResistModifierResponse {
+  body_shot_resist: number;
+  head_shot_resist: number;
+  element?: DamageTypeName;
+  source?: DamageSourceName;
+}
+export type DamageSourceName = "SNIPER" | "MELEE" | "EXPLOSION" | "ENVIRONMENTAL" | "UNKNOWN";
+export interface CombatantScaling {
+  weaponType: number;
+  exoticPrimary: number;
+  kinetic: number;
+  weapon: number;
+}
+
 export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
 
 export interface InitOutput {
   readonly memory: WebAssembly.Memory;
-  readonly __wbg_handlingresponse_free: (a: number) => void;
-  readonly __wbg_reloadresponse_free: (a: number) => void;
-  readonly __wbg_ammoresponse_free: (a: number) => void;
-  readonly __wbg_get_ammoresponse_magSize: (a: number) => number;
-  readonly __wbg_get_ammoresponse_reserveSize: (a: number) => number;
-  readonly __wbg_dpsresponse_free: (a: number) => void;
-  readonly __wbg_get_dpsresponse_totalTime: (a: number) => number;
-  readonly __wbg_get_dpsresponse_totalShots: (a: number) => number;
-  readonly dpsresponse_toString: (a: number, b: number) => void;
-  readonly dpsresponse_toJSON: (a: number, b: number) => void;
-  readonly dpsresponse_timeDamageData: (a: number) => number;
-  readonly dpsresponse_dpsPerMag: (a: number) => number;
-  readonly __wbg_optimalkilldata_free: (a: number) => void;
-  readonly __wbg_get_optimalkilldata_headshots: (a: number) => number;
-  readonly __wbg_set_optimalkilldata_headshots: (a: number, b: number) => void;
-  readonly __wbg_get_optimalkilldata_bodyshots: (a: number) => number;
-  readonly __wbg_set_optimalkilldata_bodyshots: (a: number, b: number) => void;
-  readonly __wbg_set_optimalkilldata_achievableRange: (a: number, b: number) => void;
-  readonly __wbg_bodykilldata_free: (a: number) => void;
-  readonly __wbg_get_bodykilldata_bodyshots: (a: number) => number;
-  readonly __wbg_set_bodykilldata_bodyshots: (a: number, b: number) => void;
-  readonly __wbg_get_bodykilldata_timeTaken: (a: number) => number;
-  readonly __wbg_set_bodykilldata_timeTaken: (a: number, b: number) => void;
-  readonly __wbg_resilliencesummary_free: (a: number) => void;
-  readonly __wbg_set_resilliencesummary_resillienceValue: (a: number, b: number) => void;
-  readonly __wbg_get_resilliencesummary_bodyTtk: (a: number) => number;
-  readonly __wbg_set_resilliencesummary_bodyTtk: (a: number, b: number) => void;
-  readonly __wbg_get_resilliencesummary_optimalTtk: (a: number) => number;
-  readonly __wbg_set_resilliencesummary_optimalTtk: (a: number, b: number) => void;
-  readonly __wbg_firingresponse_free: (a: number) => void;
-  readonly __wbg_get_firingresponse_pvpCritMult: (a: number) => number;
-  readonly __wbg_get_firingresponse_pveImpactDamage: (a: number) => number;
-  readonly __wbg_get_firingresponse_pveExplosionDamage: (a: number) => number;
-  readonly __wbg_get_firingresponse_pveCritMult: (a: number) => number;
-  readonly __wbg_get_firingresponse_burstDelay: (a: number) => number;
-  readonly __wbg_get_firingresponse_innerBurstDelay: (a: number) => number;
-  readonly __wbg_get_firingresponse_burstSize: (a: number) => number;
-  readonly __wbg_get_firingresponse_rpm: (a: number) => number;
-  readonly __wbg_set_firingresponse_rpm: (a: number, b: number) => void;
-  readonly __wbg_stat_free: (a: number) => void;
-  readonly __wbg_set_stat_baseValue: (a: number, b: number) => void;
-  readonly __wbg_set_stat_partValue: (a: number, b: number) => void;
-  readonly __wbg_get_stat_traitValue: (a: number) => number;
-  readonly __wbg_set_stat_traitValue: (a: number, b: number) => void;
-  readonly stat_toString: (a: number, b: number) => void;
-  readonly __wbg_metadata_free: (a: number) => void;
-  readonly __wbg_get_metadata_databaseTimestamp: (a: number) => number;
-  readonly __wbg_get_metadata_apiVersion: (a: number, b: number) => void;
-  readonly __wbg_get_metadata_apiTimestamp: (a: number, b: number) => void;
-  readonly __wbg_get_metadata_apiGitCommit: (a: number, b: number) => void;
-  readonly __wbg_get_metadata_apiGitBranch: (a: number, b: number) => void;
-  readonly start: () => void;
-  readonly getMetadata: (a: number) => void;
-  readonly stringifyWeapon: (a: number) => void;
-  readonly weaponJSON: (a: number) => void;
-  readonly setWeapon: (a: number, b: number, c: number, d: number, e: number, f: number) => void;
-  readonly getStats: (a: number) => void;
-  readonly setStats: (a: number, b: number) => void;
-  readonly addTrait: (a: number, b: number, c: number, d: number) => void;
-  readonly getTraitHashes: (a: number) => void;
-  readonly setTraitValue: (a: number, b: number) => void;
-  readonly getTraitOptions: (a: number, b: number, c: number) => void;
-  readonly getWeaponRangeFalloff: (a: number, b: number) => void;
-  readonly getWeaponHandlingTimes: (a: number, b: number) => void;
-  readonly getWeaponReloadTimes: (a: number, b: number) => void;
-  readonly getWeaponAmmoSizes: (a: number, b: number) => void;
-  readonly getWeaponTtk: (a: number, b: number) => void;
-  readonly getWeaponDps: (a: number, b: number) => void;
-  readonly getWeaponFiringData: (a: number, b: number, c: number) => void;
-  readonly setEncounter: (a: number, b: number, c: number, d: number, e: number) => void;
-  readonly __wbg_rangeresponse_free: (a: number) => void;
-  readonly __wbg_get_rangeresponse_hipFalloffStart: (a: number) => number;
-  readonly __wbg_get_reloadresponse_reloadTime: (a: number) => number;
-  readonly __wbg_get_handlingresponse_readyTime: (a: number) => number;
-  readonly __wbg_get_optimalkilldata_timeTaken: (a: number) => number;
-  readonly __wbg_get_dpsresponse_totalDamage: (a: number) => number;
-  readonly __wbg_get_firingresponse_pvpImpactDamage: (a: number) => number;
-  readonly __wbg_get_stat_baseValue: (a: number) => number;
-  readonly __wbg_set_optimalkilldata_timeTaken: (a: number, b: number) => void;
-  readonly __wbg_get_rangeresponse_hipFalloffEnd: (a: number) => number;
-  readonly __wbg_get_rangeresponse_adsFalloffStart: (a: number) => number;
-  readonly __wbg_get_reloadresponse_ammoTime: (a: number) => number;
-  readonly __wbg_get_handlingresponse_stowTime: (a: number) => number;
-  readonly __wbg_get_optimalkilldata_achievableRange: (a: number) => number;
-  readonly __wbg_get_resilliencesummary_resillienceValue: (a: number) => number;
-  readonly __wbg_get_firingresponse_pvpExplosionDamage: (a: number) => number;
-  readonly __wbg_get_handlingresponse_adsTime: (a: number) => number;
-  readonly __wbg_get_rangeresponse_adsFalloffEnd: (a: number) => number;
-  readonly __wbg_get_rangeresponse_floorPercent: (a: number) => number;
-  readonly __wbg_get_stat_partValue: (a: number) => number;
-  readonly __wbindgen_malloc: (a: number) => number;
-  readonly __wbindgen_realloc: (a: number, b: number, c: number) => number;
-  readonly __wbindgen_free: (a: number, b: number) => void;
-  readonly __wbindgen_add_to_stack_pointer: (a: number) => number;
-  readonly __wbindgen_exn_store: (a: number) => void;
-  readonly __wbindgen_start: () => void;
 }
 
 export type SyncInitInput = BufferSource | WebAssembly.Module;
@@ -527,4 +972,4 @@ export function initSync(module: SyncInitInput): InitOutput;
 *
 * @returns {Promise<InitOutput>}
 */
-export default function init(module_or_path?: InitInput | Promise<InitInput>): Promise<InitOutput>;
\ No newline at end of file
+export default function init(module_or_path?: InitInput | Promise<InitInput>): Promise<InitOutput>;
@@ -0,0 +1,168 @@
+//! Armor stats: what each stat's tier does for the player.
+//!
+//! Every 10 points in a stat is a tier, capped at 10. Discipline, Strength and Intellect speed
+//! up the grenade, melee and super. The class ability is tied to a different stat per class.
+//! Recovery shortens health regen, Mobility raises walk speed and Resilience raises PvP health
+//! and PvE damage resistance. The resilience tier is also what flinch and PvP TTK take.
+//! Cooldowns, regen and walk speed come from build_resources/armor_stats.json, none of them are
+//! measured yet so they're None until someone loads them with [`load_armor_stat_data`].
+use std::cell::RefCell;
+
+use serde::{Deserialize, Serialize};
+
+pub use super::json_types::ArmorStatData;
+use super::{Player, PlayerClass};
+use crate::{
+    d2_enums::{Seconds, StatHashes},
+    database,
+};
+
+///PvP health and shields at each resilience tier
+pub const RESILIENCE_HEALTH: [f64; 11] = [
+    215.001, 216.001, 217.001, 218.001, 219.001, 220.001, 222.001, 224.001, 226.001, 228.01, 230.00,
+];
+
+thread_local! {
+    static DATA_OVERRIDE: RefCell<Option<ArmorStatData>> = const { RefCell::new(None) };
+}
+
+///The runtime armor stat data if there is some, the baked data otherwise
+pub fn get_armor_stat_data() -> ArmorStatData {
+    DATA_OVERRIDE
+        .with(|data| *data.borrow())
+        .unwrap_or(database::ARMOR_STAT_DATA)
+}
+
+///Replaces the baked armor stat data with an armor_stats.json style document.
+///Nothing is applied unless the document is valid
+pub fn load_armor_stat_data(json: &str) -> Result<(), String> {
+    let data: ArmorStatData =
+        serde_json::from_str(json).map_err(|err| format!("invalid document: {}", err))?;
+    if let Some(err) = data.check() {
+        return Err(err.to_owned());
+    }
+    DATA_OVERRIDE.with(|override_| *override_.borrow_mut() = Some(data));
+    Ok(())
+}
+
+///Drops the runtime armor stat data, going back to the baked one
+pub fn reset_armor_stat_data() {
+    DATA_OVERRIDE.with(|data| *data.borrow_mut() = None);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArmorStats {
+    pub mobility: i32,
+    pub resilience: i32,
+    pub recovery: i32,
+    pub discipline: i32,
+    pub intellect: i32,
+    pub strength: i32,
+}
+impl ArmorStats {
+    pub fn get(&self, _stat: StatHashes) -> Option<i32> {
+        match _stat {
+            StatHashes::MOBILITY => Some(self.mobility),
+            StatHashes::RESILIENCE => Some(self.resilience),
+            StatHashes::RECOVERY => Some(self.recovery),
+            StatHashes::DISCIPLINE => Some(self.discipline),
+            StatHashes::INTELLECT => Some(self.intellect),
+            StatHashes::STRENGTH => Some(self.strength),
+            _ => None,
+        }
+    }
+    pub fn tier(&self, _stat: StatHashes) -> i32 {
+        self.get(_stat).map_or(0, stat_tier)
+    }
+}
+
+pub fn stat_tier(_value: i32) -> i32 {
+    (_value / 10).clamp(0, 10)
+}
+
+pub fn pvp_health(_resilience_tier: i32) -> f64 {
+    RESILIENCE_HEALTH[_resilience_tier.clamp(0, 10) as usize]
+}
+
+///PvE damage resistance against combatants
+pub fn resilience_damage_resistance(_resilience_tier: i32) -> f64 {
+    _resilience_tier.clamp(0, 10) as f64 * 0.04
+}
+
+fn at_tier(_table: Option<[f64; 11]>, _tier: i32) -> Option<f64> {
+    _table.map(|x| x[_tier.clamp(0, 10) as usize])
+}
+
+fn cooldown(_data: &ArmorStatData, _base: Option<Seconds>, _tier: i32) -> Option<Seconds> {
+    Some(_base? * at_tier(_data.cooldown_scale, _tier)?)
+}
+
+impl PlayerClass {
+    ///The stat driving the class ability and its tier 0 cooldown
+    pub fn class_ability(&self, _data: &ArmorStatData) -> Option<(StatHashes, Option<Seconds>)> {
+        match self {
+            PlayerClass::Titan => Some((StatHashes::RESILIENCE, _data.titan_class_cooldown)),
+            PlayerClass::Hunter => Some((StatHashes::MOBILITY, _data.hunter_class_cooldown)),
+            PlayerClass::Warlock => Some((StatHashes::RECOVERY, _data.warlock_class_cooldown)),
+            PlayerClass::Unknown => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArmorStatSummary {
+    ///cooldowns, regen and walk speed are None without data for them
+    pub grenade_cooldown: Option<Seconds>,
+    pub melee_cooldown: Option<Seconds>,
+    ///also None without a known class
+    pub class_ability_cooldown: Option<Seconds>,
+    pub super_cooldown: Option<Seconds>,
+    ///seconds from taking damage to full health
+    pub health_regen_time: Option<Seconds>,
+    ///meters per second
+    pub walk_speed: Option<f64>,
+    pub resilience_tier: i32,
+    pub pvp_health: f64,
+    pub damage_resistance: f64,
+}
+
+pub fn calc_armor_stats(_class: PlayerClass, _stats: &ArmorStats) -> ArmorStatSummary {
+    let data = get_armor_stat_data();
+    let resilience_tier = _stats.tier(StatHashes::RESILIENCE);
+    ArmorStatSummary {
+        grenade_cooldown: cooldown(
+            &data,
+            data.grenade_cooldown,
+            _stats.tier(StatHashes::DISCIPLINE),
+        ),
+        melee_cooldown: cooldown(
+            &data,
+            data.melee_cooldown,
+            _stats.tier(StatHashes::STRENGTH),
+        ),
+        class_ability_cooldown: _class
+            .class_ability(&data)
+            .and_then(|(stat, base)| cooldown(&data, base, _stats.tier(stat))),
+        super_cooldown: cooldown(
+            &data,
+            data.super_cooldown,
+            _stats.tier(StatHashes::INTELLECT),
+        ),
+        health_regen_time: at_tier(data.health_regen_time, _stats.tier(StatHashes::RECOVERY)),
+        walk_speed: at_tier(data.walk_speed, _stats.tier(StatHashes::MOBILITY)),
+        resilience_tier,
+        pvp_health: pvp_health(resilience_tier),
+        damage_resistance: resilience_damage_resistance(resilience_tier),
+    }
+}
+
+impl Player {
+    pub fn resilience_tier(&self) -> i32 {
+        self.armor.tier(StatHashes::RESILIENCE)
+    }
+    pub fn calc_armor_stats(&self) -> ArmorStatSummary {
+        calc_armor_stats(self.class, &self.armor)
+    }
+}
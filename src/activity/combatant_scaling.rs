@@ -0,0 +1,83 @@
+//! PvE damage scaling by what's being shot.
+//!
+//! All combatant multipliers are applied from here and only here, a weapon's total is its
+//! weapon type's entry in the `weapon_type_scalars` section of weapon_formulas.json times the
+//! `combatant_scalars` of its frame in that file. Weapon specific entries in that file replace
+//! the frame scalars for a single weapon, and `loadFormulaOverrides` can replace either.
+use serde::Serialize;
+
+use crate::{
+    d2_enums::{AmmoType, DamageType, WeaponType},
+    enemies::EnemyType,
+    formulas::get_weapon_type_scalars,
+    weapons::Weapon,
+};
+
+///Exotic primaries against minors
+pub const EXOTIC_PRIMARY_MINOR_SCALAR: f64 = 1.3;
+
+///Kinetic weapons against anything but bosses, (primary, special)
+pub const KINETIC_SCALARS: (f64, f64) = (1.1, 1.15);
+
+///Breakdown of the combatant multiplier for one weapon against one combatant
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CombatantScaling {
+    ///the weapon type's `weapon_type_scalars` from weapon_formulas.json
+    pub weapon_type: f64,
+    pub exotic_primary: f64,
+    pub kinetic: f64,
+    ///the weapon's `combatant_scalars` from weapon_formulas.json
+    pub weapon: f64,
+}
+impl Default for CombatantScaling {
+    fn default() -> Self {
+        CombatantScaling {
+            weapon_type: 1.0,
+            exotic_primary: 1.0,
+            kinetic: 1.0,
+            weapon: 1.0,
+        }
+    }
+}
+impl CombatantScaling {
+    pub fn total(&self) -> f64 {
+        self.weapon_type * self.exotic_primary * self.kinetic * self.weapon
+    }
+}
+
+///Weapon types missing from `weapon_type_scalars` aren't scaled
+pub fn get_weapon_type_scalar(weapon_type: WeaponType, enemy_type: EnemyType) -> f64 {
+    get_weapon_type_scalars(weapon_type as u32).map_or(1.0, |x| x.get_mod(&enemy_type))
+}
+
+///Players aren't scaled, pvp damage never goes through here
+pub fn get_combatant_scaling(weapon: &Weapon, enemy_type: EnemyType) -> CombatantScaling {
+    if enemy_type == EnemyType::PLAYER {
+        return CombatantScaling::default();
+    }
+    //legendary frames have intrinsic hashes below 1000
+    let exotic = weapon.intrinsic_hash > 1000;
+    let kinetic = if weapon.damage_type == DamageType::KINETIC && enemy_type != EnemyType::BOSS {
+        match weapon.ammo_type {
+            AmmoType::PRIMARY => KINETIC_SCALARS.0,
+            AmmoType::SPECIAL => KINETIC_SCALARS.1,
+            _ => 1.0,
+        }
+    } else {
+        1.0
+    };
+    CombatantScaling {
+        weapon_type: get_weapon_type_scalar(weapon.weapon_type, enemy_type),
+        exotic_primary: if exotic
+            && weapon.ammo_type == AmmoType::PRIMARY
+            && enemy_type == EnemyType::MINOR
+        {
+            EXOTIC_PRIMARY_MINOR_SCALAR
+        } else {
+            1.0
+        },
+        kinetic,
+        weapon: weapon.damage_mods.get_mod(&enemy_type),
+    }
+}
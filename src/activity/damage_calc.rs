@@ -1,6 +1,14 @@
 #![allow(dead_code)]
-use super::Activity;
-use crate::{enemies::EnemyType, logging, types::rs_types::DamageMods};
+use std::{borrow::Cow, cell::RefCell, collections::HashMap};
+
+pub use super::json_types::DifficultyOptions;
+use super::{json_types::DifficultyTableJson, Activity};
+use crate::{
+    database,
+    enemies::EnemyType,
+    logging,
+    types::rs_types::{DamageMods, DifficultyTable},
+};
 use piecewise_linear::PiecewiseLinearFunction;
 use serde::{Deserialize, Serialize};
 
@@ -13,71 +21,16 @@ pub struct DifficultyData {
 
 const WEAPON_DELTA_EXPONENT: f64 = 0.00672;
 
-#[derive(Debug, Clone, Default)]
-pub enum DifficultyOptions {
-    #[default]
-    NORMAL = 1,
-    RAID = 2,
-    MASTER = 3,
-}
-
 impl DifficultyOptions {
     pub fn get_difficulty_data(&self) -> DifficultyData {
-        match self {
-            DifficultyOptions::NORMAL => DifficultyData {
-                name: "Normal".to_string(),
-                cap: 50,
-                table: PiecewiseLinearFunction::try_from(vec![
-                    (-99.0, 0.4018),
-                    (-90.0, 0.4200),
-                    (-80.0, 0.4400),
-                    (-70.0, 0.4600),
-                    (-60.0, 0.4750),
-                    (-50.0, 0.5000),
-                    (-40.0, 0.5405),
-                    (-30.0, 0.5915),
-                    (-20.0, 0.6600),
-                    (-10.0, 0.7800),
-                    (0.0, 1.0),
-                ])
-                .unwrap(),
-            },
-            DifficultyOptions::MASTER => DifficultyData {
-                name: "Master".to_string(),
-                cap: 20,
-                table: PiecewiseLinearFunction::try_from(vec![
-                    (-99.0, 0.4018),
-                    (-90.0, 0.4200),
-                    (-80.0, 0.4400),
-                    (-70.0, 0.4600),
-                    (-60.0, 0.4750),
-                    (-50.0, 0.4900),
-                    (-40.0, 0.5100),
-                    (-30.0, 0.5350),
-                    (-20.0, 0.5800),
-                    (-10.0, 0.6800),
-                    (0.0, 0.8500),
-                ])
-                .unwrap(),
-            },
-            DifficultyOptions::RAID => DifficultyData {
-                name: "Raid & Dungeon".to_string(),
-                cap: 20,
-                table: PiecewiseLinearFunction::try_from(vec![
-                    (-99.0, 0.4018),
-                    (-90.0, 0.4200),
-                    (-80.0, 0.4400),
-                    (-70.0, 0.4600),
-                    (-60.0, 0.4750),
-                    (-50.0, 0.4950),
-                    (-40.0, 0.5253),
-                    (-30.0, 0.5632),
-                    (-20.0, 0.62),
-                    (-10.0, 0.73),
-                    (0.0, 0.925),
-                ])
-                .unwrap(),
-            },
+        let difficulty = get_difficulty_table(*self as u32)
+            .or_else(|| get_difficulty_table(DifficultyOptions::NORMAL as u32))
+            .expect("no difficulty table for normal");
+        DifficultyData {
+            name: difficulty.name.into_owned(),
+            cap: difficulty.cap,
+            //tables are checked when they're built or loaded
+            table: PiecewiseLinearFunction::try_from(difficulty.table.into_owned()).unwrap(),
         }
     }
 }
@@ -87,11 +40,70 @@ impl From<i32> for DifficultyOptions {
             1 => DifficultyOptions::NORMAL,
             2 => DifficultyOptions::RAID,
             3 => DifficultyOptions::MASTER,
+            4 => DifficultyOptions::GRANDMASTER,
+            5 => DifficultyOptions::CONTEST,
+            6 => DifficultyOptions::LEGEND,
             _ => DifficultyOptions::NORMAL,
         }
     }
 }
 
+thread_local! {
+    static DIFFICULTY_OVERRIDES: RefCell<HashMap<u32, DifficultyTable>> = RefCell::new(HashMap::new());
+}
+
+///Looks up a difficulty table by id, runtime overrides win over the baked tables
+pub fn get_difficulty_table(id: u32) -> Option<DifficultyTable> {
+    DIFFICULTY_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&id).cloned())
+        .or_else(|| {
+            database::DIFFICULTY_DATA
+                .binary_search_by_key(&id, |x| x.id)
+                .ok()
+                .map(|index| database::DIFFICULTY_DATA[index].clone())
+        })
+}
+
+///Loads difficulty_tables.json style tables on top of the baked ones, returning the ids loaded.
+///Nothing is applied unless every table is valid
+pub fn load_difficulty_tables(json: &str) -> Result<Vec<u32>, String> {
+    let tables: Vec<DifficultyTableJson> =
+        serde_json::from_str(json).map_err(|err| format!("invalid document: {}", err))?;
+    for (i, table) in tables.iter().enumerate() {
+        if tables[..i].iter().any(|x| x.id == table.id) {
+            return Err(format!("duplicate difficulty id {}", table.id));
+        }
+        if let Some(err) = table.check() {
+            return Err(format!("\"{}\": {}", table.name, err));
+        }
+    }
+    DIFFICULTY_OVERRIDES.with(|overrides| {
+        let mut overrides = overrides.borrow_mut();
+        for table in tables.iter() {
+            overrides.insert(
+                table.id,
+                DifficultyTable {
+                    id: table.id,
+                    name: Cow::Owned(table.name.clone()),
+                    estimate: table.estimate,
+                    cap: table.cap,
+                    table: Cow::Owned(table.table.clone()),
+                },
+            );
+        }
+    });
+    Ok(tables.iter().map(|x| x.id).collect())
+}
+
+///Drops every runtime difficulty table, going back to the baked ones
+pub fn reset_difficulty_tables() {
+    DIFFICULTY_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+}
+
+fn get_power_cap(_activity: &Activity, _difficulty_data: &DifficultyData) -> i32 {
+    _activity.cap.min(_difficulty_data.cap)
+}
+
 pub(super) fn rpl_mult(_rpl: f64) -> f64 {
     (1.0 + ((1.0 / 30.0) * _rpl)) / (4.0 / 3.0)
 }
@@ -99,7 +111,9 @@ pub(super) fn rpl_mult(_rpl: f64) -> f64 {
 pub(super) fn get_gear_delta_mult(_activity: &Activity) -> f64 {
     let difficulty_data: DifficultyData = _activity.difficulty.get_difficulty_data();
 
-    let epl = _activity.player.power as i32 - _activity.rpl as i32;
+    let cap = get_power_cap(_activity, &difficulty_data);
+
+    let epl = (_activity.player.power as i32 - _activity.rpl as i32).min(cap);
 
     if epl < -99 {
         return 0.0;
@@ -122,11 +136,7 @@ pub(super) fn get_gear_delta_mult(_activity: &Activity) -> f64 {
 pub(super) fn get_wep_delta_mult(_activity: &Activity) -> f64 {
     let difficulty_data: DifficultyData = _activity.difficulty.get_difficulty_data();
 
-    let cap = if _activity.cap < difficulty_data.cap {
-        _activity.cap
-    } else {
-        difficulty_data.cap
-    };
+    let cap = get_power_cap(_activity, &difficulty_data);
 
     let epl = (_activity.player.power as i32 - _activity.rpl as i32).clamp(-100, cap);
 
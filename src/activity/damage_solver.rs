@@ -0,0 +1,166 @@
+//! Works backwards from damage numbers seen in game.
+//!
+//! Every observation is modeled as
+//! `base damage * perks * crit * rpl * power delta * pve * combatant * buff * (perk multiplier if active)`
+//! using the same multipliers the forward calculations use, and whichever of the base damage
+//! and the perk multiplier isn't given is solved for by least squares.
+//! The weapon's own perks (including the built in ones, like the linear fusion crit bonus) go
+//! through their damage modifiers, so the perk being solved for shouldn't be on the weapon.
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{damage_calc::DifficultyOptions, Activity, NO_CAP};
+use crate::{
+    activity::combatant_scaling::get_combatant_scaling, enemies::EnemyType,
+    perks::get_dmg_modifier, weapons::Weapon,
+};
+
+const fn default_buff() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageObservation {
+    ///damage number shown in game
+    pub damage: f64,
+    pub enemy_type: EnemyType,
+    pub rpl: u32,
+    pub power: u32,
+    #[serde(default)]
+    pub difficulty: DifficultyOptions,
+    #[serde(default)]
+    pub cap: Option<i32>,
+    ///product of every other known buff and debuff active for the hit
+    #[serde(default = "default_buff")]
+    pub buff: f64,
+    #[serde(default)]
+    pub crit: bool,
+    ///whether the perk being solved for was active for the hit
+    #[serde(default)]
+    pub perk_active: bool,
+}
+impl DamageObservation {
+    //everything but the base damage and the perk multiplier
+    fn known_multiplier(&self, weapon: &Weapon) -> f64 {
+        let mut activity = Activity {
+            rpl: self.rpl,
+            cap: self.cap.unwrap_or(NO_CAP),
+            difficulty: self.difficulty,
+            ..Default::default()
+        };
+        activity.player.power = self.power;
+        let mut calc_input = weapon.static_calc_input();
+        calc_input.enemy_type = &self.enemy_type;
+        let dmr = get_dmg_modifier(weapon.list_perks(), &calc_input, false, &mut HashMap::new());
+        let crit = if self.crit {
+            weapon.firing_data.pve_crit_mult * dmr.crit_scale
+        } else {
+            1.0
+        };
+        dmr.impact_dmg_scale
+            * crit
+            * activity.get_rpl_mult()
+            * activity.get_pl_delta()
+            * weapon.damage_mods.pve
+            * get_combatant_scaling(weapon, self.enemy_type).total()
+            * self.buff
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DamageSolution {
+    pub base_damage: f64,
+    pub perk_multiplier: f64,
+    ///observed minus predicted damage, in the same order as the observations
+    pub residuals: Vec<f64>,
+    ///root mean square of the residuals
+    pub rms_error: f64,
+}
+
+//least squares fit of `observed = x * coefficient`
+fn fit_scale(points: &[(f64, f64)]) -> Option<f64> {
+    let denominator: f64 = points
+        .iter()
+        .map(|(coefficient, _)| coefficient.powi(2))
+        .sum();
+    if points.is_empty() || denominator == 0.0 {
+        return None;
+    }
+    Some(
+        points
+            .iter()
+            .map(|(coefficient, observed)| coefficient * observed)
+            .sum::<f64>()
+            / denominator,
+    )
+}
+
+///Solves for whichever of `base_damage` and `perk_multiplier` is None.
+///Solving for the perk multiplier needs hits with the perk active, solving for both
+///also needs hits without it
+pub fn solve_damage(
+    weapon: &Weapon,
+    observations: &[DamageObservation],
+    base_damage: Option<f64>,
+    perk_multiplier: Option<f64>,
+) -> Result<DamageSolution, String> {
+    if observations.is_empty() {
+        return Err("no observations".to_owned());
+    }
+    let known: Vec<(f64, &DamageObservation)> = observations
+        .iter()
+        .map(|x| (x.known_multiplier(weapon), x))
+        .collect();
+    let active = |active: bool| -> Vec<(f64, f64)> {
+        known
+            .iter()
+            .filter(|(_, x)| x.perk_active == active)
+            .map(|(mult, x)| (*mult, x.damage))
+            .collect()
+    };
+
+    let (base_damage, perk_multiplier) = match (base_damage, perk_multiplier) {
+        (Some(base), Some(perk)) => (base, perk),
+        (None, Some(perk)) => {
+            let points: Vec<(f64, f64)> = known
+                .iter()
+                .map(|(mult, x)| (mult * if x.perk_active { perk } else { 1.0 }, x.damage))
+                .collect();
+            let base = fit_scale(&points).ok_or("observations have no damage multiplier")?;
+            (base, perk)
+        }
+        (Some(base), None) => {
+            let points: Vec<(f64, f64)> = active(true)
+                .into_iter()
+                .map(|(mult, damage)| (mult * base, damage))
+                .collect();
+            let perk = fit_scale(&points).ok_or("no observations with the perk active")?;
+            (base, perk)
+        }
+        //hits with and without the perk are independent scales, the perk is their ratio
+        (None, None) => {
+            let base = fit_scale(&active(false)).ok_or("no observations without the perk")?;
+            let with_perk = fit_scale(&active(true)).ok_or("no observations with the perk")?;
+            (base, with_perk / base)
+        }
+    };
+
+    let residuals: Vec<f64> = known
+        .iter()
+        .map(|(mult, x)| {
+            let perk = if x.perk_active { perk_multiplier } else { 1.0 };
+            x.damage - base_damage * mult * perk
+        })
+        .collect();
+    let rms_error =
+        (residuals.iter().map(|x| x.powi(2)).sum::<f64>() / residuals.len() as f64).sqrt();
+    Ok(DamageSolution {
+        base_damage,
+        perk_multiplier,
+        residuals,
+        rms_error,
+    })
+}
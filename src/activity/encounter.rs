@@ -0,0 +1,187 @@
+//! Boss encounters: damage phases, downtime, crit exposure and ammo between phases.
+//!
+//! The weapon's DPS timeline against the boss is fired in order through each damage phase and
+//! paused while the boss is immune or an add wave is being cleared. Ammo is tracked as how far
+//! into that timeline the player has fired, so picking ammo up between phases winds it back by
+//! the refilled share of full reserves. Primary ammo never runs out, the simulated timeline ends
+//! on a reload so it's just fired again from the start. Damage dealt while the crit spot is hidden
+//! loses the crit multiplier.
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    d2_enums::{AmmoType, Seconds},
+    enemies::Enemy,
+    weapons::Weapon,
+};
+
+///bosses that survive this many phases count as not killed
+pub const MAX_PHASES: usize = 20;
+
+const fn default_crit_exposure() -> f64 {
+    1.0
+}
+
+///Part of a phase spent not shooting the boss
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Downtime {
+    ///seconds into the phase
+    pub start: Seconds,
+    pub duration: Seconds,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Encounter {
+    ///the boss's health
+    pub health: f64,
+    pub phase_length: Seconds,
+    ///when the boss can't be damaged in each phase
+    #[serde(default)]
+    pub immune_windows: Vec<Downtime>,
+    ///add waves in each phase, lasting as long as they take to clear
+    #[serde(default)]
+    pub add_waves: Vec<Downtime>,
+    ///share of the phase the crit spot is exposed, 0.0-1.0
+    #[serde(default = "default_crit_exposure")]
+    pub crit_exposure: f64,
+    ///share of full reserves picked up between phases
+    #[serde(default)]
+    pub ammo_refill: f64,
+}
+impl Encounter {
+    ///(start, end) of the parts of a phase the boss is being shot
+    pub fn active_windows(&self) -> Vec<(Seconds, Seconds)> {
+        let mut downtime: Vec<(f64, f64)> = self
+            .immune_windows
+            .iter()
+            .chain(self.add_waves.iter())
+            .map(|x| {
+                (
+                    x.start.max(0.0),
+                    (x.start + x.duration).min(self.phase_length),
+                )
+            })
+            .filter(|x| x.1 > x.0)
+            .collect();
+        downtime.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        let mut windows = Vec::new();
+        let mut start = 0.0;
+        for (down_start, down_end) in downtime {
+            if down_start > start {
+                windows.push((start, down_start));
+            }
+            start = f64::max(start, down_end);
+        }
+        if self.phase_length > start {
+            windows.push((start, self.phase_length));
+        }
+        windows
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncounterResponse {
+    ///damage dealt in each phase, up to the one the boss dies in
+    pub phase_damage: Vec<f64>,
+    ///None when the boss outlasts the ammo or [`MAX_PHASES`]
+    pub phases_to_kill: Option<usize>,
+    ///seconds into the last phase the boss died
+    pub kill_time: Option<Seconds>,
+}
+
+//damage scale of a hit with the crit spot exposed `_crit_exposure` of the time
+fn crit_exposure_scale(weapon: &Weapon, _crit_exposure: f64) -> f64 {
+    //the same profile the dps timeline is built from
+    let profile = weapon.get_damage_profile(true);
+    let crit_damage = profile.impact_dmg * profile.crit_mult + profile.explosion_dmg;
+    if crit_damage <= 0.0 {
+        return 1.0;
+    }
+    let body_share = (profile.impact_dmg + profile.explosion_dmg) / crit_damage;
+    let exposure = _crit_exposure.clamp(0.0, 1.0);
+    exposure + (1.0 - exposure) * body_share
+}
+
+///Fights `_enemy` through the encounter's phases until the encounter's health is gone
+pub fn simulate_encounter(
+    weapon: &Weapon,
+    _enemy: &Enemy,
+    _encounter: &Encounter,
+    _pl_dmg_mult: f64,
+) -> EncounterResponse {
+    let dps = weapon.calc_dps(_enemy.clone(), _pl_dmg_mult);
+    let timeline = dps.time_damage_data;
+    let scale =
+        crit_exposure_scale(weapon, _encounter.crit_exposure) * (1.0 - _enemy.damage_resistance);
+    let windows = _encounter.active_windows();
+    //first hit at or after `time` into the timeline
+    let hit_index = |time: f64| timeline.partition_point(|x| x.0 < time);
+    let unlimited = weapon.ammo_type == AmmoType::PRIMARY && dps.total_time > 0.0;
+    //hits fired between `from` and `until`, primaries loop the timeline
+    let hits_between = |from: f64, until: f64| -> Vec<(f64, f64)> {
+        if !unlimited {
+            return timeline[hit_index(from)..hit_index(until)].to_vec();
+        }
+        let mut hits = Vec::new();
+        let mut cycle = (from / dps.total_time).floor();
+        while cycle * dps.total_time < until {
+            let offset = cycle * dps.total_time;
+            hits.extend(
+                timeline[hit_index(from - offset)..hit_index(until - offset)]
+                    .iter()
+                    .map(|(time, hit)| (time + offset, *hit)),
+            );
+            cycle += 1.0;
+        }
+        hits
+    };
+
+    let mut health = _encounter.health;
+    let mut fired = 0.0_f64;
+    let mut phase_damage = Vec::new();
+    for phase in 0..MAX_PHASES {
+        if phase > 0 && !unlimited {
+            fired = (fired.min(dps.total_time) - _encounter.ammo_refill * dps.total_time).max(0.0);
+        }
+        let mut damage = 0.0;
+        for (start, end) in windows.iter() {
+            let until = fired + (end - start);
+            for (time, hit) in hits_between(fired, until) {
+                damage += hit * scale;
+                health -= hit * scale;
+                if health <= 0.0 {
+                    phase_damage.push(damage);
+                    return EncounterResponse {
+                        phase_damage,
+                        phases_to_kill: Some(phase + 1),
+                        kill_time: Some(start + time - fired),
+                    };
+                }
+            }
+            fired = until;
+        }
+        phase_damage.push(damage);
+        //out of ammo with nothing coming back
+        if !unlimited && fired >= dps.total_time && _encounter.ammo_refill <= 0.0 {
+            break;
+        }
+    }
+    EncounterResponse {
+        phase_damage,
+        phases_to_kill: None,
+        kill_time: None,
+    }
+}
+
+impl Weapon {
+    pub fn simulate_encounter(
+        &self,
+        _enemy: &Enemy,
+        _encounter: &Encounter,
+        _pl_dmg_mult: f64,
+    ) -> EncounterResponse {
+        simulate_encounter(self, _enemy, _encounter, _pl_dmg_mult)
+    }
+}
@@ -0,0 +1,115 @@
+//! Shapes of build_resources/difficulty_tables.json, activity_presets.json and armor_stats.json.
+//!
+//! Shared with build.rs through `#[path]`, so the files are checked the same way when they're
+//! baked and when they're loaded at runtime. Keep it free of crate imports other than
+//! [`crate::enemies::json_types`], which build.rs includes too.
+use serde::{Deserialize, Serialize};
+
+use crate::enemies::json_types::EnemyType;
+
+///Ids match the entries in build_resources/difficulty_tables.json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DifficultyOptions {
+    #[default]
+    NORMAL = 1,
+    RAID = 2,
+    MASTER = 3,
+    GRANDMASTER = 4,
+    CONTEST = 5,
+    ///Expert in newer activities
+    LEGEND = 6,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DifficultyTableJson {
+    pub id: u32,
+    pub name: String,
+    #[serde(default)]
+    pub estimate: bool,
+    pub cap: i32,
+    pub table: Vec<(f64, f64)>,
+}
+impl DifficultyTableJson {
+    //same requirements as PiecewiseLinearFunction::try_from
+    pub fn check(&self) -> Option<&'static str> {
+        if self.table.len() < 2 {
+            return Some("table needs at least two points");
+        }
+        if self.table.windows(2).any(|x| x[0].0 >= x[1].0) {
+            return Some("table is not sorted by power delta");
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActivityPresetJson {
+    pub id: String,
+    pub name: String,
+    pub rpl: u32,
+    pub cap: i32,
+    pub difficulty: DifficultyOptions,
+    pub enemy_type: EnemyType,
+    pub health: f64,
+    #[serde(default)]
+    pub estimate: bool,
+}
+impl ActivityPresetJson {
+    pub fn check(&self) -> Option<&'static str> {
+        if self.health <= 0.0 {
+            return Some("health has to be positive");
+        }
+        None
+    }
+}
+
+///Per tier tables and tier 0 cooldowns in seconds from armor_stats.json, anything missing is None
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(default)]
+pub struct ArmorStatData {
+    ///share of the tier 0 cooldown left at each tier
+    pub cooldown_scale: Option<[f64; 11]>,
+    pub grenade_cooldown: Option<f64>,
+    pub melee_cooldown: Option<f64>,
+    pub super_cooldown: Option<f64>,
+    pub titan_class_cooldown: Option<f64>,
+    pub hunter_class_cooldown: Option<f64>,
+    pub warlock_class_cooldown: Option<f64>,
+    ///seconds from taking damage to full health at each recovery tier
+    pub health_regen_time: Option<[f64; 11]>,
+    ///meters per second at each mobility tier
+    pub walk_speed: Option<[f64; 11]>,
+}
+impl ArmorStatData {
+    pub fn check(&self) -> Option<&'static str> {
+        let cooldowns = [
+            self.grenade_cooldown,
+            self.melee_cooldown,
+            self.super_cooldown,
+            self.titan_class_cooldown,
+            self.hunter_class_cooldown,
+            self.warlock_class_cooldown,
+        ];
+        if cooldowns.iter().flatten().any(|x| *x <= 0.0) {
+            return Some("cooldowns have to be positive");
+        }
+        if self.cooldown_scale.map_or(false, |x| {
+            x[0] != 1.0 || x[10] <= 0.0 || x.windows(2).any(|x| x[0] < x[1])
+        }) {
+            return Some("cooldown_scale has to start at 1 and shrink towards tier 10");
+        }
+        if self
+            .health_regen_time
+            .map_or(false, |x| x[10] <= 0.0 || x.windows(2).any(|x| x[0] < x[1]))
+        {
+            return Some("health_regen_time has to be positive and shrink towards tier 10");
+        }
+        if self
+            .walk_speed
+            .map_or(false, |x| x[0] <= 0.0 || x.windows(2).any(|x| x[0] > x[1]))
+        {
+            return Some("walk_speed has to be positive and grow towards tier 10");
+        }
+        None
+    }
+}
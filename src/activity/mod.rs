@@ -1,8 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use self::armor_stats::ArmorStats;
 use self::damage_calc::{get_gear_delta_mult, get_wep_delta_mult, rpl_mult, DifficultyOptions};
+use crate::enemies::champions::ChampionMod;
 
+pub mod armor_stats;
+pub mod combatant_scaling;
 pub mod damage_calc;
+pub mod damage_solver;
+pub mod encounter;
+pub mod json_types;
+pub mod presets;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub enum PlayerClass {
     #[default]
     Unknown = 0,
@@ -15,13 +26,20 @@ pub enum PlayerClass {
 pub struct Player {
     pub power: u32,
     pub class: PlayerClass,
+    ///artifact mods that let weapon types stun champions
+    pub champion_mods: Vec<ChampionMod>,
+    pub armor: ArmorStats,
 }
 
+///activity cap that leaves the difficulty's own cap in place
+pub const NO_CAP: i32 = 100;
+
 #[derive(Debug, Clone)]
 pub struct Activity {
     pub name: String,
     pub difficulty: DifficultyOptions,
     pub rpl: u32,
+    ///lowers the difficulty's power cap when it's below it
     pub cap: i32,
     pub player: Player,
 }
@@ -32,10 +50,12 @@ impl Default for Activity {
             name: "Default".to_string(),
             difficulty: DifficultyOptions::default(),
             rpl: expansion_base,
-            cap: 100,
+            cap: NO_CAP,
             player: Player {
                 power: expansion_base + 210,
                 class: PlayerClass::default(),
+                champion_mods: Vec::new(),
+                armor: ArmorStats::default(),
             },
         }
     }
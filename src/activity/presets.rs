@@ -0,0 +1,33 @@
+//! Named activities from build_resources/activity_presets.json
+use crate::{database, enemies::Enemy, types::rs_types::ActivityPreset};
+
+use super::Activity;
+
+pub fn get_activity_preset(id: &str) -> Option<&'static ActivityPreset> {
+    database::ACTIVITY_PRESETS
+        .binary_search_by(|x| x.id.cmp(id))
+        .ok()
+        .map(|index| &database::ACTIVITY_PRESETS[index])
+}
+
+pub fn list_activity_presets() -> &'static [ActivityPreset] {
+    &database::ACTIVITY_PRESETS
+}
+
+impl Activity {
+    ///Sets up the activity from a preset, the player is kept as is
+    pub fn apply_preset(&mut self, preset: &ActivityPreset) {
+        self.name = preset.name.to_string();
+        self.rpl = preset.rpl;
+        self.cap = preset.cap;
+        self.difficulty = preset.difficulty;
+    }
+}
+
+impl Enemy {
+    ///Sets the enemy type and health from a preset
+    pub fn apply_preset(&mut self, preset: &ActivityPreset) {
+        self.type_ = preset.enemy_type;
+        self.health = preset.health;
+    }
+}
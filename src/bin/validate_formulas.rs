@@ -0,0 +1,37 @@
+//! Validates a weapon_formulas.json style document.
+//!
+//! `cargo run --bin validate_formulas -- [path]`, defaults to build_resources/weapon_formulas.json.
+//! Exits with 1 if the document has errors, warnings alone do not fail.
+#[path = "../formulas/validate.rs"]
+mod validate;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "build_resources/weapon_formulas.json".to_owned());
+    let raw = match std::fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(err) => {
+            eprintln!("could not read {}: {}", path, err);
+            std::process::exit(2);
+        }
+    };
+
+    let diagnostics = validate::validate_weapon_formulas(&raw);
+    for diagnostic in diagnostics.iter() {
+        println!("{}", diagnostic);
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|x| x.severity == validate::Severity::Error)
+        .count();
+    println!(
+        "{}: {} errors, {} warnings",
+        path,
+        errors,
+        diagnostics.len() - errors
+    );
+    if validate::has_errors(&diagnostics) {
+        std::process::exit(1);
+    }
+}
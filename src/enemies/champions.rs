@@ -0,0 +1,229 @@
+//! Champions: what each type does until it's stunned, and what stuns it.
+//!
+//! Every champion resists damage until it's stunned. Overloads regenerate health, Barriers go
+//! immune behind their shield at set health thresholds and Unstoppables simply resist more.
+//! A stun strips all of that for a few seconds. Weapons stun through the exotics listed in
+//! build_resources/champions.json or the artifact mods in
+//! [`crate::activity::Player::champion_mods`]. How each type fights comes from the same file's
+//! profiles, none are measured yet so they're left out until someone loads them with
+//! [`load_champion_profiles`].
+use std::{borrow::Cow, cell::RefCell, collections::HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use super::json_types::ChampionProfileJson;
+pub use super::json_types::ChampionType;
+use super::{Enemy, EnemyType};
+use crate::{
+    d2_enums::{Seconds, WeaponType},
+    database,
+    weapons::Weapon,
+};
+
+///An artifact mod letting a weapon type stun a champion type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionMod {
+    pub champion: ChampionType,
+    ///the weapon type's id, same as a WeaponConfig's
+    pub weapon_type: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChampionProfile {
+    ///damage resistance while not stunned, 0.0-1.0
+    pub resistance: f64,
+    pub stunned_resistance: f64,
+    ///share of max health regenerated per second while not stunned
+    pub regen: f64,
+    ///shares of max health where it goes immune, highest first
+    pub immune_thresholds: Cow<'static, [f64]>,
+    pub immune_time: Seconds,
+    pub stun_time: Seconds,
+}
+
+thread_local! {
+    static PROFILE_OVERRIDES: RefCell<HashMap<ChampionType, ChampionProfile>> = RefCell::new(HashMap::new());
+}
+
+///Looks up how a champion type fights, runtime profiles win over the baked ones
+pub fn get_champion_profile(_champion: ChampionType) -> Option<ChampionProfile> {
+    PROFILE_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&_champion).cloned())
+        .or_else(|| {
+            database::CHAMPION_PROFILES
+                .iter()
+                .find(|x| x.0 == _champion)
+                .map(|x| x.1.clone())
+        })
+}
+
+///Loads champions.json style profiles on top of the baked ones, returning the champion types loaded.
+///Nothing is applied unless every profile is valid
+pub fn load_champion_profiles(json: &str) -> Result<Vec<ChampionType>, String> {
+    let profiles: Vec<ChampionProfileJson> =
+        serde_json::from_str(json).map_err(|err| format!("invalid document: {}", err))?;
+    for (i, profile) in profiles.iter().enumerate() {
+        if profiles[..i].iter().any(|x| x.champion == profile.champion) {
+            return Err(format!("duplicate champion {:?}", profile.champion));
+        }
+        if let Some(err) = profile.check() {
+            return Err(format!("{:?}: {}", profile.champion, err));
+        }
+    }
+    PROFILE_OVERRIDES.with(|overrides| {
+        let mut overrides = overrides.borrow_mut();
+        for profile in profiles.iter() {
+            overrides.insert(
+                profile.champion,
+                ChampionProfile {
+                    resistance: profile.resistance,
+                    stunned_resistance: profile.stunned_resistance,
+                    regen: profile.regen,
+                    immune_thresholds: Cow::Owned(profile.immune_thresholds.clone()),
+                    immune_time: profile.immune_time,
+                    stun_time: profile.stun_time,
+                },
+            );
+        }
+    });
+    Ok(profiles.iter().map(|x| x.champion).collect())
+}
+
+///Drops every runtime champion profile, going back to the baked ones
+pub fn reset_champion_profiles() {
+    PROFILE_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+}
+
+impl Weapon {
+    ///Champion types this weapon stuns on its own or through `_mods`
+    pub fn stunned_champions(&self, _mods: &[ChampionMod]) -> Vec<ChampionType> {
+        let mut out: Vec<ChampionType> = _mods
+            .iter()
+            .filter(|x| WeaponType::from(x.weapon_type as u32) == self.weapon_type)
+            .map(|x| x.champion)
+            .collect();
+        //stuns are keyed by the weapon, its intrinsic or the perk that carries them
+        let intrinsic = self.raw_intrinsic_hash();
+        out.extend(
+            database::CHAMPION_STUNS
+                .iter()
+                .filter(|(hash, _)| {
+                    *hash == self.hash || *hash == intrinsic || self.perks.contains_key(hash)
+                })
+                .map(|(_, champion)| *champion),
+        );
+        out.sort_by_key(|x| *x as u8);
+        out.dedup();
+        out
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampionTtk {
+    pub champion: ChampionType,
+    pub can_stun: bool,
+    ///None when the champion outlasts the reserves
+    pub unstunned: Option<Seconds>,
+    ///stunned on the first hit, and again whenever the stun wears off if this weapon stuns it
+    pub stunned: Option<Seconds>,
+}
+
+//walks a damage timeline against a champion, returns when it died
+fn champion_kill_time(
+    _timeline: &[(f64, f64)],
+    _health: f64,
+    _damage_resistance: f64,
+    _profile: &ChampionProfile,
+    _stun_first_hit: bool,
+    _restun: bool,
+) -> Option<Seconds> {
+    let mut health = _health;
+    let mut last_time = 0.0;
+    let mut stunned_until = Seconds::NEG_INFINITY;
+    let mut immune_until = Seconds::NEG_INFINITY;
+    let mut thresholds = _profile.immune_thresholds.iter().peekable();
+    for (index, (time, damage)) in _timeline.iter().enumerate() {
+        if *time >= stunned_until {
+            health = (health + _profile.regen * _health * (time - last_time)).min(_health);
+        }
+        last_time = *time;
+        if *time >= stunned_until && ((index == 0 && _stun_first_hit) || _restun) {
+            stunned_until = time + _profile.stun_time;
+            //the stun breaks a barrier
+            immune_until = Seconds::NEG_INFINITY;
+        }
+        if *time < immune_until {
+            continue;
+        }
+        let stunned = *time < stunned_until;
+        let resistance = if stunned {
+            _profile.stunned_resistance
+        } else {
+            _profile.resistance
+        };
+        health -= damage * (1.0 - resistance) * (1.0 - _damage_resistance);
+        if health <= 0.0 {
+            return Some(*time);
+        }
+        while let Some(threshold) = thresholds.next_if(|x| health <= **x * _health) {
+            if !stunned {
+                //damage past the threshold goes into the shield
+                health = threshold * _health;
+                immune_until = time + _profile.immune_time;
+            }
+        }
+    }
+    None
+}
+
+///Time to kill a champion with `_health` as each champion type that has a profile,
+///along the weapon's damage timeline against `_enemy`
+pub fn calc_champion_ttk(
+    _weapon: &Weapon,
+    _enemy: &Enemy,
+    _health: f64,
+    _mods: &[ChampionMod],
+    _pl_dmg_mult: f64,
+) -> Vec<ChampionTtk> {
+    let mut enemy = _enemy.clone();
+    enemy.type_ = EnemyType::CHAMPION;
+    let timeline = _weapon.calc_dps(enemy, _pl_dmg_mult).time_damage_data;
+    let stuns = _weapon.stunned_champions(_mods);
+    ChampionType::ALL
+        .iter()
+        .filter_map(|champion| Some((champion, get_champion_profile(*champion)?)))
+        .map(|(champion, profile)| {
+            let can_stun = stuns.contains(champion);
+            let kill_time = |stun_first_hit: bool, restun: bool| {
+                champion_kill_time(
+                    &timeline,
+                    _health,
+                    _enemy.damage_resistance,
+                    &profile,
+                    stun_first_hit,
+                    restun,
+                )
+            };
+            ChampionTtk {
+                champion: *champion,
+                can_stun,
+                unstunned: kill_time(false, false),
+                stunned: kill_time(true, can_stun),
+            }
+        })
+        .collect()
+}
+
+impl Weapon {
+    pub fn calc_champion_ttk(
+        &self,
+        _enemy: &Enemy,
+        _health: f64,
+        _mods: &[ChampionMod],
+        _pl_dmg_mult: f64,
+    ) -> Vec<ChampionTtk> {
+        calc_champion_ttk(self, _enemy, _health, _mods, _pl_dmg_mult)
+    }
+}
@@ -0,0 +1,91 @@
+//! Enemy types as build_resources/activity_presets.json names them, champion types and the
+//! shape of build_resources/champions.json.
+//!
+//! Shared with build.rs through `#[path]`, so champions.json is checked the same way when it's
+//! baked and when profiles are loaded at runtime. Keep it free of crate imports.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum EnemyType {
+    MINOR,
+    ELITE,
+    MINIBOSS,
+    BOSS,
+    VEHICLE,
+    #[default]
+    ENCLAVE,
+    PLAYER,
+    CHAMPION,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChampionType {
+    Barrier,
+    Overload,
+    Unstoppable,
+}
+impl ChampionType {
+    pub const ALL: [ChampionType; 3] = [
+        ChampionType::Barrier,
+        ChampionType::Overload,
+        ChampionType::Unstoppable,
+    ];
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChampionsJson {
+    pub stuns: Vec<ChampionStunJson>,
+    pub profiles: Vec<ChampionProfileJson>,
+}
+
+///A weapon, intrinsic or perk hash that stuns a champion type
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChampionStunJson {
+    pub hash: u32,
+    pub name: String,
+    pub champion: ChampionType,
+    pub source: String,
+}
+impl ChampionStunJson {
+    pub fn check(&self) -> Option<&'static str> {
+        if self.source.is_empty() {
+            return Some("every stun needs a source");
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChampionProfileJson {
+    pub champion: ChampionType,
+    pub resistance: f64,
+    #[serde(default)]
+    pub stunned_resistance: f64,
+    #[serde(default)]
+    pub regen: f64,
+    #[serde(default)]
+    pub immune_thresholds: Vec<f64>,
+    #[serde(default)]
+    pub immune_time: f64,
+    pub stun_time: f64,
+}
+impl ChampionProfileJson {
+    pub fn check(&self) -> Option<&'static str> {
+        let share = 0.0..1.0;
+        if !share.contains(&self.resistance) || !share.contains(&self.stunned_resistance) {
+            return Some("resistances have to be in 0..1");
+        }
+        if self.regen < 0.0 || self.immune_time < 0.0 || self.stun_time <= 0.0 {
+            return Some(
+                "regen and immune time can't be negative and stun time has to be positive",
+            );
+        }
+        if self.immune_thresholds.iter().any(|x| !share.contains(x))
+            || self.immune_thresholds.windows(2).any(|x| x[0] <= x[1])
+        {
+            return Some("immune thresholds have to be in 0..1, highest first");
+        }
+        None
+    }
+}
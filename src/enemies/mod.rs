@@ -1,19 +1,11 @@
+use serde::{Deserialize, Serialize};
+
 use crate::activity::Activity;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-#[derive(Default)]
-pub enum EnemyType {
-    MINOR,
-    ELITE,
-    MINIBOSS,
-    BOSS,
-    VEHICLE,
-    #[default]
-    ENCLAVE,
-    PLAYER,
-    CHAMPION,
-}
+pub mod champions;
+pub mod json_types;
 
+pub use json_types::EnemyType;
 
 #[derive(Debug, Clone, Default)]
 pub struct Enemy {
@@ -22,9 +14,33 @@ pub struct Enemy {
     pub damage_resistance: f64,
     pub type_: EnemyType,
     pub tier: u8,
+    ///meters between the player and the enemy, only projectiles care
+    pub distance: f64,
 }
 impl Enemy {
     pub fn get_adjusted_health(&self, _activity: Activity) -> f64 {
         self.health * (1.0 - self.damage_resistance)
     }
+
+    ///Stacks another source of damage resistance (a glaive shield, a buff) on top of the
+    ///enemy's own, they multiply rather than add
+    pub fn add_damage_resistance(&mut self, _damage_resistance: f64) {
+        self.damage_resistance =
+            1.0 - (1.0 - self.damage_resistance) * (1.0 - _damage_resistance.clamp(0.0, 1.0));
+    }
+}
+
+///Copies of one enemy standing in a line `spacing` meters apart, the nearest living one is
+///the one being shot
+#[derive(Debug, Clone, Default)]
+pub struct TargetGroup {
+    pub enemy: Enemy,
+    pub count: usize,
+    pub spacing: f64,
+}
+impl TargetGroup {
+    ///meters between two targets in the group
+    pub fn offset(&self, _from: usize, _to: usize) -> f64 {
+        _from.abs_diff(_to) as f64 * self.spacing
+    }
 }
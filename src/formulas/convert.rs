@@ -0,0 +1,214 @@
+//conversions from the shared json types into the runtime formula types,
+//the unit math lives in json_types.rs so build.rs applies the exact same conversions
+use std::borrow::Cow;
+
+use super::json_types::{
+    crit_mult_from_json, frames_to_seconds, AmmoJson, BlastJson, BowFrameJson, DamageModsJson,
+    GlaiveFrameJson, HandlingJson, RangeJson, ReloadJson, ReserveJson, StatQuadraticJson,
+    SubFamJson, SwordFrameJson,
+};
+use crate::types::rs_types::{
+    AmmoFormula, BlastFormula, BowFrame, DamageMods, FiringData, GlaiveFrame, HandlingFormula,
+    RangeFormula, ReloadFormula, ReserveFormula, StatQuadraticFormula, SwordFrame,
+};
+
+impl From<StatQuadraticJson> for StatQuadraticFormula {
+    fn from(value: StatQuadraticJson) -> Self {
+        StatQuadraticFormula {
+            evpp: value.evpp,
+            vpp: value.vpp,
+            offset: value.offset,
+        }
+    }
+}
+
+impl From<RangeJson> for RangeFormula {
+    fn from(value: RangeJson) -> Self {
+        RangeFormula {
+            start: StatQuadraticFormula {
+                vpp: value.vpp_start,
+                offset: value.offset_start,
+                ..Default::default()
+            },
+            end: StatQuadraticFormula {
+                vpp: value.vpp_end,
+                offset: value.offset_end,
+                ..Default::default()
+            },
+            floor_percent: value.floor_percent,
+            pve_floor_percent: value.pve_floor_percent,
+            fusion: value.fusion.unwrap_or_default(),
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<ReloadJson> for ReloadFormula {
+    fn from(value: ReloadJson) -> Self {
+        ReloadFormula {
+            reload_data: value.reload_data.into(),
+            ammo_percent: value.ammo_percent,
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<HandlingJson> for HandlingFormula {
+    fn from(value: HandlingJson) -> Self {
+        HandlingFormula {
+            ready: value.ready.into(),
+            stow: value.stow.into(),
+            ads: value.ads.into(),
+            timestamp: 0,
+        }
+    }
+}
+
+pub fn damage_mods_with_pve(value: DamageModsJson, pve: f64) -> DamageMods {
+    DamageMods {
+        pve,
+        minor: value.minor,
+        elite: value.elite,
+        miniboss: value.miniboss,
+        champion: value.champion,
+        boss: value.boss,
+        vehicle: value.vehicle,
+        timestamp: 0,
+    }
+}
+
+impl From<AmmoJson> for AmmoFormula {
+    fn from(value: AmmoJson) -> Self {
+        AmmoFormula {
+            mag: value.mag.into(),
+            round_to: value.round_to,
+            reserve_id: value.reserve_id,
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<SubFamJson> for FiringData {
+    fn from(value: SubFamJson) -> Self {
+        FiringData {
+            damage: value.damage,
+            crit_mult: crit_mult_from_json(value.crit_mult),
+            pve_damage: value.pve_damage,
+            pve_crit_mult: crit_mult_from_json(value.pve_crit_mult),
+            burst_delay: frames_to_seconds(value.burst_delay),
+            inner_burst_delay: frames_to_seconds(value.inner_burst_delay),
+            burst_size: value.burst_size,
+            one_ammo: value.one_ammo.unwrap_or_default(),
+            charge: value.charge.unwrap_or_default(),
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<SwordFrameJson> for SwordFrame {
+    fn from(value: SwordFrameJson) -> Self {
+        SwordFrame {
+            combo: value.combo,
+            finisher_scale: value.finisher_scale,
+            heavy_scale: value.heavy_scale,
+            heavy_time: frames_to_seconds(value.heavy_delay),
+            swing_speed: value.swing_speed.into(),
+            energy_regen: value.energy_regen.into(),
+            guard_resistance: value.guard_resistance.into(),
+            guard_efficiency: value.guard_efficiency.into(),
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<BowFrameJson> for BowFrame {
+    fn from(value: BowFrameJson) -> Self {
+        BowFrame {
+            draw_time: value.draw_time.into(),
+            perfect_draw: value.perfect_draw.map(Into::into),
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<GlaiveFrameJson> for GlaiveFrame {
+    fn from(value: GlaiveFrameJson) -> Self {
+        GlaiveFrame {
+            melee_scale: value.melee_scale,
+            melee_time: frames_to_seconds(value.melee_delay),
+            melees_per_shot: value.melees_per_shot,
+            shield_resistance_pve: value.shield_resistance_pve,
+            shield_resistance_pvp: value.shield_resistance_pvp,
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<BlastJson> for BlastFormula {
+    fn from(value: BlastJson) -> Self {
+        BlastFormula {
+            radius: value.radius.into(),
+            edge_damage: value.edge_damage,
+            timestamp: 0,
+        }
+    }
+}
+
+impl From<ReserveJson> for ReserveFormula {
+    fn from(value: ReserveJson) -> Self {
+        match value {
+            ReserveJson::Constant { value } => ReserveFormula::Constant { value },
+            ReserveJson::Linear {
+                vpp,
+                offset,
+                full_mag,
+            } => ReserveFormula::Linear {
+                vpp,
+                offset,
+                full_mag: full_mag.map(|x| (x.vpp, x.offset)),
+            },
+            ReserveJson::PerMag { vpp, offset } => ReserveFormula::PerMag { vpp, offset },
+            ReserveJson::MagTable {
+                offsets,
+                default,
+                inv_mult,
+                bonus,
+            } => ReserveFormula::MagTable {
+                offsets: Cow::Owned(offsets),
+                default,
+                inv_mult,
+                bonus,
+            },
+            ReserveJson::Exact { values, default } => ReserveFormula::Exact {
+                values: Cow::Owned(values),
+                default,
+            },
+            ReserveJson::Steps { steps, default } => ReserveFormula::Steps {
+                steps: Cow::Owned(steps),
+                default,
+            },
+            ReserveJson::MagRounding {
+                base,
+                offset,
+                round_scale,
+                mag_stat_scale,
+                inv_mult,
+            } => ReserveFormula::MagRounding {
+                base,
+                offset,
+                round_scale,
+                mag_stat_scale,
+                inv_mult,
+            },
+            ReserveJson::MagStatSteps {
+                steps,
+                default,
+                inv_mult,
+            } => ReserveFormula::MagStatSteps {
+                steps: Cow::Owned(steps),
+                default,
+                inv_mult,
+            },
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! Serde types for weapon_formulas.json and the unit conversions applied to them.
+//!
+//! This file is shared by the library and build.rs so runtime documents parse and convert
+//! exactly like the baked database, it may only depend on std and serde.
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+///crit multipliers are stored as `(multiplier - 1.5) * 51`
+pub fn crit_mult_from_json(value: f64) -> f64 {
+    value / 51.0 + 1.5
+}
+
+///delays are stored in frames at 30fps
+pub fn frames_to_seconds(frames: f64) -> f64 {
+    frames / 30.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeaponFormulaJson {
+    #[serde(flatten)]
+    pub types: BTreeMap<String, WeaponFamilyJson>,
+    #[serde(default)]
+    pub reserves: BTreeMap<String, ReserveJson>,
+    ///combatant multipliers shared by every weapon of a family, keyed by family name
+    #[serde(default)]
+    pub weapon_type_scalars: BTreeMap<String, DamageModsJson>,
+    ///keyed by sword weapon or intrinsic hash
+    #[serde(default)]
+    pub sword_frames: BTreeMap<String, SwordFrameJson>,
+    ///keyed by glaive weapon or intrinsic hash
+    #[serde(default)]
+    pub glaive_frames: BTreeMap<String, GlaiveFrameJson>,
+    ///explosion reach shared by every weapon of a family, keyed by family name
+    #[serde(default)]
+    pub blast_radii: BTreeMap<String, BlastJson>,
+    ///keyed by the perk hash of extra damage that cleaves
+    #[serde(default)]
+    pub cleave_radii: BTreeMap<String, CleaveJson>,
+    ///rounds in one special or heavy ammo brick, keyed by family name
+    #[serde(default)]
+    pub brick_sizes: BTreeMap<String, u32>,
+    ///keyed by bow weapon or intrinsic hash
+    #[serde(default)]
+    pub bow_frames: BTreeMap<String, BowFrameJson>,
+    ///keyed by bow string perk hash
+    #[serde(default)]
+    pub bow_strings: BTreeMap<String, BowStringJson>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeaponFamilyJson {
+    #[serde(flatten)]
+    pub intrinsics: BTreeMap<String, WeaponIntrinsicJson>,
+    #[serde(default)]
+    pub cat: BTreeMap<String, CategoryJson>,
+    #[serde(rename = "subFam", default)]
+    pub sub_fam: BTreeMap<String, SubFamJson>,
+    #[serde(rename = "magProf", default)]
+    pub mag_prof: BTreeMap<String, AmmoJson>,
+}
+
+const fn default_pve() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponIntrinsicJson {
+    pub cat: String,
+    pub sub_fam: String,
+    pub mag_prof: String,
+    #[serde(default = "default_pve")]
+    pub pve: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct StatQuadraticJson {
+    #[serde(default)]
+    pub evpp: f64,
+    pub vpp: f64,
+    pub offset: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct CategoryJson {
+    pub range: RangeJson,
+    pub reload: ReloadJson,
+    pub handling: HandlingJson,
+    pub combatant_scalars: DamageModsJson,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct RangeJson {
+    pub vpp_start: f64,
+    pub offset_start: f64,
+    pub vpp_end: f64,
+    pub offset_end: f64,
+    pub floor_percent: f64,
+    #[serde(default)]
+    pub pve_floor_percent: Option<f64>,
+    pub fusion: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ReloadJson {
+    #[serde(flatten)]
+    pub reload_data: StatQuadraticJson,
+    #[serde(default)]
+    pub ammo_percent: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct HandlingJson {
+    pub ready: StatQuadraticJson,
+    pub stow: StatQuadraticJson,
+    pub ads: StatQuadraticJson,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct DamageModsJson {
+    pub minor: f64,
+    pub elite: f64,
+    pub miniboss: f64,
+    pub champion: f64,
+    pub boss: f64,
+    pub vehicle: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct AmmoJson {
+    pub mag: StatQuadraticJson,
+    #[serde(default)]
+    pub round_to: i32,
+    #[serde(default)]
+    pub reserve_id: u32,
+}
+
+const fn default_i32_1() -> i32 {
+    1
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct SubFamJson {
+    pub damage: f64,
+    pub crit_mult: f64,
+    pub pve_damage: f64,
+    pub pve_crit_mult: f64,
+    pub burst_delay: f64,
+    #[serde(default = "default_i32_1")]
+    pub burst_size: i32,
+    pub inner_burst_delay: f64,
+    pub one_ammo: Option<bool>,
+    pub charge: Option<bool>,
+}
+
+///Everything about a sword frame besides its light swing, which is the frame's firing data
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct SwordFrameJson {
+    ///light swings before the combo starts over
+    pub combo: i32,
+    ///damage of the last light swing of a combo against the others
+    pub finisher_scale: f64,
+    ///damage of a heavy attack against a light swing
+    pub heavy_scale: f64,
+    ///heavy attack length in frames, at the Swing Speed the light swing was timed at
+    pub heavy_delay: f64,
+    ///swing length multiplier over Swing Speed
+    pub swing_speed: StatQuadraticJson,
+    ///sword energy per second over Charge Rate, a heavy attack costs 100
+    pub energy_regen: StatQuadraticJson,
+    ///damage reduction while guarding over Guard Resistance
+    pub guard_resistance: StatQuadraticJson,
+    ///guard drained per point of damage blocked over Guard Efficiency
+    pub guard_efficiency: StatQuadraticJson,
+}
+
+///The draw of a bow frame, on top of the frame's firing data
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BowFrameJson {
+    ///seconds to draw over Draw Time
+    pub draw_time: StatQuadraticJson,
+    ///seconds a full draw can be held over Stability, frames without one never lose accuracy
+    #[serde(default)]
+    pub perfect_draw: Option<StatQuadraticJson>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BowStringJson {
+    ///seconds added to the draw, the bow string stat bump doesn't carry it
+    pub draw_time_add: f64,
+}
+
+///The melee and shield of a glaive frame, its projectile is the frame's firing data
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct GlaiveFrameJson {
+    ///damage of a melee hit against a projectile's impact
+    pub melee_scale: f64,
+    ///melee hit length in frames
+    pub melee_delay: f64,
+    ///melee hits it takes to charge a shot
+    pub melees_per_shot: i32,
+    ///damage reduction for whoever holds the shield up, 0.0-1.0
+    pub shield_resistance_pve: f64,
+    pub shield_resistance_pvp: f64,
+}
+
+///How far a family's explosions reach and how much damage they lose on the way
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct BlastJson {
+    ///meters over Blast Radius, families without the stat only use the offset
+    pub radius: StatQuadraticJson,
+    ///explosion damage at the very edge of the blast, rising linearly to full at the center
+    pub edge_damage: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct CleaveJson {
+    ///meters around the target that was shot the extra damage reaches
+    pub radius: f64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct ReserveLinearJson {
+    pub vpp: f64,
+    pub offset: f64,
+}
+
+const fn default_inv_mult() -> f64 {
+    5.0 / 3.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReserveJson {
+    Constant {
+        value: f64,
+    },
+    Linear {
+        vpp: f64,
+        offset: f64,
+        full_mag: Option<ReserveLinearJson>,
+    },
+    PerMag {
+        vpp: f64,
+        offset: f64,
+    },
+    MagTable {
+        offsets: Vec<(i32, f64)>,
+        default: f64,
+        #[serde(default = "default_inv_mult")]
+        inv_mult: f64,
+        #[serde(default)]
+        bonus: f64,
+    },
+    Exact {
+        values: Vec<(i32, f64)>,
+        default: f64,
+    },
+    Steps {
+        steps: Vec<(i32, f64)>,
+        default: f64,
+    },
+    MagRounding {
+        base: f64,
+        offset: f64,
+        round_scale: f64,
+        #[serde(default)]
+        mag_stat_scale: f64,
+        inv_mult: f64,
+    },
+    MagStatSteps {
+        steps: Vec<(i32, f64)>,
+        default: f64,
+        inv_mult: f64,
+    },
+}
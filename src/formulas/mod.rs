@@ -0,0 +1,572 @@
+mod convert;
+pub mod json_types;
+pub mod validate;
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+};
+
+use serde::Serialize;
+
+use crate::{
+    d2_enums::BungieHash,
+    database,
+    types::rs_types::{
+        AmmoFormula, BlastFormula, BowFrame, DamageMods, DataPointers, FiringData, GlaiveFrame,
+        HandlingFormula, RangeFormula, ReloadFormula, ReserveFormula, SwordFrame, WeaponPath,
+    },
+};
+
+use self::{convert::damage_mods_with_pve, json_types::WeaponFormulaJson};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeaponFormulas {
+    pub range: RangeFormula,
+    pub handling: HandlingFormula,
+    pub reload: ReloadFormula,
+    pub scalars: DamageMods,
+    pub firing: FiringData,
+    pub ammo: AmmoFormula,
+}
+impl WeaponFormulas {
+    fn from_pointers(pointers: DataPointers) -> WeaponFormulas {
+        WeaponFormulas {
+            range: database::RANGE_DATA[pointers.r],
+            handling: database::HANDLING_DATA[pointers.h],
+            reload: database::RELOAD_DATA[pointers.rl],
+            scalars: database::SCALAR_DATA[pointers.s],
+            firing: database::FIRING_DATA[pointers.f],
+            ammo: database::AMMO_DATA[pointers.a],
+        }
+    }
+
+    fn timestamps_cleared(mut self) -> WeaponFormulas {
+        self.range.timestamp = 0;
+        self.handling.timestamp = 0;
+        self.reload.timestamp = 0;
+        self.scalars.timestamp = 0;
+        self.firing.timestamp = 0;
+        self.ammo.timestamp = 0;
+        self
+    }
+
+    ///names of the components that differ from `other`, timestamps are ignored
+    pub fn changed_components(&self, other: &WeaponFormulas) -> Vec<String> {
+        let new = self.timestamps_cleared();
+        let old = other.timestamps_cleared();
+        let mut changed = Vec::new();
+        if new.range != old.range {
+            changed.push("range".to_owned());
+        }
+        if new.handling != old.handling {
+            changed.push("handling".to_owned());
+        }
+        if new.reload != old.reload {
+            changed.push("reload".to_owned());
+        }
+        if new.scalars != old.scalars {
+            changed.push("scalars".to_owned());
+        }
+        if new.firing != old.firing {
+            changed.push("firing".to_owned());
+        }
+        if new.ammo != old.ammo {
+            changed.push("ammo".to_owned());
+        }
+        changed
+    }
+
+    //unchanged components keep the timestamp they had, changed ones get the new one
+    fn stamp(&mut self, previous: Option<&WeaponFormulas>, timestamp: u64) {
+        let changed = previous.map(|x| self.changed_components(x));
+        macro_rules! stamp_field {
+            ($field:ident) => {
+                self.$field.timestamp = match (previous, &changed) {
+                    (Some(previous), Some(changed))
+                        if !changed.iter().any(|x| x == stringify!($field)) =>
+                    {
+                        previous.$field.timestamp
+                    }
+                    _ => timestamp,
+                };
+            };
+        }
+        stamp_field!(range);
+        stamp_field!(handling);
+        stamp_field!(reload);
+        stamp_field!(scalars);
+        stamp_field!(firing);
+        stamp_field!(ammo);
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponPathChange {
+    pub weapon_type_id: u32,
+    pub hash: BungieHash,
+    pub components: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormulaOverrideReport {
+    ///weapon paths that were not in the database before
+    pub added: Vec<WeaponPathChange>,
+    ///weapon paths whose formulas differ from what was loaded before
+    pub changed: Vec<WeaponPathChange>,
+    ///weapon paths in the document that match what was loaded before
+    pub unchanged: Vec<WeaponPathChange>,
+    ///reserve ids that are new or differ from what was loaded before
+    pub changed_reserves: Vec<u32>,
+    ///weapon type ids whose weapon type scalars are new or differ from what was loaded before
+    pub changed_type_scalars: Vec<u32>,
+    ///sword weapon and intrinsic hashes whose frame data is new or differs from what was loaded before
+    pub changed_sword_frames: Vec<BungieHash>,
+    ///glaive weapon and intrinsic hashes whose frame data is new or differs from what was loaded before
+    pub changed_glaive_frames: Vec<BungieHash>,
+    ///weapon type ids whose blast data is new or differs from what was loaded before
+    pub changed_blast_radii: Vec<u32>,
+    ///perk hashes whose cleave radius is new or differs from what was loaded before
+    pub changed_cleave_radii: Vec<BungieHash>,
+    ///weapon type ids whose brick size is new or differs from what was loaded before
+    pub changed_brick_sizes: Vec<u32>,
+    ///bow weapon and intrinsic hashes whose frame data is new or differs from what was loaded before
+    pub changed_bow_frames: Vec<BungieHash>,
+    ///bow string perk hashes whose draw time change is new or differs from what was loaded before
+    pub changed_bow_strings: Vec<BungieHash>,
+    ///validation warnings for the document, these do not stop it from loading
+    pub warnings: Vec<String>,
+}
+
+thread_local! {
+    static FORMULA_OVERRIDES: RefCell<HashMap<WeaponPath, WeaponFormulas>> = RefCell::new(HashMap::new());
+    static RESERVE_OVERRIDES: RefCell<HashMap<u32, ReserveFormula>> = RefCell::new(HashMap::new());
+    static TYPE_SCALAR_OVERRIDES: RefCell<HashMap<u32, DamageMods>> = RefCell::new(HashMap::new());
+    static SWORD_FRAME_OVERRIDES: RefCell<HashMap<BungieHash, SwordFrame>> = RefCell::new(HashMap::new());
+    static GLAIVE_FRAME_OVERRIDES: RefCell<HashMap<BungieHash, GlaiveFrame>> = RefCell::new(HashMap::new());
+    static BLAST_OVERRIDES: RefCell<HashMap<u32, BlastFormula>> = RefCell::new(HashMap::new());
+    static CLEAVE_OVERRIDES: RefCell<HashMap<BungieHash, f64>> = RefCell::new(HashMap::new());
+    static BRICK_OVERRIDES: RefCell<HashMap<u32, u32>> = RefCell::new(HashMap::new());
+    static BOW_FRAME_OVERRIDES: RefCell<HashMap<BungieHash, BowFrame>> = RefCell::new(HashMap::new());
+    static BOW_STRING_OVERRIDES: RefCell<HashMap<BungieHash, f64>> = RefCell::new(HashMap::new());
+}
+
+fn weapon_family_id(name: &str) -> Option<u32> {
+    validate::WEAPON_FAMILIES
+        .iter()
+        .find(|(family, _)| *family == name)
+        .map(|(_, id)| *id)
+}
+
+fn baked_formulas(path: &WeaponPath) -> Option<WeaponFormulas> {
+    database::DATA_POINTERS
+        .iter()
+        .find(|(x, _)| x == path)
+        .map(|(_, pointers)| WeaponFormulas::from_pointers(*pointers))
+}
+
+fn override_formulas(path: &WeaponPath) -> Option<WeaponFormulas> {
+    FORMULA_OVERRIDES.with(|overrides| overrides.borrow().get(path).cloned())
+}
+
+fn current_formulas(path: &WeaponPath) -> Option<WeaponFormulas> {
+    override_formulas(path).or_else(|| baked_formulas(path))
+}
+
+///Looks up the formulas for a weapon, weapon specific entries win over intrinsic ones
+///and runtime overrides win over the baked database
+pub fn get_weapon_formulas(
+    weapon_type_id: u8,
+    intrinsic_hash: BungieHash,
+    weapon_hash: BungieHash,
+) -> Option<WeaponFormulas> {
+    current_formulas(&WeaponPath(weapon_type_id as u32, weapon_hash))
+        .or_else(|| current_formulas(&WeaponPath(weapon_type_id as u32, intrinsic_hash)))
+}
+
+fn baked_reserve_formula(reserve_id: u32) -> Option<ReserveFormula> {
+    database::RESERVE_DATA
+        .binary_search_by_key(&reserve_id, |(id, _)| *id)
+        .ok()
+        .map(|index| database::RESERVE_DATA[index].1.clone())
+}
+
+///Looks up the reserve formula for an `AmmoFormula::reserve_id`,
+///runtime overrides win over the baked database
+pub fn get_reserve_formula(reserve_id: u32) -> Option<ReserveFormula> {
+    RESERVE_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&reserve_id).cloned())
+        .or_else(|| baked_reserve_formula(reserve_id))
+}
+
+fn baked_type_scalars(weapon_type_id: u32) -> Option<DamageMods> {
+    database::WEAPON_TYPE_SCALAR_DATA
+        .binary_search_by_key(&weapon_type_id, |(id, _)| *id)
+        .ok()
+        .map(|index| database::WEAPON_TYPE_SCALAR_DATA[index].1)
+}
+
+///Looks up the combatant multipliers every weapon of a type gets,
+///runtime overrides win over the baked database
+pub fn get_weapon_type_scalars(weapon_type_id: u32) -> Option<DamageMods> {
+    TYPE_SCALAR_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&weapon_type_id).cloned())
+        .or_else(|| baked_type_scalars(weapon_type_id))
+}
+
+fn current_sword_frame(hash: BungieHash) -> Option<SwordFrame> {
+    SWORD_FRAME_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&hash).cloned())
+        .or_else(|| {
+            database::SWORD_FRAME_DATA
+                .binary_search_by_key(&hash, |(x, _)| *x)
+                .ok()
+                .map(|index| database::SWORD_FRAME_DATA[index].1)
+        })
+}
+
+///Looks up the frame data of a sword, keyed like its weapon formulas: weapon specific
+///entries win over intrinsic ones and runtime overrides win over the baked database
+pub fn get_sword_frame(intrinsic_hash: BungieHash, weapon_hash: BungieHash) -> Option<SwordFrame> {
+    current_sword_frame(weapon_hash).or_else(|| current_sword_frame(intrinsic_hash))
+}
+
+fn current_glaive_frame(hash: BungieHash) -> Option<GlaiveFrame> {
+    GLAIVE_FRAME_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&hash).cloned())
+        .or_else(|| {
+            database::GLAIVE_FRAME_DATA
+                .binary_search_by_key(&hash, |(x, _)| *x)
+                .ok()
+                .map(|index| database::GLAIVE_FRAME_DATA[index].1)
+        })
+}
+
+///Looks up the melee and shield data of a glaive, keyed like its weapon formulas:
+///weapon specific entries win over intrinsic ones and runtime overrides win over the baked database
+pub fn get_glaive_frame(
+    intrinsic_hash: BungieHash,
+    weapon_hash: BungieHash,
+) -> Option<GlaiveFrame> {
+    current_glaive_frame(weapon_hash).or_else(|| current_glaive_frame(intrinsic_hash))
+}
+
+fn baked_blast_formula(weapon_type_id: u32) -> Option<BlastFormula> {
+    database::BLAST_DATA
+        .binary_search_by_key(&weapon_type_id, |(id, _)| *id)
+        .ok()
+        .map(|index| database::BLAST_DATA[index].1)
+}
+
+///Looks up how far a weapon type's explosions reach,
+///runtime overrides win over the baked database
+pub fn get_blast_formula(weapon_type_id: u32) -> Option<BlastFormula> {
+    BLAST_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&weapon_type_id).cloned())
+        .or_else(|| baked_blast_formula(weapon_type_id))
+}
+
+///Looks up how far the extra damage of a perk cleaves,
+///runtime overrides win over the baked database
+pub fn get_cleave_radius(perk_hash: BungieHash) -> Option<f64> {
+    CLEAVE_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&perk_hash).copied())
+        .or_else(|| {
+            database::CLEAVE_DATA
+                .binary_search_by_key(&perk_hash, |(x, _)| *x)
+                .ok()
+                .map(|index| database::CLEAVE_DATA[index].1)
+        })
+}
+
+///Looks up how many rounds one ammo brick gives a weapon type,
+///runtime overrides win over the baked database
+pub fn get_brick_size(weapon_type_id: u32) -> Option<u32> {
+    BRICK_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&weapon_type_id).copied())
+        .or_else(|| {
+            database::BRICK_DATA
+                .binary_search_by_key(&weapon_type_id, |(id, _)| *id)
+                .ok()
+                .map(|index| database::BRICK_DATA[index].1)
+        })
+}
+
+fn current_bow_frame(hash: BungieHash) -> Option<BowFrame> {
+    BOW_FRAME_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&hash).cloned())
+        .or_else(|| {
+            database::BOW_FRAME_DATA
+                .binary_search_by_key(&hash, |(x, _)| *x)
+                .ok()
+                .map(|index| database::BOW_FRAME_DATA[index].1)
+        })
+}
+
+///Looks up the draw of a bow, keyed like its weapon formulas:
+///weapon specific entries win over intrinsic ones and runtime overrides win over the baked database
+pub fn get_bow_frame(intrinsic_hash: BungieHash, weapon_hash: BungieHash) -> Option<BowFrame> {
+    current_bow_frame(weapon_hash).or_else(|| current_bow_frame(intrinsic_hash))
+}
+
+///Looks up the seconds a bow string perk adds to the draw,
+///runtime overrides win over the baked database
+pub fn get_bow_string(perk_hash: BungieHash) -> Option<f64> {
+    BOW_STRING_OVERRIDES
+        .with(|overrides| overrides.borrow().get(&perk_hash).copied())
+        .or_else(|| {
+            database::BOW_STRING_DATA
+                .binary_search_by_key(&perk_hash, |(x, _)| *x)
+                .ok()
+                .map(|index| database::BOW_STRING_DATA[index].1)
+        })
+}
+
+///A weapon_formulas.json style document that passed validation
+#[derive(Debug, Clone, Default)]
+pub struct FormulaDocument {
+    ///keyed by weapon type id and weapon/intrinsic hash
+    pub weapons: BTreeMap<(u32, BungieHash), WeaponFormulas>,
+    ///keyed by reserve id
+    pub reserves: BTreeMap<u32, ReserveFormula>,
+    ///keyed by weapon type id
+    pub weapon_type_scalars: BTreeMap<u32, DamageMods>,
+    ///keyed by sword weapon or intrinsic hash
+    pub sword_frames: BTreeMap<BungieHash, SwordFrame>,
+    ///keyed by glaive weapon or intrinsic hash
+    pub glaive_frames: BTreeMap<BungieHash, GlaiveFrame>,
+    ///keyed by weapon type id
+    pub blast_radii: BTreeMap<u32, BlastFormula>,
+    ///keyed by perk hash
+    pub cleave_radii: BTreeMap<BungieHash, f64>,
+    ///keyed by weapon type id
+    pub brick_sizes: BTreeMap<u32, u32>,
+    ///keyed by bow weapon or intrinsic hash
+    pub bow_frames: BTreeMap<BungieHash, BowFrame>,
+    ///keyed by bow string perk hash
+    pub bow_strings: BTreeMap<BungieHash, f64>,
+    pub warnings: Vec<String>,
+}
+
+///Parses a weapon_formulas.json style document after running it through the validator,
+///every reference has to resolve inside its own weapon family
+pub fn parse_formula_document(json: &str) -> Result<FormulaDocument, Vec<String>> {
+    let diagnostics = validate::validate_weapon_formulas(json);
+    if validate::has_errors(&diagnostics) {
+        return Err(diagnostics
+            .iter()
+            .filter(|x| x.severity == validate::Severity::Error)
+            .map(|x| x.to_string())
+            .collect());
+    }
+    let warnings = diagnostics.iter().map(|x| x.to_string()).collect();
+
+    let document: WeaponFormulaJson =
+        serde_json::from_str(json).map_err(|err| vec![format!("error: $: {}", err)])?;
+    let mut parsed = FormulaDocument {
+        warnings,
+        ..Default::default()
+    };
+    for (id, reserve) in document.reserves {
+        if let Ok(id) = id.parse::<u32>() {
+            parsed.reserves.insert(id, reserve.into());
+        }
+    }
+    for (family_name, scalars) in document.weapon_type_scalars {
+        if let Some(family_id) = weapon_family_id(&family_name) {
+            parsed
+                .weapon_type_scalars
+                .insert(family_id, damage_mods_with_pve(scalars, 1.0));
+        }
+    }
+    for (hash, frame) in document.sword_frames {
+        if let Ok(hash) = hash.parse::<BungieHash>() {
+            parsed.sword_frames.insert(hash, frame.into());
+        }
+    }
+    for (hash, frame) in document.glaive_frames {
+        if let Ok(hash) = hash.parse::<BungieHash>() {
+            parsed.glaive_frames.insert(hash, frame.into());
+        }
+    }
+    for (family_name, blast) in document.blast_radii {
+        if let Some(family_id) = weapon_family_id(&family_name) {
+            parsed.blast_radii.insert(family_id, blast.into());
+        }
+    }
+    for (hash, cleave) in document.cleave_radii {
+        if let Ok(hash) = hash.parse::<BungieHash>() {
+            parsed.cleave_radii.insert(hash, cleave.radius);
+        }
+    }
+    for (family_name, rounds) in document.brick_sizes {
+        if let Some(family_id) = weapon_family_id(&family_name) {
+            parsed.brick_sizes.insert(family_id, rounds);
+        }
+    }
+    for (hash, frame) in document.bow_frames {
+        if let Ok(hash) = hash.parse::<BungieHash>() {
+            parsed.bow_frames.insert(hash, frame.into());
+        }
+    }
+    for (hash, string) in document.bow_strings {
+        if let Ok(hash) = hash.parse::<BungieHash>() {
+            parsed.bow_strings.insert(hash, string.draw_time_add);
+        }
+    }
+    for (family_name, family) in document.types.iter() {
+        let Some(family_id) = weapon_family_id(family_name) else {
+            continue;
+        };
+        for (key, intrinsic) in family.intrinsics.iter() {
+            let (Ok(hash), Some(cat), Some(sub_fam), Some(mag_prof)) = (
+                key.parse::<BungieHash>(),
+                family.cat.get(&intrinsic.cat),
+                family.sub_fam.get(&intrinsic.sub_fam),
+                family.mag_prof.get(&intrinsic.mag_prof),
+            ) else {
+                continue;
+            };
+            parsed.weapons.insert(
+                (family_id, hash),
+                WeaponFormulas {
+                    range: cat.range.into(),
+                    handling: cat.handling.into(),
+                    reload: cat.reload.into(),
+                    scalars: damage_mods_with_pve(cat.combatant_scalars, intrinsic.pve),
+                    firing: (*sub_fam).into(),
+                    ammo: (*mag_prof).into(),
+                },
+            );
+        }
+    }
+    Ok(parsed)
+}
+
+///Loads a weapon_formulas.json style document on top of the baked database.
+///Nothing is applied unless the whole document validates.
+///`timestamp` is used for every formula that changed, since wasm has no clock of its own
+pub fn load_formula_overrides(
+    json: &str,
+    timestamp: u64,
+) -> Result<FormulaOverrideReport, Vec<String>> {
+    let parsed = parse_formula_document(json)?;
+    let mut report = FormulaOverrideReport {
+        warnings: parsed.warnings,
+        ..Default::default()
+    };
+    FORMULA_OVERRIDES.with(|overrides| {
+        let mut overrides = overrides.borrow_mut();
+        for ((weapon_type_id, hash), mut formulas) in parsed.weapons {
+            let path = WeaponPath(weapon_type_id, hash);
+            let previous = overrides
+                .get(&path)
+                .cloned()
+                .or_else(|| baked_formulas(&path));
+            formulas.stamp(previous.as_ref(), timestamp);
+            let mut change = WeaponPathChange {
+                weapon_type_id,
+                hash,
+                components: Vec::new(),
+            };
+            match previous {
+                None => report.added.push(change),
+                Some(previous) => {
+                    change.components = formulas.changed_components(&previous);
+                    if change.components.is_empty() {
+                        report.unchanged.push(change);
+                    } else {
+                        report.changed.push(change);
+                    }
+                }
+            }
+            overrides.insert(path, formulas);
+        }
+    });
+    for (id, reserve) in parsed.reserves {
+        if get_reserve_formula(id).as_ref() != Some(&reserve) {
+            report.changed_reserves.push(id);
+        }
+        RESERVE_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(id, reserve));
+    }
+    for (id, mut scalars) in parsed.weapon_type_scalars {
+        let previous = get_weapon_type_scalars(id);
+        scalars.timestamp = previous.map_or(0, |x| x.timestamp);
+        if previous != Some(scalars) {
+            scalars.timestamp = timestamp;
+            report.changed_type_scalars.push(id);
+        }
+        TYPE_SCALAR_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(id, scalars));
+    }
+    for (hash, mut frame) in parsed.sword_frames {
+        let previous = current_sword_frame(hash);
+        frame.timestamp = previous.map_or(0, |x| x.timestamp);
+        if previous != Some(frame) {
+            frame.timestamp = timestamp;
+            report.changed_sword_frames.push(hash);
+        }
+        SWORD_FRAME_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(hash, frame));
+    }
+    for (hash, mut frame) in parsed.glaive_frames {
+        let previous = current_glaive_frame(hash);
+        frame.timestamp = previous.map_or(0, |x| x.timestamp);
+        if previous != Some(frame) {
+            frame.timestamp = timestamp;
+            report.changed_glaive_frames.push(hash);
+        }
+        GLAIVE_FRAME_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(hash, frame));
+    }
+    for (id, mut blast) in parsed.blast_radii {
+        let previous = get_blast_formula(id);
+        blast.timestamp = previous.map_or(0, |x| x.timestamp);
+        if previous != Some(blast) {
+            blast.timestamp = timestamp;
+            report.changed_blast_radii.push(id);
+        }
+        BLAST_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(id, blast));
+    }
+    for (hash, radius) in parsed.cleave_radii {
+        if get_cleave_radius(hash) != Some(radius) {
+            report.changed_cleave_radii.push(hash);
+        }
+        CLEAVE_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(hash, radius));
+    }
+    for (id, rounds) in parsed.brick_sizes {
+        if get_brick_size(id) != Some(rounds) {
+            report.changed_brick_sizes.push(id);
+        }
+        BRICK_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(id, rounds));
+    }
+    for (hash, mut frame) in parsed.bow_frames {
+        let previous = current_bow_frame(hash);
+        frame.timestamp = previous.map_or(0, |x| x.timestamp);
+        if previous != Some(frame) {
+            frame.timestamp = timestamp;
+            report.changed_bow_frames.push(hash);
+        }
+        BOW_FRAME_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(hash, frame));
+    }
+    for (hash, draw_time_add) in parsed.bow_strings {
+        if get_bow_string(hash) != Some(draw_time_add) {
+            report.changed_bow_strings.push(hash);
+        }
+        BOW_STRING_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(hash, draw_time_add));
+    }
+    Ok(report)
+}
+
+///Drops every runtime override, going back to the baked database
+pub fn reset_formula_overrides() {
+    FORMULA_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    RESERVE_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    TYPE_SCALAR_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    SWORD_FRAME_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    GLAIVE_FRAME_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    BLAST_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    CLEAVE_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    BRICK_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    BOW_FRAME_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+    BOW_STRING_OVERRIDES.with(|overrides| overrides.borrow_mut().clear());
+}
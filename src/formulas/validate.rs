@@ -0,0 +1,816 @@
+//! Validation for weapon_formulas.json style documents.
+//!
+//! This file is shared by the library, build.rs and the `validate_formulas` binary,
+//! so it may only depend on std, serde and serde_json.
+use std::{collections::BTreeSet, fmt};
+
+use serde::{
+    de::{MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use serde_json::Value;
+
+///Weapon family names and the weapon type ids they map to, mirrors NAME_TO_ID in build.rs
+pub const WEAPON_FAMILIES: [(&str, u32); 17] = [
+    ("Auto Rifle", 6),
+    ("Combat Bow", 31),
+    ("Fusion Rifle", 11),
+    ("Grenade Launcher", 23),
+    ("Hand Cannon", 9),
+    ("Linear Fusion Rifle", 22),
+    ("Machine Gun", 8),
+    ("Pulse Rifle", 13),
+    ("Rocket Launcher", 10),
+    ("Scout Rifle", 14),
+    ("Shotgun", 7),
+    ("Sniper Rifle", 12),
+    ("Submachine Gun", 24),
+    ("Glaive", 33),
+    ("Sword", 18),
+    ("Trace Rifle", 25),
+    ("Sidearm", 17),
+];
+
+const SECTIONS: [&str; 3] = ["cat", "subFam", "magProf"];
+
+///Top level section holding reserve curves keyed by reserve id
+pub const RESERVES: &str = "reserves";
+
+///Top level section holding combatant multipliers for whole weapon families
+pub const WEAPON_TYPE_SCALARS: &str = "weapon_type_scalars";
+
+///Top level section holding sword frame data keyed by weapon or intrinsic hash
+pub const SWORD_FRAMES: &str = "sword_frames";
+
+///Top level section holding glaive melee and shield data keyed by weapon or intrinsic hash
+pub const GLAIVE_FRAMES: &str = "glaive_frames";
+
+///Top level section holding explosion reach for whole weapon families
+pub const BLAST_RADII: &str = "blast_radii";
+
+///Top level section holding how far extra damage perks cleave keyed by perk hash
+pub const CLEAVE_RADII: &str = "cleave_radii";
+
+///Top level section holding the rounds in one ammo brick for whole weapon families
+pub const BRICK_SIZES: &str = "brick_sizes";
+
+///Top level section holding bow draw data keyed by weapon or intrinsic hash
+pub const BOW_FRAMES: &str = "bow_frames";
+
+///Top level section holding the draw time change of bow strings keyed by perk hash
+pub const BOW_STRINGS: &str = "bow_strings";
+
+const COMBATANTS: [&str; 6] = ["minor", "elite", "miniboss", "champion", "boss", "vehicle"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    ///JSON path of the offending value, ex. `$["Pulse Rifle"]["69420"]["subFam"]`
+    pub path: String,
+    pub message: String,
+}
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}: {}", severity, self.path, self.message)
+    }
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|x| x.severity == Severity::Error)
+}
+
+//json objects in key order with duplicates kept, serde_json silently drops them otherwise
+struct Entries<T>(Vec<(String, T)>);
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Entries<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor<T>(std::marker::PhantomData<T>);
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for EntriesVisitor<T> {
+            type Value = Entries<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry::<String, T>()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+        deserializer.deserialize_map(EntriesVisitor(std::marker::PhantomData))
+    }
+}
+
+fn path_join(path: &str, key: &str) -> String {
+    format!("{}[{:?}]", path, key)
+}
+
+struct Validator {
+    diagnostics: Vec<Diagnostic>,
+}
+impl Validator {
+    fn error(&mut self, path: &str, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            path: path.to_owned(),
+            message,
+        });
+    }
+
+    fn warning(&mut self, path: &str, message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            path: path.to_owned(),
+            message,
+        });
+    }
+
+    fn duplicates<T>(&mut self, path: &str, entries: &[(String, T)], what: &str) {
+        let mut seen = BTreeSet::new();
+        for (key, _) in entries {
+            if !seen.insert(key) {
+                self.error(&path_join(path, key), format!("duplicate {}", what));
+            }
+        }
+    }
+
+    fn number(&mut self, path: &str, value: &Value, key: &str, required: bool) -> Option<f64> {
+        match value.get(key) {
+            Some(Value::Number(num)) => num.as_f64(),
+            Some(_) => {
+                self.error(&path_join(path, key), "expected a number".to_owned());
+                None
+            }
+            None if required => {
+                self.error(path, format!("missing field \"{}\"", key));
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn numbers(&mut self, path: &str, value: &Value, required: &[&str], optional: &[&str]) {
+        if !value.is_object() {
+            self.error(path, "expected an object".to_owned());
+            return;
+        }
+        for key in required {
+            self.number(path, value, key, true);
+        }
+        for key in optional {
+            self.number(path, value, key, false);
+        }
+    }
+
+    fn object<'a>(&mut self, path: &str, value: &'a Value, key: &str) -> Option<&'a Value> {
+        match value.get(key) {
+            Some(inner) if inner.is_object() => Some(inner),
+            Some(_) => {
+                self.error(&path_join(path, key), "expected an object".to_owned());
+                None
+            }
+            None => {
+                self.error(path, format!("missing field \"{}\"", key));
+                None
+            }
+        }
+    }
+
+    //checks a quadratic stat curve only moves one way over the stats the game can reach
+    fn curve(&mut self, path: &str, value: &Value, max_stat: i32, decreasing: bool, what: &str) {
+        if !value.is_object() {
+            self.error(path, "expected an object".to_owned());
+            return;
+        }
+        let evpp = self.number(path, value, "evpp", false).unwrap_or_default();
+        let (Some(vpp), Some(offset)) = (
+            self.number(path, value, "vpp", true),
+            self.number(path, value, "offset", true),
+        ) else {
+            return;
+        };
+        let solve = |x: i32| evpp * (x * x) as f64 + vpp * x as f64 + offset;
+        for stat in 0..max_stat {
+            let delta = solve(stat + 1) - solve(stat);
+            if (decreasing && delta > 0.0) || (!decreasing && delta < 0.0) {
+                self.error(
+                    path,
+                    format!(
+                        "{} gets worse going from {} to {} stat",
+                        what,
+                        stat,
+                        stat + 1
+                    ),
+                );
+                return;
+            }
+        }
+    }
+
+    fn category(&mut self, path: &str, cat: &Value, family: &str) {
+        if let Some(range) = self.object(path, cat, "range") {
+            let range_path = path_join(path, "range");
+            self.numbers(
+                &range_path,
+                range,
+                &[
+                    "vpp_start",
+                    "offset_start",
+                    "vpp_end",
+                    "offset_end",
+                    "floor_percent",
+                ],
+                &["pve_floor_percent"],
+            );
+            for key in ["vpp_start", "vpp_end"] {
+                if range.get(key).and_then(Value::as_f64).unwrap_or_default() < 0.0 {
+                    self.error(
+                        &path_join(&range_path, key),
+                        "falloff gets worse with more range stat".to_owned(),
+                    );
+                }
+            }
+        }
+        if let Some(reload) = self.object(path, cat, "reload") {
+            //bows clamp reload to 85, see calc_reload_time
+            let max_stat = if family == "Combat Bow" { 85 } else { 100 };
+            let reload_path = path_join(path, "reload");
+            self.number(&reload_path, reload, "ammo_percent", false);
+            self.curve(&reload_path, reload, max_stat, true, "reload time");
+        }
+        if let Some(handling) = self.object(path, cat, "handling") {
+            let handling_path = path_join(path, "handling");
+            for key in ["ready", "stow", "ads"] {
+                if let Some(curve) = self.object(&handling_path, handling, key) {
+                    let what = format!("{} time", key);
+                    self.curve(&path_join(&handling_path, key), curve, 100, true, &what);
+                }
+            }
+        }
+        if let Some(scalars) = self.object(path, cat, "combatant_scalars") {
+            self.numbers(
+                &path_join(path, "combatant_scalars"),
+                scalars,
+                &COMBATANTS,
+                &[],
+            );
+        }
+    }
+
+    //[[stat, value], ...] sorted by stat, returns the pairs that parsed
+    fn table(&mut self, path: &str, value: &Value, key: &str) -> Vec<(f64, f64)> {
+        let table_path = path_join(path, key);
+        let Some(rows) = value.get(key) else {
+            self.error(path, format!("missing field \"{}\"", key));
+            return Vec::new();
+        };
+        let Some(rows) = rows.as_array() else {
+            self.error(&table_path, "expected an array".to_owned());
+            return Vec::new();
+        };
+        let mut pairs: Vec<(f64, f64)> = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            let row_path = format!("{}[{}]", table_path, i);
+            let pair = row.as_array().and_then(|x| match x.as_slice() {
+                [stat, value] if stat.is_i64() => Some((stat.as_f64()?, value.as_f64()?)),
+                _ => None,
+            });
+            let Some(pair) = pair else {
+                self.error(&row_path, "expected [whole number, number]".to_owned());
+                continue;
+            };
+            if pairs.last().map_or(false, |last| last.0 >= pair.0) {
+                self.error(
+                    &row_path,
+                    "table is not sorted by its first value".to_owned(),
+                );
+            }
+            pairs.push(pair);
+        }
+        pairs
+    }
+
+    fn reserve(&mut self, path: &str, value: &Value) {
+        if !value.is_object() {
+            self.error(path, "expected an object".to_owned());
+            return;
+        }
+        let kind = match value.get("kind") {
+            Some(Value::String(kind)) => kind.as_str(),
+            Some(_) => {
+                self.error(&path_join(path, "kind"), "expected a string".to_owned());
+                return;
+            }
+            None => {
+                self.error(path, "missing field \"kind\"".to_owned());
+                return;
+            }
+        };
+        let mut slopes = Vec::new();
+        match kind {
+            "constant" => {
+                self.number(path, value, "value", true);
+            }
+            "linear" | "per_mag" => {
+                slopes.push((path.to_owned(), self.number(path, value, "vpp", true)));
+                self.number(path, value, "offset", true);
+                if kind == "linear" && value.get("full_mag").is_some() {
+                    if let Some(full_mag) = self.object(path, value, "full_mag") {
+                        let full_mag_path = path_join(path, "full_mag");
+                        self.number(&full_mag_path, full_mag, "offset", true);
+                        let vpp = self.number(&full_mag_path, full_mag, "vpp", true);
+                        slopes.push((full_mag_path, vpp));
+                    }
+                }
+            }
+            "mag_rounding" => {
+                self.number(path, value, "base", true);
+                self.number(path, value, "offset", true);
+                self.number(path, value, "round_scale", true);
+                self.number(path, value, "mag_stat_scale", false);
+                self.inv_mult(path, value, true);
+            }
+            "mag_stat_steps" => {
+                self.table(path, value, "steps");
+                self.number(path, value, "default", true);
+                self.inv_mult(path, value, true);
+            }
+            "mag_table" => {
+                self.table(path, value, "offsets");
+                self.number(path, value, "default", true);
+                self.number(path, value, "bonus", false);
+                self.inv_mult(path, value, false);
+            }
+            "exact" | "steps" => {
+                let key = if kind == "exact" { "values" } else { "steps" };
+                let mut values: Vec<f64> = self
+                    .table(path, value, key)
+                    .into_iter()
+                    .map(|(_, value)| value)
+                    .collect();
+                if let Some(default) = self.number(path, value, "default", true) {
+                    values.push(default);
+                }
+                if values.windows(2).any(|x| x[1] < x[0]) {
+                    self.error(path, "reserves go down with more inventory stat".to_owned());
+                }
+            }
+            _ => self.error(
+                &path_join(path, "kind"),
+                format!("unknown reserve kind \"{}\"", kind),
+            ),
+        }
+        for (path, vpp) in slopes {
+            if vpp.unwrap_or_default() < 0.0 {
+                self.error(
+                    &path_join(&path, "vpp"),
+                    "reserves go down with more inventory stat".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn inv_mult(&mut self, path: &str, value: &Value, required: bool) {
+        if let Some(inv_mult) = self.number(path, value, "inv_mult", required) {
+            if inv_mult < 1.0 {
+                self.error(
+                    &path_join(path, "inv_mult"),
+                    "reserves go down with more inventory stat".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn reserves(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "reserve id");
+        for (key, value) in entries {
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(&entry_path, "key is not a reserve id".to_owned());
+            }
+            self.reserve(&entry_path, value);
+        }
+    }
+
+    fn weapon_type_scalars(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "weapon family");
+        for (family_name, value) in entries {
+            let entry_path = path_join(path, family_name);
+            if !WEAPON_FAMILIES.iter().any(|(name, _)| name == family_name) {
+                self.error(&entry_path, "unknown weapon family".to_owned());
+                continue;
+            }
+            self.numbers(&entry_path, value, &COMBATANTS, &[]);
+            for key in COMBATANTS {
+                if value
+                    .get(key)
+                    .and_then(Value::as_f64)
+                    .map_or(false, |x| x <= 0.0)
+                {
+                    self.error(
+                        &path_join(&entry_path, key),
+                        "multiplier has to be positive".to_owned(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn sword_frames(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "intrinsic hash");
+        for (key, value) in entries {
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(
+                    &entry_path,
+                    "key is not a weapon or intrinsic hash".to_owned(),
+                );
+            }
+            self.numbers(
+                &entry_path,
+                value,
+                &["combo", "finisher_scale", "heavy_scale", "heavy_delay"],
+                &[],
+            );
+            if value
+                .get("combo")
+                .and_then(Value::as_f64)
+                .map_or(false, |x| x < 1.0 || x.fract() != 0.0)
+            {
+                self.error(
+                    &path_join(&entry_path, "combo"),
+                    "combo has to be a whole number of swings".to_owned(),
+                );
+            }
+            for (key, decreasing, what) in [
+                ("swing_speed", true, "swing time"),
+                ("energy_regen", false, "energy regen"),
+                ("guard_resistance", false, "guard resistance"),
+                ("guard_efficiency", true, "guard drain"),
+            ] {
+                if let Some(curve) = self.object(&entry_path, value, key) {
+                    self.curve(&path_join(&entry_path, key), curve, 100, decreasing, what);
+                }
+            }
+        }
+    }
+
+    fn glaive_frames(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "intrinsic hash");
+        for (key, value) in entries {
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(
+                    &entry_path,
+                    "key is not a weapon or intrinsic hash".to_owned(),
+                );
+            }
+            self.numbers(
+                &entry_path,
+                value,
+                &[
+                    "melee_scale",
+                    "melee_delay",
+                    "melees_per_shot",
+                    "shield_resistance_pve",
+                    "shield_resistance_pvp",
+                ],
+                &[],
+            );
+            if value
+                .get("melees_per_shot")
+                .and_then(Value::as_f64)
+                .map_or(false, |x| x < 1.0 || x.fract() != 0.0)
+            {
+                self.error(
+                    &path_join(&entry_path, "melees_per_shot"),
+                    "melees per shot has to be a whole number of hits".to_owned(),
+                );
+            }
+            for key in ["shield_resistance_pve", "shield_resistance_pvp"] {
+                if value
+                    .get(key)
+                    .and_then(Value::as_f64)
+                    .map_or(false, |x| !(0.0..1.0).contains(&x))
+                {
+                    self.error(
+                        &path_join(&entry_path, key),
+                        "resistance has to be between 0 and 1".to_owned(),
+                    );
+                }
+            }
+        }
+    }
+
+    fn blast_radii(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "weapon family");
+        for (family_name, value) in entries {
+            let entry_path = path_join(path, family_name);
+            if !WEAPON_FAMILIES.iter().any(|(name, _)| name == family_name) {
+                self.error(&entry_path, "unknown weapon family".to_owned());
+                continue;
+            }
+            self.numbers(&entry_path, value, &["edge_damage"], &[]);
+            if value
+                .get("edge_damage")
+                .and_then(Value::as_f64)
+                .map_or(false, |x| !(0.0..=1.0).contains(&x))
+            {
+                self.error(
+                    &path_join(&entry_path, "edge_damage"),
+                    "edge damage has to be between 0 and 1".to_owned(),
+                );
+            }
+            if let Some(radius) = self.object(&entry_path, value, "radius") {
+                self.curve(
+                    &path_join(&entry_path, "radius"),
+                    radius,
+                    100,
+                    false,
+                    "blast radius",
+                );
+            }
+        }
+    }
+
+    fn cleave_radii(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "perk hash");
+        for (key, value) in entries {
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(&entry_path, "key is not a perk hash".to_owned());
+            }
+            self.numbers(&entry_path, value, &["radius"], &[]);
+            if value
+                .get("radius")
+                .and_then(Value::as_f64)
+                .map_or(false, |x| x <= 0.0)
+            {
+                self.error(
+                    &path_join(&entry_path, "radius"),
+                    "a cleave has to reach past the target that was shot".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn brick_sizes(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "weapon family");
+        for (family_name, value) in entries {
+            let entry_path = path_join(path, family_name);
+            if !WEAPON_FAMILIES.iter().any(|(name, _)| name == family_name) {
+                self.error(&entry_path, "unknown weapon family".to_owned());
+                continue;
+            }
+            if !value.as_u64().map_or(false, |x| x >= 1) {
+                self.error(
+                    &entry_path,
+                    "brick size has to be a whole number of rounds".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn bow_frames(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "intrinsic hash");
+        for (key, value) in entries {
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(
+                    &entry_path,
+                    "key is not a weapon or intrinsic hash".to_owned(),
+                );
+            }
+            if let Some(curve) = self.object(&entry_path, value, "draw_time") {
+                let curve_path = path_join(&entry_path, "draw_time");
+                self.curve(&curve_path, curve, 100, true, "draw time");
+            }
+            if value.get("perfect_draw").is_some() {
+                if let Some(curve) = self.object(&entry_path, value, "perfect_draw") {
+                    let curve_path = path_join(&entry_path, "perfect_draw");
+                    self.curve(&curve_path, curve, 100, false, "perfect draw");
+                }
+            }
+        }
+    }
+
+    fn bow_strings(&mut self, path: &str, entries: &[(String, Value)]) {
+        self.duplicates(path, entries, "perk hash");
+        for (key, value) in entries {
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(&entry_path, "key is not a perk hash".to_owned());
+            }
+            self.numbers(&entry_path, value, &["draw_time_add"], &[]);
+            if value
+                .get("draw_time_add")
+                .and_then(Value::as_f64)
+                .map_or(false, |x| x.abs() >= 1.0)
+            {
+                self.error(
+                    &path_join(&entry_path, "draw_time_add"),
+                    "a string can't move the draw by a second or more".to_owned(),
+                );
+            }
+        }
+    }
+
+    //a magProf reserve id has to be in the document's reserves section when it has one,
+    //documents without one lean on the baked reserves
+    fn reserve_id(&mut self, path: &str, value: &Value, reserves: Option<&BTreeSet<u32>>) {
+        let (Some(reserves), Some(id)) =
+            (reserves, value.get("reserve_id").and_then(Value::as_u64))
+        else {
+            return;
+        };
+        let id = id as u32;
+        if !reserves.contains(&id) {
+            self.error(
+                &path_join(path, "reserve_id"),
+                format!("{} is not defined in {}", id, RESERVES),
+            );
+        }
+    }
+
+    fn family(
+        &mut self,
+        path: &str,
+        family_name: &str,
+        entries: &[(String, Value)],
+        reserves: Option<&BTreeSet<u32>>,
+    ) {
+        self.duplicates(path, entries, "weapon hash");
+        let mut used: [BTreeSet<String>; 3] = Default::default();
+
+        for (key, value) in entries {
+            if SECTIONS.contains(&key.as_str()) {
+                continue;
+            }
+            let entry_path = path_join(path, key);
+            if key.parse::<u32>().is_err() {
+                self.error(
+                    &entry_path,
+                    "key is not a weapon or intrinsic hash".to_owned(),
+                );
+            }
+            if !value.is_object() {
+                self.error(&entry_path, "expected an object".to_owned());
+                continue;
+            }
+            for (i, section) in SECTIONS.iter().enumerate() {
+                match value.get(section) {
+                    Some(Value::String(name)) => {
+                        let exists = entries
+                            .iter()
+                            .any(|(k, v)| k == section && v.get(name).is_some());
+                        if !exists {
+                            self.error(
+                                &path_join(&entry_path, section),
+                                format!("\"{}\" is not defined in {}", name, section),
+                            );
+                        }
+                        used[i].insert(name.clone());
+                    }
+                    Some(_) => self.error(
+                        &path_join(&entry_path, section),
+                        "expected a string".to_owned(),
+                    ),
+                    None => self.error(&entry_path, format!("missing field \"{}\"", section)),
+                }
+            }
+            self.number(&entry_path, value, "pve", false);
+        }
+
+        for (i, section) in SECTIONS.iter().enumerate() {
+            let section_path = path_join(path, section);
+            let Some((_, defs)) = entries.iter().find(|(k, _)| k == section) else {
+                continue;
+            };
+            let Some(defs) = defs.as_object() else {
+                self.error(&section_path, "expected an object".to_owned());
+                continue;
+            };
+            for (name, def) in defs {
+                let def_path = path_join(&section_path, name);
+                if !used[i].contains(name) {
+                    self.warning(&def_path, "defined but never used".to_owned());
+                }
+                match *section {
+                    "cat" => self.category(&def_path, def, family_name),
+                    "subFam" => self.numbers(
+                        &def_path,
+                        def,
+                        &[
+                            "damage",
+                            "crit_mult",
+                            "pve_damage",
+                            "pve_crit_mult",
+                            "burst_delay",
+                            "inner_burst_delay",
+                        ],
+                        &["burst_size"],
+                    ),
+                    _ => {
+                        if let Some(mag) = self.object(&def_path, def, "mag") {
+                            self.curve(&path_join(&def_path, "mag"), mag, 100, false, "mag size");
+                        }
+                        self.numbers(&def_path, def, &[], &["round_to", "reserve_id"]);
+                        self.reserve_id(&def_path, def, reserves);
+                    }
+                }
+            }
+        }
+    }
+}
+
+///Validates a weapon_formulas.json style document, returning every problem found
+pub fn validate_weapon_formulas(json: &str) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        diagnostics: Vec::new(),
+    };
+    let document: Entries<Entries<Value>> = match serde_json::from_str(json) {
+        Ok(document) => document,
+        Err(err) => {
+            validator.error("$", format!("invalid document: {}", err));
+            return validator.diagnostics;
+        }
+    };
+    validator.duplicates("$", &document.0, "weapon family");
+    let reserves = document.0.iter().find(|(key, _)| key == RESERVES);
+    if let Some((_, reserves)) = reserves {
+        validator.reserves(&path_join("$", RESERVES), &reserves.0);
+    }
+    let reserve_ids: Option<BTreeSet<u32>> = reserves.map(|(_, reserves)| {
+        reserves
+            .0
+            .iter()
+            .filter_map(|(key, _)| key.parse().ok())
+            .collect()
+    });
+    if let Some((_, scalars)) = document
+        .0
+        .iter()
+        .find(|(key, _)| key == WEAPON_TYPE_SCALARS)
+    {
+        validator.weapon_type_scalars(&path_join("$", WEAPON_TYPE_SCALARS), &scalars.0);
+    }
+    if let Some((_, frames)) = document.0.iter().find(|(key, _)| key == SWORD_FRAMES) {
+        validator.sword_frames(&path_join("$", SWORD_FRAMES), &frames.0);
+    }
+    if let Some((_, frames)) = document.0.iter().find(|(key, _)| key == GLAIVE_FRAMES) {
+        validator.glaive_frames(&path_join("$", GLAIVE_FRAMES), &frames.0);
+    }
+    if let Some((_, blasts)) = document.0.iter().find(|(key, _)| key == BLAST_RADII) {
+        validator.blast_radii(&path_join("$", BLAST_RADII), &blasts.0);
+    }
+    if let Some((_, cleaves)) = document.0.iter().find(|(key, _)| key == CLEAVE_RADII) {
+        validator.cleave_radii(&path_join("$", CLEAVE_RADII), &cleaves.0);
+    }
+    if let Some((_, bricks)) = document.0.iter().find(|(key, _)| key == BRICK_SIZES) {
+        validator.brick_sizes(&path_join("$", BRICK_SIZES), &bricks.0);
+    }
+    if let Some((_, frames)) = document.0.iter().find(|(key, _)| key == BOW_FRAMES) {
+        validator.bow_frames(&path_join("$", BOW_FRAMES), &frames.0);
+    }
+    if let Some((_, strings)) = document.0.iter().find(|(key, _)| key == BOW_STRINGS) {
+        validator.bow_strings(&path_join("$", BOW_STRINGS), &strings.0);
+    }
+    for (family_name, family) in document.0.iter() {
+        if [
+            RESERVES,
+            WEAPON_TYPE_SCALARS,
+            SWORD_FRAMES,
+            GLAIVE_FRAMES,
+            BLAST_RADII,
+            CLEAVE_RADII,
+            BRICK_SIZES,
+            BOW_FRAMES,
+            BOW_STRINGS,
+        ]
+        .contains(&family_name.as_str())
+        {
+            continue;
+        }
+        let path = path_join("$", family_name);
+        if !WEAPON_FAMILIES.iter().any(|(name, _)| name == family_name) {
+            validator.error(&path, "unknown weapon family".to_owned());
+            continue;
+        }
+        validator.family(&path, family_name, &family.0, reserve_ids.as_ref());
+    }
+    validator.diagnostics.sort_by_key(|x| x.severity);
+    validator.diagnostics
+}
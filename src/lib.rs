@@ -8,8 +8,10 @@ pub mod abilities;
 pub mod activity;
 pub mod d2_enums;
 pub mod enemies;
+pub mod formulas;
 pub mod logging;
 pub mod perks;
+pub mod reference;
 #[cfg(test)]
 mod test;
 pub mod types;
@@ -18,9 +20,10 @@ pub mod weapons;
 use crate::perks::{Perk, Perks};
 use crate::weapons::{Stat, Weapon};
 use abilities::Ability;
+use activity::combatant_scaling::get_combatant_scaling;
 use activity::Activity;
 use d2_enums::StatHashes;
-use enemies::Enemy;
+use enemies::{champions::ChampionMod, Enemy, TargetGroup};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::panic;
@@ -97,8 +100,8 @@ pub fn get_metadata() -> Result<JsMetaData, JsValue> {
     let metadata = JsMetaData {
         api_timestamp: built_info::BUILT_TIME_UTC,
         api_version: built_info::PKG_VERSION,
-        api_commit: built_info::GIT_COMMIT_HASH.unwrap(),
-        api_branch: built_info::GIT_HEAD_REF.unwrap(),
+        api_commit: built_info::GIT_COMMIT_HASH.unwrap_or("unknown"),
+        api_branch: built_info::GIT_HEAD_REF.unwrap_or("unknown"),
     };
     Ok(metadata)
 }
@@ -151,6 +154,52 @@ pub fn set_weapon(
     Ok(())
 }
 
+#[wasm_bindgen(js_name = "setWeaponFromItem")]
+///Sets the weapon from its item hash alone, type, intrinsic, ammo, damage type and base stats
+///all come from the manifest snapshot baked in at build time
+pub fn set_weapon_from_item(_hash: u32) -> Result<(), JsValue> {
+    PERS_DATA.with(|perm_data| {
+        let new_weapon = Weapon::from_item_hash(_hash);
+
+        if let Some(weapon) = new_weapon {
+            perm_data.borrow_mut().weapon = weapon;
+        } else if database::ITEM_DATA.is_empty() {
+            logging::log(
+                "This build has no weapon item table, its manifest snapshot needs refreshing",
+                LogLevel::Error.into(),
+            );
+            perm_data.borrow_mut().weapon = Weapon::default();
+        } else {
+            logging::log(
+                format!("Could not find weapon data for item: {}", _hash).as_str(),
+                LogLevel::Error.into(),
+            );
+            perm_data.borrow_mut().weapon = Weapon::default();
+        }
+    });
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = "loadFormulaOverrides")]
+///Loads a weapon_formulas.json style document on top of the built in formulas,
+///changed formulas are stamped with _timestamp
+pub fn load_formula_overrides(_json: String, _timestamp: u32) -> Result<JsValue, JsValue> {
+    let report = formulas::load_formula_overrides(&_json, _timestamp as u64)
+        .map_err(|errors| JsValue::from_str(&errors.join("\n")))?;
+    let value = serde_wasm_bindgen::to_value(&report);
+    if value.is_err() {
+        return Err(JsValue::from_str(
+            "Could not convert formula report to JsValue",
+        ));
+    }
+    Ok(value.unwrap())
+}
+
+#[wasm_bindgen(js_name = "resetFormulaOverrides")]
+pub fn reset_formula_overrides() {
+    formulas::reset_formula_overrides();
+}
+
 #[wasm_bindgen(js_name = "getStats")]
 pub fn get_stats() -> Result<JsValue, JsValue> {
     let stat_map = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.stats.clone());
@@ -176,6 +225,29 @@ pub fn set_stats(_stats: JsValue) -> Result<(), JsValue> {
     Ok(())
 }
 
+#[wasm_bindgen(js_name = "setInvestmentStats")]
+///Takes investment stats instead of displayed ones, the engine handles the stat group interpolation.
+///Trait stat buffs are treated as investment bumps for these stats
+pub fn set_investment_stats(_stat_group: u32, _stats: JsValue) -> Result<(), JsValue> {
+    let stat_group = weapons::weapon_constructor::get_stat_group(_stat_group).ok_or_else(|| {
+        if database::STAT_GROUP_DATA.is_empty() {
+            JsValue::from_str(
+                "This build has no stat groups, its manifest snapshot needs refreshing",
+            )
+        } else {
+            JsValue::from_str("Unknown stat group")
+        }
+    })?;
+    let in_stats: HashMap<u32, i32> = serde_wasm_bindgen::from_value(_stats).unwrap();
+    PERS_DATA.with(|perm_data| {
+        perm_data
+            .borrow_mut()
+            .weapon
+            .set_investment_stats(stat_group, in_stats)
+    });
+    Ok(())
+}
+
 #[wasm_bindgen(js_name = "addTrait")]
 pub fn add_perk(_stats: JsValue, _value: u32, _hash: u32) -> Result<(), JsValue> {
     let data = perks::enhanced_check(_hash);
@@ -275,14 +347,201 @@ pub fn get_weapon_ammo(_dynamic_traits: bool, _pvp: bool) -> Result<JsAmmoRespon
     }
 }
 
-#[wasm_bindgen(js_name = "getWeaponTtk")]
-pub fn get_weapon_ttk(_overshield: f64) -> Result<JsValue, JsValue> {
+#[wasm_bindgen(js_name = "getStatCurves")]
+///Reload, handling, range and ammo at every stat value from 0 to 100, with and without
+///dynamic traits
+pub fn get_stat_curves(_pvp: bool) -> Result<JsValue, JsValue> {
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    Ok(serde_wasm_bindgen::to_value(&weapon.calc_stat_curve_set(_pvp)).unwrap())
+}
+
+#[wasm_bindgen(js_name = "getStatCurvesCsv")]
+///Same as getStatCurves, one row per stat value
+pub fn get_stat_curves_csv(_pvp: bool) -> Result<String, JsValue> {
     let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let ttk_data = weapon.calc_ttk(_overshield);
+    Ok(weapon.calc_stat_curve_set(_pvp).to_csv())
+}
+
+#[wasm_bindgen(js_name = "compareWeapons")]
+///Diffs every calculated value for two or more weapons against the first, using the current
+///encounter for pve damage and dps. The current weapon is untouched
+pub fn compare_weapons(
+    _weapons: JsValue,
+    _dynamic_traits: bool,
+    _pvp: bool,
+) -> Result<JsValue, JsValue> {
+    let configs: Vec<weapons::weapon_constructor::WeaponConfig> =
+        serde_wasm_bindgen::from_value(_weapons)?;
+    let weapons = configs
+        .iter()
+        .map(|config| config.build())
+        .collect::<Result<Vec<Weapon>, String>>()
+        .map_err(|err| JsValue::from_str(&err))?;
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let comparison = weapons::comparison::compare_weapons(
+        &weapons,
+        &persistent.activity,
+        &persistent.enemy,
+        _dynamic_traits,
+        _pvp,
+    )
+    .map_err(|err| JsValue::from_str(&err))?;
+    Ok(serde_wasm_bindgen::to_value(&comparison).unwrap())
+}
+
+#[wasm_bindgen(js_name = "rankRolls")]
+///Scores every combination of one perk per column on top of the current weapon and its
+///traits, best first
+pub fn rank_rolls(_columns: JsValue, _options: JsValue) -> Result<JsValue, JsValue> {
+    let ranking = rank_current_weapon(_columns, _options)?;
+    Ok(serde_wasm_bindgen::to_value(&ranking).unwrap())
+}
+
+#[wasm_bindgen(js_name = "exportWishlist")]
+///Same as rankRolls, written out as DIM wishlist lines for the current weapon's hash
+pub fn export_wishlist(_columns: JsValue, _options: JsValue) -> Result<String, JsValue> {
+    let ranking = rank_current_weapon(_columns, _options)?;
+    let hash = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.hash);
+    Ok(ranking.to_wishlist(hash))
+}
+
+fn rank_current_weapon(
+    _columns: JsValue,
+    _options: JsValue,
+) -> Result<weapons::roll_ranking::RollRanking, JsValue> {
+    let columns: Vec<Vec<weapons::weapon_constructor::PerkConfig>> =
+        serde_wasm_bindgen::from_value(_columns)?;
+    let options: weapons::roll_ranking::RollRankingOptions =
+        serde_wasm_bindgen::from_value(_options)?;
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    weapons::roll_ranking::rank_rolls(
+        &persistent.weapon,
+        &columns,
+        &options,
+        &persistent.activity,
+        &persistent.enemy,
+    )
+    .map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = "getWeaponTtk")]
+///`_distance` is in meters and only matters for projectiles, defaults to point blank.
+///`_damage_resistance` is the target's, like the `shield_damage_resistance` misc stat of a
+///glaive they hold up. `_resilience` narrows the result to the target's tier, every tier
+///from 0 to 10 is returned without it
+pub fn get_weapon_ttk(
+    _overshield: f64,
+    _distance: Option<f64>,
+    _damage_resistance: Option<f64>,
+    _resilience: Option<u8>,
+) -> Result<JsValue, JsValue> {
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let tiers = weapons::ttk_calc::resilience_tiers(_resilience.map(i32::from));
+    let ttk_data = persistent.weapon.calc_ttk_resisted(
+        _overshield,
+        _damage_resistance.unwrap_or(0.0),
+        _distance.unwrap_or(0.0),
+        &tiers,
+    );
     let js_ttk_data: Vec<JsResillienceSummary> = ttk_data.into_iter().map(|r| r.into()).collect();
     Ok(serde_wasm_bindgen::to_value(&js_ttk_data).unwrap())
 }
 
+#[wasm_bindgen(js_name = "getGroupDamage")]
+///Shoots a line of `_count` copies of the current enemy standing `_spacing` meters apart,
+///each with `_health` health
+pub fn get_group_damage(_count: usize, _spacing: f64, _health: f64) -> Result<JsValue, JsValue> {
+    if _health <= 0.0 {
+        return Err(JsValue::from_str("health has to be positive"));
+    }
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let group = TargetGroup {
+        enemy: Enemy {
+            health: _health,
+            ..persistent.enemy.clone()
+        },
+        count: _count,
+        spacing: _spacing,
+    };
+    let response = persistent
+        .weapon
+        .calc_group_damage(&group, persistent.activity.get_pl_delta());
+    Ok(serde_wasm_bindgen::to_value(&response).unwrap())
+}
+
+#[wasm_bindgen(js_name = "setChampionMods")]
+///Sets the artifact mods that let weapon types stun champions
+pub fn set_champion_mods(_mods: JsValue) -> Result<(), JsValue> {
+    let mods: Vec<ChampionMod> = serde_wasm_bindgen::from_value(_mods)?;
+    PERS_DATA.with(|perm_data| {
+        perm_data.borrow_mut().activity.player.champion_mods = mods;
+    });
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = "getChampionTtk")]
+///Time to kill a champion with `_health` as each champion type that has a profile,
+///with and without stuns
+pub fn get_champion_ttk(_health: f64) -> Result<JsValue, JsValue> {
+    if _health <= 0.0 {
+        return Err(JsValue::from_str("health has to be positive"));
+    }
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let response = persistent.weapon.calc_champion_ttk(
+        &persistent.enemy,
+        _health,
+        &persistent.activity.player.champion_mods,
+        persistent.activity.get_pl_delta(),
+    );
+    Ok(serde_wasm_bindgen::to_value(&response).unwrap())
+}
+
+#[wasm_bindgen(js_name = "simulateEncounter")]
+///Fights the current enemy through an encounter's damage phases until the encounter's health is gone
+pub fn simulate_encounter(_encounter: JsValue) -> Result<JsValue, JsValue> {
+    let encounter: activity::encounter::Encounter = serde_wasm_bindgen::from_value(_encounter)?;
+    if encounter.health <= 0.0 {
+        return Err(JsValue::from_str("health has to be positive"));
+    }
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let response = persistent.weapon.simulate_encounter(
+        &persistent.enemy,
+        &encounter,
+        persistent.activity.get_pl_delta(),
+    );
+    Ok(serde_wasm_bindgen::to_value(&response).unwrap())
+}
+
+#[wasm_bindgen(js_name = "getDpsMetrics")]
+///Burst and sustained DPS and ammo economy against the current enemy
+pub fn get_dps_metrics() -> Result<JsValue, JsValue> {
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let metrics = persistent
+        .weapon
+        .calc_dps_metrics(persistent.enemy, persistent.activity.get_pl_delta());
+    Ok(serde_wasm_bindgen::to_value(&metrics).unwrap())
+}
+
+#[wasm_bindgen(js_name = "setArmorStats")]
+///Sets the player's class and armor stat totals
+pub fn set_armor_stats(_class: JsValue, _stats: JsValue) -> Result<(), JsValue> {
+    let class: activity::PlayerClass = serde_wasm_bindgen::from_value(_class)?;
+    let stats: activity::armor_stats::ArmorStats = serde_wasm_bindgen::from_value(_stats)?;
+    PERS_DATA.with(|perm_data| {
+        let player = &mut perm_data.borrow_mut().activity.player;
+        player.class = class;
+        player.armor = stats;
+    });
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = "getArmorStats")]
+///Cooldowns, regen, walk speed and resilience from the player's armor stat tiers
+pub fn get_armor_stats() -> Result<JsValue, JsValue> {
+    let player = PERS_DATA.with(|perm_data| perm_data.borrow().activity.player.clone());
+    Ok(serde_wasm_bindgen::to_value(&player.calc_armor_stats()).unwrap())
+}
+
 ///DEPRECATED for now
 //
 // #[wasm_bindgen(js_name = "getWeaponDps")]
@@ -318,31 +577,31 @@ pub fn get_weapon_firing_data(
         persistent.activity.get_rpl_mult(),
         persistent.activity.get_pl_delta(),
         persistent.weapon.damage_mods.pve,
-        persistent
-            .weapon
-            .damage_mods
-            .get_mod(&persistent.enemy.type_),
+        get_combatant_scaling(&persistent.weapon, persistent.enemy.type_).total(),
     );
     crate::logging::log(format!("{:?}", response).as_str(), LogLevel::Debug.into());
     Ok(response.into())
 }
 
 #[wasm_bindgen(js_name = "getWeaponFlinch")]
+///`_resilience` is a tier, defaults to the one from `setArmorStats`
 pub fn get_weapon_flinch(
     _dynamic_traits: bool,
     _pvp: bool,
-    _resilience: u8,
+    _resilience: Option<u8>,
 ) -> Result<f64, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let persistent = PERS_DATA.with(|perm_data| perm_data.borrow().clone());
+    let weapon = persistent.weapon;
+    let resilience = _resilience.map_or(persistent.activity.player.resilience_tier(), i32::from);
     if _dynamic_traits {
         Ok(weapon.calc_flinch_resist(
             Some(weapon.static_calc_input()),
-            _resilience as i32,
+            resilience,
             _pvp,
             None,
         ))
     } else {
-        Ok(weapon.calc_flinch_resist(None, _resilience as i32, _pvp, None))
+        Ok(weapon.calc_flinch_resist(None, resilience, _pvp, None))
     }
 }
 
@@ -367,6 +626,7 @@ pub fn set_encounter(
     _override_cap: i32,
     _difficulty: JsDifficultyOptions,
     _enemy_type: JsEnemyType,
+    _target_distance: Option<f64>,
 ) -> Result<(), JsValue> {
     PERS_DATA.with(|perm_data| {
         let activity = &mut perm_data.borrow_mut().activity;
@@ -378,10 +638,108 @@ pub fn set_encounter(
     PERS_DATA.with(|perm_data| {
         let enemy = &mut perm_data.borrow_mut().enemy;
         enemy.type_ = _enemy_type.into();
+        enemy.distance = _target_distance.unwrap_or(0.0);
+    });
+    Ok(())
+}
+
+#[wasm_bindgen(js_name = "setEnemyDamageResistance")]
+///Replaces the current enemy's damage resistance with `_damage_resistances` stacked together,
+///like the `shield_damage_resistance` misc stat of a glaive they hold up and a buff
+pub fn set_enemy_damage_resistance(_damage_resistances: Vec<f64>) {
+    PERS_DATA.with(|perm_data| {
+        let enemy = &mut perm_data.borrow_mut().enemy;
+        enemy.damage_resistance = 0.0;
+        for resistance in _damage_resistances {
+            enemy.add_damage_resistance(resistance);
+        }
+    });
+}
+
+#[wasm_bindgen(js_name = "runReferenceCorpus")]
+///Runs a corpus of reference values through the calculators, the current weapon is untouched
+pub fn run_reference_corpus(_corpus: String) -> Result<JsValue, JsValue> {
+    let report = reference::run_corpus(&_corpus).map_err(|err| JsValue::from_str(&err))?;
+    Ok(serde_wasm_bindgen::to_value(&report).unwrap())
+}
+
+#[wasm_bindgen(js_name = "solveDamage")]
+///Fits observed damage numbers against the current weapon, solving for whichever of
+///_base_damage and _perk_multiplier is left undefined
+pub fn solve_damage(
+    _observations: JsValue,
+    _base_damage: Option<f64>,
+    _perk_multiplier: Option<f64>,
+) -> Result<JsValue, JsValue> {
+    let observations: Vec<activity::damage_solver::DamageObservation> =
+        serde_wasm_bindgen::from_value(_observations)?;
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let solution = activity::damage_solver::solve_damage(
+        &weapon,
+        &observations,
+        _base_damage,
+        _perk_multiplier,
+    )
+    .map_err(|err| JsValue::from_str(&err))?;
+    Ok(serde_wasm_bindgen::to_value(&solution).unwrap())
+}
+
+#[wasm_bindgen(js_name = "setActivityPreset")]
+///Sets the encounter from a named activity, see getActivityPresets for the ids
+pub fn set_activity_preset(_id: String) -> Result<(), JsValue> {
+    let Some(preset) = activity::presets::get_activity_preset(&_id) else {
+        return Err(JsValue::from_str(&format!("unknown activity preset: {}", _id)));
+    };
+    PERS_DATA.with(|perm_data| {
+        let mut persistent = perm_data.borrow_mut();
+        persistent.activity.apply_preset(preset);
+        persistent.enemy.apply_preset(preset);
     });
     Ok(())
 }
 
+#[wasm_bindgen(js_name = "getActivityPresets")]
+pub fn get_activity_presets() -> Result<JsValue, JsValue> {
+    Ok(serde_wasm_bindgen::to_value(activity::presets::list_activity_presets()).unwrap())
+}
+
+#[wasm_bindgen(js_name = "loadDifficultyTables")]
+///Loads difficulty_tables.json style power delta tables on top of the built in ones,
+///returns the difficulty ids that were loaded
+pub fn load_difficulty_tables(_json: String) -> Result<Vec<u32>, JsValue> {
+    activity::damage_calc::load_difficulty_tables(&_json).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = "resetDifficultyTables")]
+pub fn reset_difficulty_tables() {
+    activity::damage_calc::reset_difficulty_tables();
+}
+
+#[wasm_bindgen(js_name = "loadChampionProfiles")]
+///Loads champions.json style champion profiles on top of the built in ones,
+///returns the champion types that were loaded
+pub fn load_champion_profiles(_json: String) -> Result<JsValue, JsValue> {
+    let response = enemies::champions::load_champion_profiles(&_json)
+        .map_err(|err| JsValue::from_str(&err))?;
+    Ok(serde_wasm_bindgen::to_value(&response).unwrap())
+}
+
+#[wasm_bindgen(js_name = "resetChampionProfiles")]
+pub fn reset_champion_profiles() {
+    enemies::champions::reset_champion_profiles();
+}
+
+#[wasm_bindgen(js_name = "loadArmorStatData")]
+///Replaces the built in armor stat data with an armor_stats.json style document
+pub fn load_armor_stat_data(_json: String) -> Result<(), JsValue> {
+    activity::armor_stats::load_armor_stat_data(&_json).map_err(|err| JsValue::from_str(&err))
+}
+
+#[wasm_bindgen(js_name = "resetArmorStatData")]
+pub fn reset_armor_stat_data() {
+    activity::armor_stats::reset_armor_stat_data();
+}
+
 #[wasm_bindgen(js_name = "setLoggingLevel")]
 pub fn set_logging_level(_level: usize) -> Result<(), JsValue> {
     PERS_DATA.with(|perm_data| {
@@ -392,18 +750,33 @@ pub fn set_logging_level(_level: usize) -> Result<(), JsValue> {
 
 #[wasm_bindgen(js_name = "getModifierResponseSummary")]
 pub fn get_modifier_response(_dynamic_traits: bool, _pvp: bool) -> Result<JsValue, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
-    let modifier = weapon.get_modifier_summary(
-        _dynamic_traits.then_some(weapon.static_calc_input()),
-        _pvp,
-        None,
-    );
+    let (weapon, enemy_type) = PERS_DATA.with(|perm_data| {
+        let persistent = perm_data.borrow();
+        (persistent.weapon.clone(), persistent.enemy.type_)
+    });
+    //against the current encounter's combatant so the built in modifier carries its scaling
+    let mut calc_input = weapon.static_calc_input();
+    calc_input.enemy_type = &enemy_type;
+    let modifier = weapon.get_modifier_summary(_dynamic_traits.then_some(calc_input), _pvp, None);
     Ok(serde_wasm_bindgen::to_value(&modifier).unwrap())
 }
 
+#[wasm_bindgen(js_name = "getCombatantScaling")]
+///Breakdown of the pve damage multiplier against the current encounter's combatant type
+pub fn get_combatant_scaling_js() -> Result<JsValue, JsValue> {
+    let scaling = PERS_DATA.with(|perm_data| {
+        let persistent = perm_data.borrow();
+        get_combatant_scaling(&persistent.weapon, persistent.enemy.type_)
+    });
+    Ok(serde_wasm_bindgen::to_value(&scaling).unwrap())
+}
+
 #[wasm_bindgen(js_name = "getScalarResponseSummary")]
 pub fn get_scalar_response(_pvp: bool) -> Result<JsScalarResponse, JsValue> {
-    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let (weapon, enemy_type) = PERS_DATA.with(|perm_data| {
+        let persistent = perm_data.borrow();
+        (persistent.weapon.clone(), persistent.enemy.type_)
+    });
     let input_data = weapon.static_calc_input();
     let mut cached_data = HashMap::new();
     let rmr = perks::get_range_modifier(weapon.list_perks(), &input_data, _pvp, &mut cached_data);
@@ -423,5 +796,10 @@ pub fn get_scalar_response(_pvp: bool) -> Result<JsScalarResponse, JsValue> {
         reload_scalar: rsmr.reload_time_scale,
         mag_size_scalar: mmr.magazine_scale,
         reserve_size_scalar: imr.inv_scale,
+        combatant_scalar: if _pvp {
+            1.0
+        } else {
+            get_combatant_scaling(&weapon, enemy_type).total()
+        },
     })
 }
@@ -9,9 +9,10 @@ use super::{
     add_dmr, add_edr, add_epr, add_fmr, add_hmr, add_mmr, add_rmr, add_rr, add_rsmr, add_sbr,
     add_vmr, clamp,
     lib::{
-        CalculationInput, DamageModifierResponse, ExtraDamageResponse, FiringModifierResponse,
-        HandlingModifierResponse, InventoryModifierResponse, MagazineModifierResponse,
-        RangeModifierResponse, RefundResponse, ReloadModifierResponse, ReloadOverrideResponse,
+        CalculationInput, DamageModifierResponse, ExtraDamageResponse, ExtraDamageSpread,
+        FiringModifierResponse, HandlingModifierResponse, InventoryModifierResponse,
+        MagazineModifierResponse, RangeModifierResponse, RefundResponse, ReloadModifierResponse,
+        ReloadOverrideResponse,
     },
     ModifierResponseInput, Perks,
 };
@@ -258,6 +259,7 @@ pub fn exotic_perks() {
                 weapon_scale: true,
                 crit_scale: false,
                 combatant_scale: true,
+                spread: ExtraDamageSpread::cleave(Perks::ReignHavoc),
             }
         }),
     );
@@ -334,6 +336,7 @@ pub fn exotic_perks() {
                 weapon_scale: true,
                 crit_scale: false,
                 combatant_scale: true,
+                spread: ExtraDamageSpread::Single,
             }
         }),
     );
@@ -1336,7 +1339,7 @@ pub fn exotic_perks() {
             let hits_needed = if _input.pvp { 5 } else { 14 };
 
             if _input.calc_data.shots_fired_this_mag < (hits_needed as f64)
-                && _input.calc_data.intrinsic_hash != Perks::Judgment.into()
+                && _input.calc_data.intrinsic_hash != u32::from(Perks::Judgment)
                 && _input.value == 0
             {
                 return DamageModifierResponse::default();
@@ -1,6 +1,8 @@
+use super::Perks;
 use crate::{
     d2_enums::{AmmoType, BungieHash, DamageSource, DamageType, StatBump, StatHashes, WeaponType},
     enemies::EnemyType,
+    formulas::get_cleave_radius,
     types::rs_types::{FiringData, HandlingResponse},
     weapons::{Stat, Weapon},
 };
@@ -171,6 +173,23 @@ impl DamageModifierResponse {
     }
 }
 
+///Who else extra damage hits when there's more than one target around
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ExtraDamageSpread {
+    ///only the target that was shot
+    #[default]
+    Single,
+    ///everything within `radius` meters of the target that was shot
+    Cleave { radius: f64 },
+}
+impl ExtraDamageSpread {
+    ///Cleaves as far as the perk's `cleave_radii` entry in weapon_formulas.json,
+    ///a perk without one only hits the target that was shot
+    pub fn cleave(perk: Perks) -> Self {
+        get_cleave_radius(perk.into()).map_or(Self::Single, |radius| Self::Cleave { radius })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ExtraDamageResponse {
     pub additive_damage: f64,
@@ -189,6 +208,7 @@ pub struct ExtraDamageResponse {
     pub weapon_scale: bool,
     pub crit_scale: bool,
     pub combatant_scale: bool,
+    pub spread: ExtraDamageSpread,
 }
 impl Default for ExtraDamageResponse {
     fn default() -> Self {
@@ -202,6 +222,7 @@ impl Default for ExtraDamageResponse {
             weapon_scale: false,
             crit_scale: false,
             combatant_scale: false,
+            spread: ExtraDamageSpread::Single,
         }
     }
 }
@@ -27,62 +27,7 @@ pub fn meta_perks() {
             if *_input.calc_data.weapon_type == WeaponType::LINEARFUSIONRIFLE && !_input.pvp {
                 crit_scale *= 1.15;
             };
-            if *_input.calc_data.damage_type == DamageType::KINETIC
-                && !_input.pvp
-                && *_input.calc_data.enemy_type != EnemyType::BOSS
-            {
-                if _input.calc_data.ammo_type == &AmmoType::PRIMARY {
-                    dmg_scale *= 1.1;
-                } else if _input.calc_data.ammo_type == &AmmoType::SPECIAL {
-                    dmg_scale *= 1.15;
-                };
-            };
-
-            if *_input.calc_data.ammo_type == AmmoType::PRIMARY
-                && *_input.calc_data.enemy_type == EnemyType::MINOR
-                && !_input.pvp
-                && _input.calc_data.intrinsic_hash > 1000
-            {
-                dmg_scale *= 1.3
-            }
-            if *_input.calc_data.enemy_type == EnemyType::MINOR && !_input.pvp {
-                dmg_scale *= match *_input.calc_data.weapon_type {
-                    WeaponType::SIDEARM | WeaponType::BOW => 1.2,
-                    WeaponType::TRACERIFLE => 1.44,
-                    WeaponType::SCOUTRIFLE => 1.38,
-                    WeaponType::PULSERIFLE => 1.15,
-                    WeaponType::AUTORIFLE => 1.265,
-                    WeaponType::SUBMACHINEGUN => 1.155,
-                    WeaponType::HANDCANNON => 1.05,
-                    WeaponType::SNIPER => 1.6,
-                    WeaponType::FUSIONRIFLE => 1.3,
-                    _ => 1.0,
-                };
-            }
-            if *_input.calc_data.enemy_type == EnemyType::ELITE && !_input.pvp {
-                dmg_scale *= match *_input.calc_data.weapon_type {
-                    WeaponType::TRACERIFLE => 1.2,
-                    WeaponType::SNIPER => 1.75,
-                    WeaponType::SCOUTRIFLE => 1.3,
-                    WeaponType::AUTORIFLE => 1.1,
-                    WeaponType::FUSIONRIFLE => 1.3,
-                    _ => 1.0,
-                };
-            }
-            if *_input.calc_data.enemy_type == EnemyType::MINIBOSS && !_input.pvp {
-                dmg_scale *= match *_input.calc_data.weapon_type {
-                    WeaponType::SNIPER => 1.35,
-                    WeaponType::FUSIONRIFLE => 1.3,
-                    _ => 1.0,
-                };
-            }
-            if *_input.calc_data.enemy_type == EnemyType::CHAMPION && !_input.pvp {
-                dmg_scale *= match *_input.calc_data.weapon_type {
-                    WeaponType::SNIPER => 1.25,
-                    WeaponType::FUSIONRIFLE => 1.3,
-                    _ => 1.0,
-                };
-            }
+            //combatant and kinetic scaling is applied once in activity::combatant_scaling
 
             if *_input.calc_data.weapon_type == WeaponType::LINEARFUSIONRIFLE
                 && _input.calc_data.intrinsic_hash < 1000
@@ -108,58 +53,6 @@ pub fn meta_perks() {
         }),
     );
 
-    add_fmr(
-        Perks::BuiltIn,
-        Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
-            #[allow(unused_mut)]
-            let mut delay_add = 0.0;
-
-            if matches!(
-                _input.calc_data.weapon_type,
-                WeaponType::FUSIONRIFLE | WeaponType::LINEARFUSIONRIFLE
-            ) && _input.calc_data.intrinsic_hash < 1000
-            {
-                let charge_time = _input
-                    .calc_data
-                    .stats
-                    .get(&StatHashes::CHARGE_TIME.into())
-                    .unwrap();
-                let stat = (charge_time.perk_val() - charge_time.base_value) as f64;
-                delay_add -= match _input.calc_data.weapon_type {
-                    WeaponType::FUSIONRIFLE => stat * 0.0040,
-                    WeaponType::LINEARFUSIONRIFLE => stat * 0.0033,
-                    _ => 0.0,
-                }
-            }
-
-            if _input.calc_data.weapon_type == &WeaponType::BOW {
-                let draw_time = _input
-                    .calc_data
-                    .stats
-                    .get(&StatHashes::DRAW_TIME.into())
-                    .unwrap()
-                    .clone();
-                delay_add += match _input.calc_data.intrinsic_hash {
-                    //Lightweights, Wishender, Ticcus, Verglas
-                    905 | 1470121888 | 3239299468 | 2636679416 => {
-                        (draw_time.perk_val() as f64 * -4.0 + 900.0) / 1100.0
-                    }
-                    //Precisions, Lemon, Trinity, Hierarchy
-                    906 | 2186532310 | 1573888036 | 2226793914 => {
-                        (draw_time.perk_val() as f64 * -3.6 + 900.0) / 1100.0
-                    }
-                    //Levi Breath lol
-                    1699724249 => (draw_time.perk_val() as f64 * -5.0 + 1428.0) / 1100.0,
-                    _ => 0.0,
-                };
-            }
-            FiringModifierResponse {
-                burst_delay_add: delay_add,
-                ..Default::default()
-            }
-        }),
-    );
-
     add_epr(
         Perks::BuiltIn,
         Box::new(
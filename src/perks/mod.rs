@@ -20,6 +20,7 @@ use std::collections::HashMap;
 use num_enum::{FromPrimitive, IntoPrimitive};
 use serde::{Deserialize, Serialize};
 
+use crate::activity::combatant_scaling::get_combatant_scaling;
 use crate::d2_enums::{BungieHash, StatBump, StatHashes, WeaponType};
 use crate::database;
 
@@ -74,7 +75,7 @@ pub struct Perk {
 pub fn enhanced_check(_hash: u32) -> (u32, bool) {
     let mut result = _hash;
     let mut found = false;
-    for (_, (h, r)) in database::ENHANCE_PERK_MAPPING.iter().enumerate() {
+    for (h, r) in database::ENHANCE_PERK_MAPPING.iter() {
         if _hash == *h {
             result = *r;
             found = true;
@@ -1259,6 +1260,15 @@ impl Weapon {
             buffer.insert(perk.raw_hash, mod_buffer);
         }
 
+        //combatant scaling isn't a perk, it shows up as part of the built in modifier
+        let scaling = get_combatant_scaling(self, *calc_input.enemy_type).total();
+        if !_pvp && scaling != 1.0 {
+            let built_in = buffer.entry(Perks::BuiltIn.into()).or_default();
+            let dmr = built_in.dmr.get_or_insert_with(Default::default);
+            dmr.impact_dmg_scale *= scaling;
+            dmr.explosive_dmg_scale *= scaling;
+        }
+
         buffer
     }
 }
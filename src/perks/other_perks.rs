@@ -3,6 +3,7 @@ use std::collections::{btree_map::Range, HashMap};
 use crate::{
     d2_enums::{AmmoType, BungieHash, DamageType, StatBump, StatHashes, WeaponType},
     enemies::EnemyType,
+    formulas::get_bow_string,
 };
 
 use super::{
@@ -502,6 +503,26 @@ pub fn other_perks() {
         }),
     );
 
+    //bow strings, the draw time change isn't in their investment stats but in the
+    //bow_strings section of weapon_formulas.json
+    for string in [
+        Perks::FasterStringT1,
+        Perks::FasterStringT2,
+        Perks::SlowerStringT1,
+    ] {
+        add_fmr(
+            string,
+            Box::new(
+                move |_input: ModifierResponseInput| -> FiringModifierResponse {
+                    FiringModifierResponse {
+                        burst_delay_add: get_bow_string(string.into()).unwrap_or_default(),
+                        ..Default::default()
+                    }
+                },
+            ),
+        );
+    }
+
     add_fmr(
         Perks::AssaultMag,
         Box::new(|_input: ModifierResponseInput| -> FiringModifierResponse {
@@ -15,10 +15,10 @@ pub enum PerkValueVariant {
 
 #[derive(Debug, Clone, Serialize)]
 pub struct PerkOptionData {
-    stacks: (u32, u32),
-    options: Vec<String>,
+    pub stacks: (u32, u32),
+    pub options: Vec<String>,
     #[serde(rename = "optionType")]
-    option_type: PerkValueVariant,
+    pub option_type: PerkValueVariant,
 }
 impl PerkOptionData {
     pub fn static_() -> PerkOptionData {
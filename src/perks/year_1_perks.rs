@@ -11,9 +11,9 @@ use super::{
     add_sbr, add_vmr, clamp, lerp,
     lib::{
         CalculationInput, DamageModifierResponse, ExplosivePercentResponse, ExtraDamageResponse,
-        FiringModifierResponse, HandlingModifierResponse, InventoryModifierResponse,
-        MagazineModifierResponse, RangeModifierResponse, RefundResponse, ReloadModifierResponse,
-        VelocityModifierResponse,
+        ExtraDamageSpread, FiringModifierResponse, HandlingModifierResponse,
+        InventoryModifierResponse, MagazineModifierResponse, RangeModifierResponse, RefundResponse,
+        ReloadModifierResponse, VelocityModifierResponse,
     },
     ModifierResponseInput, Perks,
 };
@@ -702,6 +702,8 @@ pub fn year_1_perks() {
                 weapon_scale: true,
                 hit_at_same_time: true,
                 is_dot: false,
+                //the submunitions scatter around the impact
+                spread: ExtraDamageSpread::cleave(Perks::ClusterBomb),
             }
         }),
     );
@@ -0,0 +1,253 @@
+//! Checks the calculators against reference values.
+//!
+//! A corpus is a list of cases, each a weapon (see [`WeaponConfig`]), optionally where it was
+//! used and the value expected for it. Every case is rebuilt from scratch and run through the
+//! same calculators the JS API uses, so a formula change that drifts away from the references
+//! shows up as a case outside its tolerance. The checked in corpus lives in
+//! build_resources/reference_corpus.json and only holds pvp TTKs from published frame charts,
+//! not in-game measurements.
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    activity::{
+        combatant_scaling::get_combatant_scaling, damage_calc::DifficultyOptions, Activity, NO_CAP,
+    },
+    enemies::EnemyType,
+    weapons::{weapon_constructor::WeaponConfig, Weapon},
+};
+
+///seconds
+pub const DEFAULT_RELOAD_TOLERANCE: f64 = 0.05;
+///seconds, a little over two frames at 60fps
+pub const DEFAULT_TTK_TOLERANCE: f64 = 0.04;
+///fraction of the measured damage
+pub const DEFAULT_DAMAGE_TOLERANCE: f64 = 0.01;
+
+const fn default_true() -> bool {
+    true
+}
+
+const fn default_buff() -> f64 {
+    1.0
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Measurement {
+    ///reload time in seconds
+    Reload {
+        time: f64,
+        #[serde(default)]
+        pvp: bool,
+    },
+    ///pvp time to kill in seconds against one resilience tier
+    Ttk {
+        time: f64,
+        resilience: i32,
+        #[serde(default)]
+        overshield: f64,
+        ///optimal (precision) kill, otherwise body shots only
+        #[serde(default = "default_true")]
+        optimal: bool,
+    },
+    ///damage number shown for a single hit in pve
+    #[serde(rename_all = "camelCase")]
+    Damage {
+        damage: f64,
+        enemy_type: EnemyType,
+        rpl: u32,
+        power: u32,
+        #[serde(default)]
+        difficulty: DifficultyOptions,
+        #[serde(default)]
+        cap: Option<i32>,
+        ///product of every buff and debuff active for the hit not covered by the weapon's perks
+        #[serde(default = "default_buff")]
+        buff: f64,
+        #[serde(default)]
+        crit: bool,
+    },
+}
+impl Measurement {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Measurement::Reload { .. } => "reload",
+            Measurement::Ttk { .. } => "ttk",
+            Measurement::Damage { .. } => "damage",
+        }
+    }
+
+    pub fn measured(&self) -> f64 {
+        match self {
+            Measurement::Reload { time, .. } | Measurement::Ttk { time, .. } => *time,
+            Measurement::Damage { damage, .. } => *damage,
+        }
+    }
+
+    ///absolute tolerance used when a case doesn't set its own
+    pub fn default_tolerance(&self) -> f64 {
+        match self {
+            Measurement::Reload { .. } => DEFAULT_RELOAD_TOLERANCE,
+            Measurement::Ttk { .. } => DEFAULT_TTK_TOLERANCE,
+            Measurement::Damage { damage, .. } => damage * DEFAULT_DAMAGE_TOLERANCE,
+        }
+    }
+
+    pub fn predict(&self, weapon: &Weapon) -> Result<f64, String> {
+        match self {
+            Measurement::Reload { pvp, .. } => Ok(weapon
+                .calc_reload_time(Some(weapon.static_calc_input()), None, *pvp)
+                .reload_time),
+            Measurement::Ttk {
+                resilience,
+                overshield,
+                optimal,
+                ..
+            } => {
+                let summary = weapon
+                    .calc_ttk(*overshield)
+                    .into_iter()
+                    .find(|x| x.value == *resilience)
+                    .ok_or_else(|| format!("no ttk for resilience {}", resilience))?;
+                Ok(if *optimal {
+                    summary.optimal_ttk.time_taken
+                } else {
+                    summary.body_ttk.time_taken
+                })
+            }
+            Measurement::Damage {
+                enemy_type,
+                rpl,
+                power,
+                difficulty,
+                cap,
+                buff,
+                crit,
+                ..
+            } => {
+                let mut activity = Activity {
+                    rpl: *rpl,
+                    cap: cap.unwrap_or(NO_CAP),
+                    difficulty: *difficulty,
+                    ..Default::default()
+                };
+                activity.player.power = *power;
+                let mut calc_input = weapon.static_calc_input();
+                calc_input.enemy_type = enemy_type;
+                let mut response = weapon.calc_firing_data(Some(calc_input), None, false);
+                response.apply_pve_bonuses(
+                    activity.get_rpl_mult(),
+                    activity.get_pl_delta(),
+                    weapon.damage_mods.pve,
+                    get_combatant_scaling(weapon, *enemy_type).total(),
+                );
+                let crit_mult = if *crit { response.pve_crit_mult } else { 1.0 };
+                Ok((response.pve_impact_damage * crit_mult + response.pve_explosion_damage) * buff)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceCase {
+    pub name: String,
+    ///where the measurement came from
+    #[serde(default)]
+    pub source: String,
+    pub weapon: WeaponConfig,
+    pub measurement: Measurement,
+    ///absolute, defaults to [`Measurement::default_tolerance`]
+    #[serde(default)]
+    pub tolerance: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaseResult {
+    pub name: String,
+    pub kind: &'static str,
+    pub measured: f64,
+    ///None when the case couldn't be run, `message` says why
+    pub predicted: Option<f64>,
+    ///predicted minus measured
+    pub error: Option<f64>,
+    pub tolerance: f64,
+    pub passed: bool,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorpusReport {
+    pub cases: Vec<CaseResult>,
+    pub failures: usize,
+}
+impl CorpusReport {
+    pub fn failed(&self) -> impl Iterator<Item = &CaseResult> {
+        self.cases.iter().filter(|x| !x.passed)
+    }
+}
+impl std::fmt::Display for CorpusReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for case in &self.cases {
+            let status = if case.passed { "ok" } else { "FAIL" };
+            match (case.predicted, case.error) {
+                (Some(predicted), Some(error)) => writeln!(
+                    f,
+                    "{:<4} {:<6} {}: measured {:.4}, predicted {:.4}, error {:+.4} (tolerance {:.4})",
+                    status, case.kind, case.name, case.measured, predicted, error, case.tolerance
+                )?,
+                _ => writeln!(
+                    f,
+                    "{:<4} {:<6} {}: {}",
+                    status,
+                    case.kind,
+                    case.name,
+                    case.message.as_deref().unwrap_or("no prediction")
+                )?,
+            }
+        }
+        write!(f, "{} of {} cases failed", self.failures, self.cases.len())
+    }
+}
+
+pub fn run_case(case: &ReferenceCase) -> CaseResult {
+    let measured = case.measurement.measured();
+    let tolerance = case
+        .tolerance
+        .unwrap_or_else(|| case.measurement.default_tolerance());
+    let predicted = case
+        .weapon
+        .build()
+        .and_then(|weapon| case.measurement.predict(&weapon));
+    let (predicted, message) = match predicted {
+        Ok(predicted) => (Some(predicted), None),
+        Err(err) => (None, Some(err)),
+    };
+    let error = predicted.map(|x| x - measured);
+    CaseResult {
+        name: case.name.clone(),
+        kind: case.measurement.kind(),
+        measured,
+        predicted,
+        error,
+        tolerance,
+        passed: error.map_or(false, |x| x.abs() <= tolerance),
+        message,
+    }
+}
+
+pub fn run_cases(cases: &[ReferenceCase]) -> CorpusReport {
+    let cases: Vec<CaseResult> = cases.iter().map(run_case).collect();
+    CorpusReport {
+        failures: cases.iter().filter(|x| !x.passed).count(),
+        cases,
+    }
+}
+
+///Takes a corpus in the same format as build_resources/reference_corpus.json
+pub fn run_corpus(json: &str) -> Result<CorpusReport, String> {
+    let cases: Vec<ReferenceCase> = serde_json::from_str(json).map_err(|err| err.to_string())?;
+    Ok(run_cases(&cases))
+}
@@ -132,6 +132,36 @@ fn test_pulse_range() {
     });
 }
 
+#[test]
+fn test_stat_curves() {
+    setup_pulse();
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let curves = weapon.calc_stat_curve_set(false);
+    assert_eq!(curves.base.reload_time.len(), 101);
+    assert_eq!(curves.dynamic.reserve_size.len(), 101);
+    //the point at the weapon's own stat matches the single value getters
+    assert!(cmp_floats(
+        curves.base.reload_time[50],
+        weapon.calc_reload_time(None, None, false).reload_time
+    ));
+    assert!(cmp_floats(
+        curves.base.ready_time[50],
+        weapon.calc_handling_times(None, None, false).ready_time
+    ));
+    assert!(cmp_floats(
+        curves.dynamic.hip_falloff_start[50],
+        weapon
+            .calc_range_falloff(Some(weapon.static_calc_input()), None, false)
+            .hip_falloff_start
+    ));
+    assert!(curves.base.reload_time[100] < curves.base.reload_time[0]);
+    assert!(curves.base.hip_falloff_end[100] > curves.base.hip_falloff_end[0]);
+    let csv = curves.to_csv();
+    assert_eq!(csv.lines().count(), 102);
+    assert!(csv.starts_with("stat,reload_time,"));
+    assert!(csv.lines().nth(1).unwrap().starts_with("0,"));
+}
+
 #[test]
 fn test_pulse_firing_data() {
     setup_pulse();
@@ -313,6 +343,764 @@ fn test_bow_firing_data() {
     });
 }
 
+#[test]
+fn test_bow_draw() {
+    let mut bow = Weapon::generate_weapon(
+        0, 31,         //bow
+        715195141,  //precision
+        2,          //special
+        3949783978, //strand
+    )
+    .unwrap();
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::DRAW_TIME.into(), Stat::from(50));
+    stats.insert(StatHashes::STABILITY.into(), Stat::from(40));
+    stats.insert(StatHashes::RELOAD.into(), Stat::from(100));
+    bow.set_stats(stats);
+
+    let draw = bow
+        .calc_charge_profile(&Default::default())
+        .expect("bows charge");
+    assert!(cmp_floats(draw.charge_time, (50.0 * -3.6 + 900.0) / 1100.0));
+    assert!(draw.hold);
+    assert!(cmp_floats(bow.calc_perfect_draw(), 40.0 / 400.0 + 0.3));
+
+    //a held draw means the first arrow is ready straight away, every one after is nocked
+    //then drawn
+    let nock = bow.calc_reload_time(None, None, true).reload_time;
+    let ttk = bow.calc_ttk(0.0).remove(0);
+    assert!(cmp_floats(
+        ttk.body_ttk.time_taken,
+        (draw.charge_time + nock) * (ttk.body_ttk.bodyshots - 1) as f64
+    ));
+
+    //reload past 85 doesn't nock any faster
+    bow.stats.insert(StatHashes::RELOAD.into(), Stat::from(85));
+    assert!(cmp_floats(
+        bow.calc_reload_time(None, None, true).reload_time,
+        nock
+    ));
+
+    bow.add_perk(Perk {
+        stat_buffs: HashMap::new(),
+        enhanced: false,
+        value: 0,
+        hash: Perks::FasterStringT1.into(),
+        raw_hash: Perks::FasterStringT1.into(),
+    });
+    map_perks();
+    let firing = bow.calc_firing_data(Some(bow.static_calc_input()), None, true);
+    assert!(cmp_floats(firing.burst_delay, draw.charge_time - 0.02));
+
+    //strings and draw curves come from weapon_formulas.json
+    let doc = r#"{
+        "bow_strings": {"1885045197": {"draw_time_add": -0.05}},
+        "bow_frames": {"715195141": {"draw_time": {"vpp": -0.003, "offset": 0.9}}}
+    }"#;
+    let report = crate::formulas::load_formula_overrides(doc, 0).unwrap();
+    assert_eq!(report.changed_bow_strings, vec![1885045197]);
+    assert_eq!(report.changed_bow_frames, vec![715195141]);
+    assert!(cmp_floats(bow.calc_draw_time(), 50.0 * -0.003 + 0.9));
+    //no perfect draw curve, the draw can't be held
+    assert!(bow.calc_perfect_draw().is_nan());
+    let firing = bow.calc_firing_data(Some(bow.static_calc_input()), None, true);
+    assert!(cmp_floats(firing.burst_delay, 50.0 * -0.003 + 0.9 - 0.05));
+    crate::formulas::reset_formula_overrides();
+
+    let doc = r#"{"bow_frames": {"715195141": {"draw_time": {"vpp": 0.003, "offset": 0.9}}}}"#;
+    let errors = crate::formulas::load_formula_overrides(doc, 0).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "error: $[\"bow_frames\"][\"715195141\"][\"draw_time\"]: draw time gets worse going from 0 to 1 stat"
+                .to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_sword() {
+    use crate::enemies::Enemy;
+
+    //shipped frame data, no override loaded
+    assert!(crate::formulas::get_sword_frame(903, 0).is_some());
+
+    let mut sword = Weapon::generate_weapon(
+        0, 18,         //sword
+        903,        //adaptive
+        3,          //heavy
+        2303181850, //arc
+    )
+    .unwrap();
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::SWING_SPEED.into(), Stat::from(50));
+    stats.insert(StatHashes::AMMO_CAPACITY.into(), Stat::from(50));
+    stats.insert(StatHashes::CHARGE_RATE.into(), Stat::from(50));
+    stats.insert(StatHashes::GUARD_RESISTANCE.into(), Stat::from(50));
+    stats.insert(StatHashes::GUARD_EFFICIENCY.into(), Stat::from(50));
+    stats.insert(StatHashes::GUARD_ENDURANCE.into(), Stat::from(50));
+    sword.set_stats(stats);
+
+    let profile = sword.calc_sword_profile(&Default::default()).unwrap();
+    assert!(cmp_floats(profile.light_time, 24.0 / 30.0));
+    assert!(cmp_floats(profile.heavy_time, 27.0 / 30.0));
+    assert!(cmp_floats(profile.energy_regen, 12.5));
+    let firing = sword.calc_firing_data(None, None, false);
+    assert!(cmp_floats(firing.burst_delay, profile.light_time));
+    assert_eq!(sword.calc_ammo_sizes(None, None, false).mag_size, 30);
+
+    let guard = sword.calc_guard().unwrap();
+    assert!(cmp_floats(guard.resistance, 0.7));
+    assert!(cmp_floats(guard.efficiency, 1.0));
+    assert!(cmp_floats(guard.endurance, sword.calc_shield_duration()));
+    let misc = sword.get_misc_stats(None, false);
+    assert!(misc.contains_key("guard_resistance"));
+
+    //every swing is one ammo, the opener is a heavy since the sword starts with full energy
+    let dps = sword.calc_dps(Enemy::default(), 1.0);
+    assert_eq!(dps.total_shots, 30);
+    let light = dps.time_damage_data[1].1;
+    assert!(cmp_floats(
+        dps.time_damage_data[0].1,
+        light * profile.heavy_scale
+    ));
+    assert!(dps.total_damage > light * 30.0);
+
+    //faster swings, same damage in less time
+    sword
+        .stats
+        .insert(StatHashes::SWING_SPEED.into(), Stat::from(100));
+    let fast = sword.calc_dps(Enemy::default(), 1.0);
+    assert!(cmp_floats(fast.total_damage, dps.total_damage));
+    assert!(fast.total_time < dps.total_time);
+
+    //an override replaces the shipped frame until reset
+    let doc = r#"{
+        "sword_frames": {
+            "903": {
+                "combo": 3, "finisher_scale": 1.2, "heavy_scale": 2.0, "heavy_delay": 30.0,
+                "swing_speed": {"vpp": 0.0, "offset": 1.0},
+                "energy_regen": {"vpp": 0.15, "offset": 5.0},
+                "guard_resistance": {"vpp": 0.004, "offset": 0.5},
+                "guard_efficiency": {"vpp": -0.01, "offset": 1.5}
+            }
+        }
+    }"#;
+    let report = crate::formulas::load_formula_overrides(doc, 0).unwrap();
+    assert_eq!(report.changed_sword_frames, vec![903]);
+    let profile = sword.calc_sword_profile(&Default::default()).unwrap();
+    assert!(cmp_floats(profile.heavy_time, 1.0));
+    crate::formulas::reset_formula_overrides();
+    let profile = sword.calc_sword_profile(&Default::default()).unwrap();
+    assert!(cmp_floats(profile.heavy_time, 27.0 / 30.0 * 0.85));
+}
+
+#[test]
+fn test_glaive() {
+    use crate::enemies::Enemy;
+
+    let mut glaive = Weapon::generate_weapon(
+        0, 33,         //glaive
+        1316753551, //adaptive
+        2,          //special
+        1847026933, //solar
+    )
+    .unwrap();
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::MAGAZINE.into(), Stat::from(0));
+    stats.insert(StatHashes::INVENTORY_SIZE.into(), Stat::from(50));
+    stats.insert(StatHashes::SHIELD_DURATION.into(), Stat::from(50));
+    glaive.set_stats(stats.clone());
+
+    //shipped frame data, no override loaded
+    assert!(crate::formulas::get_glaive_frame(1316753551, 0).is_some());
+    let profile = glaive.calc_glaive_profile(&Default::default()).unwrap();
+    assert!(cmp_floats(profile.shot_time, 27.0 / 30.0));
+    //two melees charge a shot, unless the first finishes the target
+    let (time, damage, killed) = profile.melee_recharge(100.0, 1000.0);
+    assert!(cmp_floats(time, profile.recharge_time()));
+    assert!(cmp_floats(damage, 200.0 * profile.melee_scale));
+    assert!(!killed);
+    let (time, _, killed) = profile.melee_recharge(100.0, 50.0);
+    assert!(cmp_floats(time, profile.melee_time));
+    assert!(killed);
+
+    //every shot after the magazine is charged by melees out of reserves
+    let ammo = glaive.calc_ammo_sizes(None, None, false);
+    let dps = glaive.calc_dps(Enemy::default(), 1.0);
+    let charged = ammo.reserve_size - ammo.mag_size;
+    assert_eq!(dps.total_shots, ammo.reserve_size);
+    assert_eq!(
+        dps.time_damage_data.len() as i32,
+        dps.total_shots + charged * profile.melees_per_shot
+    );
+    //per ammo metrics leave the melees out
+    assert!(dps.melee_damage > 0.0);
+    let metrics = glaive.calc_dps_metrics(Enemy::default(), 1.0);
+    assert!(cmp_floats(
+        metrics.damage_per_reserve,
+        dps.total_damage - dps.melee_damage
+    ));
+
+    //the two shots in the magazine kill, behind a shield a melee has to finish the job
+    let ttk = glaive.calc_ttk(0.0).remove(0);
+    assert_eq!(ttk.body_ttk.bodyshots, 2);
+    assert!(cmp_floats(ttk.body_ttk.time_taken, profile.shot_time));
+    let shield = glaive.calc_glaive_shield(true).unwrap();
+    let ttk = glaive
+        .calc_ttk_resisted(0.0, shield.damage_resistance, 0.0, &[0])
+        .remove(0);
+    assert!(cmp_floats(
+        ttk.body_ttk.time_taken,
+        profile.shot_time + profile.melee_time
+    ));
+
+    let mut enemy = Enemy {
+        damage_resistance: 0.5,
+        ..Default::default()
+    };
+    enemy.add_damage_resistance(glaive.calc_glaive_shield(false).unwrap().damage_resistance);
+    assert!(cmp_floats(enemy.damage_resistance, 0.8));
+
+    //vexcaliber fires five projectiles for one round
+    let mut vex = Weapon::generate_weapon(0, 33, 2898674457, 2, 1847026933).unwrap();
+    vex.set_stats(stats);
+    let ammo = vex.calc_ammo_sizes(None, None, false);
+    let dps = vex.calc_dps(Enemy::default(), 1.0);
+    assert_eq!(dps.total_shots, ammo.reserve_size);
+    assert_eq!(
+        dps.time_damage_data.len() as i32,
+        dps.total_shots * 5 + (ammo.reserve_size - ammo.mag_size) * profile.melees_per_shot
+    );
+    //with no inner burst delay all five land together
+    let (landed, projectile) = dps.time_damage_data[0];
+    assert!(dps.time_damage_data[..5]
+        .iter()
+        .all(|x| cmp_floats(x.0, landed) && cmp_floats(x.1, projectile)));
+    assert!(dps.time_damage_data[5].0 > landed);
+
+    //an override replaces the shipped frame until reset
+    let doc = r#"{"glaive_frames": {
+        "1316753551": {"melee_scale": 0.75, "melee_delay": 18, "melees_per_shot": 3, "shield_resistance_pve": 0.6, "shield_resistance_pvp": 0.4}
+    }}"#;
+    let report = crate::formulas::load_formula_overrides(doc, 0).unwrap();
+    assert_eq!(report.changed_glaive_frames, vec![1316753551]);
+    let profile = glaive.calc_glaive_profile(&Default::default()).unwrap();
+    assert_eq!(profile.melees_per_shot, 3);
+    crate::formulas::reset_formula_overrides();
+    let profile = glaive.calc_glaive_profile(&Default::default()).unwrap();
+    assert_eq!(profile.melees_per_shot, 2);
+}
+
+#[test]
+fn test_projectile_travel() {
+    use crate::enemies::Enemy;
+
+    let mut rocket = Weapon::generate_weapon(
+        0, 10,         //rocket
+        1019291327, //high impact
+        3,          //heavy
+        2303181850, //arc
+    )
+    .unwrap();
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::VELOCITY.into(), Stat::from(100));
+    rocket.set_stats(stats);
+    let velocity = rocket.calc_projectile_velocity(None, false, None);
+    assert!(cmp_floats(velocity, 42.0));
+    assert!(cmp_floats(rocket.calc_travel_time(84.0, None, false, None), 2.0));
+
+    //damage lands after the rocket crosses the distance, not when it's fired
+    let close = rocket.calc_dps(Enemy::default(), 1.0);
+    let far = rocket.calc_dps(
+        Enemy {
+            distance: velocity,
+            ..Default::default()
+        },
+        1.0,
+    );
+    assert!(cmp_floats(close.total_damage, far.total_damage));
+    assert!(cmp_floats(
+        far.time_damage_data[0].0,
+        close.time_damage_data[0].0 + 1.0
+    ));
+
+    let close = rocket.calc_ttk(0.0).remove(0);
+    let far = rocket.calc_ttk_resisted(0.0, 0.0, velocity, &[0]).remove(0);
+    assert_eq!(close.body_ttk.bodyshots, far.body_ttk.bodyshots);
+    assert!(cmp_floats(
+        far.body_ttk.time_taken,
+        close.body_ttk.time_taken + 1.0
+    ));
+
+    //hitscan doesn't travel
+    let pulse = Weapon::generate_weapon(0, 13, 69420, 1, 2303181850).unwrap();
+    assert!(cmp_floats(pulse.calc_travel_time(50.0, None, false, None), 0.0));
+}
+
+#[test]
+fn test_group_damage() {
+    use crate::enemies::{Enemy, TargetGroup};
+    use crate::perks::{lib::ExtraDamageSpread, Perks};
+
+    map_perks();
+    let mut launcher = Weapon::generate_weapon(
+        0, 23,         //grenade launcher
+        1294026524, //adaptive
+        3,          //heavy
+        1847026933, //solar
+    )
+    .unwrap();
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::BLAST_RADIUS.into(), Stat::from(50));
+    launcher.set_stats(stats);
+
+    //shipped blast data, no override loaded
+    let blast = launcher.calc_blast_profile().unwrap();
+    assert!(cmp_floats(blast.radius, 4.0));
+    assert!(cmp_floats(blast.falloff(0.0), 1.0));
+    assert!(cmp_floats(blast.falloff(4.0), 0.5));
+    assert!(cmp_floats(blast.falloff(4.5), 0.0));
+    let mut group = TargetGroup {
+        enemy: Enemy {
+            health: 1.0,
+            ..Default::default()
+        },
+        count: 3,
+        spacing: 2.0,
+    };
+
+    //one grenade clears a tight group, spread out each target needs its own
+    let tight = launcher.calc_group_damage(&group, 1.0);
+    assert_eq!(tight.total_shots, 1);
+    assert_eq!(tight.targets_killed, 3);
+    assert_eq!(tight.clear_time, Some(0.0));
+    assert!(cmp_floats(tight.total_damage, 3.0));
+    group.spacing = 10.0;
+    let spread = launcher.calc_group_damage(&group, 1.0);
+    assert_eq!(spread.total_shots, 3);
+    assert_eq!(spread.kill_times[0], Some(0.0));
+    assert!(spread.clear_time.unwrap() > 0.0);
+
+    //targets in the blast take falloff damage from the explosion alone
+    group.enemy.health = 1e9;
+    group.count = 1;
+    let single = launcher.calc_group_damage(&group, 1.0);
+    group.count = 3;
+    group.spacing = 2.0;
+    let crowd = launcher.calc_group_damage(&group, 1.0);
+    let damage = launcher.get_damage_profile(false);
+    let explosion_share = damage.explosion_dmg
+        / (damage.impact_dmg * damage.crit_mult + damage.explosion_dmg);
+    assert_eq!(single.total_shots, crowd.total_shots);
+    assert!(cmp_floats_delta(
+        crowd.total_damage,
+        single.total_damage * (1.0 + explosion_share * (0.75 + 0.5)),
+        0.01
+    ));
+    assert_eq!(crowd.clear_time, None);
+
+    //cleaving extra damage reaches as far as its shipped radius
+    assert_eq!(
+        ExtraDamageSpread::cleave(Perks::ReignHavoc),
+        ExtraDamageSpread::Cleave { radius: 3.0 }
+    );
+    let doc = r#"{
+        "blast_radii": {"Grenade Launcher": {"radius": {"vpp": 0.0, "offset": 0.0}, "edge_damage": 0.5}},
+        "cleave_radii": {"1275731761": {"radius": 4.0}}
+    }"#;
+    let report = crate::formulas::load_formula_overrides(doc, 0).unwrap();
+    assert_eq!(report.changed_blast_radii, vec![23]);
+    assert_eq!(report.changed_cleave_radii, vec![1275731761]);
+    let errors = crate::formulas::load_formula_overrides(
+        r#"{"cleave_radii": {"1275731761": {"radius": 0.0}}}"#,
+        0,
+    )
+    .unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|x| x.contains("a cleave has to reach past the target that was shot")));
+    assert_eq!(
+        ExtraDamageSpread::cleave(Perks::ClusterBomb),
+        ExtraDamageSpread::Cleave { radius: 4.0 }
+    );
+    //an explosion that reaches nobody else only hits the target shot
+    group.enemy.health = 1.0;
+    let lone = launcher.calc_group_damage(&group, 1.0);
+    assert_eq!(lone.total_shots, 3);
+    crate::formulas::reset_formula_overrides();
+    assert_eq!(
+        ExtraDamageSpread::cleave(Perks::ClusterBomb),
+        ExtraDamageSpread::Cleave { radius: 2.5 }
+    );
+}
+
+#[test]
+fn test_champion_ttk() {
+    use crate::enemies::champions::{
+        load_champion_profiles, reset_champion_profiles, ChampionMod, ChampionType,
+    };
+    use crate::enemies::{Enemy, EnemyType};
+
+    let pulse = Weapon::generate_weapon(0, 13, 69420, 1, 2303181850).unwrap();
+    let champion = Enemy {
+        type_: EnemyType::CHAMPION,
+        ..Default::default()
+    };
+    let timeline = pulse.calc_dps(champion.clone(), 1.0).time_damage_data;
+    let health = timeline.iter().map(|x| x.1).sum::<f64>() / 4.0;
+    //with no resistance left the champion dies where the raw damage reaches its health
+    let mut dealt = 0.0;
+    let raw_ttk = timeline
+        .iter()
+        .find(|x| {
+            dealt += x.1;
+            dealt >= health
+        })
+        .unwrap()
+        .0;
+
+    let mods = [ChampionMod {
+        champion: ChampionType::Unstoppable,
+        weapon_type: 13,
+    }];
+    assert!(pulse.stunned_champions(&[]).is_empty());
+    assert_eq!(pulse.stunned_champions(&mods), vec![ChampionType::Unstoppable]);
+    //exotic stuns come from champions.json by weapon hash
+    let arbalest = Weapon {
+        hash: 2564164194,
+        ..pulse.clone()
+    };
+    assert_eq!(arbalest.stunned_champions(&[]), vec![ChampionType::Barrier]);
+
+    //no profile is measured yet so there's nothing to fight
+    assert!(pulse.calc_champion_ttk(&champion, health, &mods, 1.0).is_empty());
+
+    //test-only profiles, not real champion numbers
+    let profiles = r#"[
+        {"champion": "barrier", "resistance": 0.3, "regen": 0.02, "immune_thresholds": [0.66, 0.33], "immune_time": 6.0, "stun_time": 4.0},
+        {"champion": "overload", "resistance": 0.3, "regen": 0.06, "stun_time": 6.0},
+        {"champion": "unstoppable", "resistance": 0.5, "stun_time": 4.0}
+    ]"#;
+    let bad = r#"[{"champion": "barrier", "resistance": 1.5, "stun_time": 4.0}]"#;
+    assert!(load_champion_profiles(bad).is_err());
+    assert_eq!(load_champion_profiles(profiles).unwrap(), ChampionType::ALL.to_vec());
+    let ttks = pulse.calc_champion_ttk(&champion, health, &mods, 1.0);
+    assert_eq!(ttks.len(), 3);
+    for ttk in ttks {
+        assert_eq!(ttk.can_stun, ttk.champion == ChampionType::Unstoppable);
+        //a stun only ever helps
+        if let Some(unstunned) = ttk.unstunned {
+            assert!(ttk.stunned.unwrap() < unstunned);
+        }
+        match ttk.champion {
+            ChampionType::Unstoppable => assert!(cmp_floats(ttk.stunned.unwrap(), raw_ttk)),
+            //heals faster than a primary that can't keep it stunned
+            ChampionType::Overload => assert_eq!(ttk.unstunned, None),
+            ChampionType::Barrier => assert!(ttk.unstunned.is_some()),
+        }
+    }
+    reset_champion_profiles();
+}
+
+#[test]
+fn test_encounter() {
+    use crate::activity::encounter::{Downtime, Encounter, MAX_PHASES};
+    use crate::enemies::{Enemy, EnemyType};
+
+    let pulse = Weapon::generate_weapon(0, 13, 69420, 1, 2303181850).unwrap();
+    let boss = Enemy {
+        type_: EnemyType::BOSS,
+        ..Default::default()
+    };
+    let dps = pulse.calc_dps(boss.clone(), 1.0);
+    let timeline = dps.time_damage_data.clone();
+    let damage_before = |time: f64| -> f64 {
+        timeline
+            .iter()
+            .filter(|x| x.0 < time)
+            .map(|x| x.1)
+            .sum()
+    };
+    let mut encounter = Encounter {
+        health: 1e12,
+        phase_length: 10.0,
+        immune_windows: vec![],
+        add_waves: vec![],
+        crit_exposure: 1.0,
+        ammo_refill: 0.0,
+    };
+
+    //primary ammo never runs out, the timeline is fired again once it ends
+    let response = pulse.simulate_encounter(&boss, &encounter, 1.0);
+    assert_eq!(response.phases_to_kill, None);
+    assert_eq!(response.phase_damage.len(), MAX_PHASES);
+    assert!(cmp_floats(response.phase_damage[0], damage_before(10.0)));
+    let cycles = (10.0 * MAX_PHASES as f64 / dps.total_time).floor();
+    assert!(response.phase_damage.iter().sum::<f64>() >= dps.total_damage * cycles);
+
+    //without refills special reserves run dry and the boss lives
+    let special = Weapon {
+        ammo_type: AmmoType::SPECIAL,
+        ..pulse.clone()
+    };
+    let special_dps = special.calc_dps(boss.clone(), 1.0);
+    let response = special.simulate_encounter(&boss, &encounter, 1.0);
+    assert_eq!(response.phases_to_kill, None);
+    assert!(response.phase_damage.len() < MAX_PHASES);
+    assert!(cmp_floats_delta(
+        response.phase_damage.iter().sum::<f64>(),
+        special_dps.time_damage_data.iter().map(|x| x.1).sum(),
+        0.01
+    ));
+    encounter.ammo_refill = 1.0;
+    let response = special.simulate_encounter(&boss, &encounter, 1.0);
+    assert_eq!(response.phase_damage.len(), MAX_PHASES);
+
+    encounter.health = damage_before(10.0) * 1.5;
+    let response = pulse.simulate_encounter(&boss, &encounter, 1.0);
+    assert_eq!(response.phases_to_kill, Some(2));
+    assert!(response.kill_time.unwrap() < 10.0);
+
+    //downtime overlaps merge, the boss is only shot outside of it
+    encounter.immune_windows = vec![Downtime {
+        start: 2.0,
+        duration: 3.0,
+    }];
+    encounter.add_waves = vec![Downtime {
+        start: 4.0,
+        duration: 3.0,
+    }];
+    assert_eq!(encounter.active_windows(), vec![(0.0, 2.0), (7.0, 10.0)]);
+    encounter.health = 1e12;
+    let response = pulse.simulate_encounter(&boss, &encounter, 1.0);
+    assert!(cmp_floats(response.phase_damage[0], damage_before(5.0)));
+    encounter.crit_exposure = 0.0;
+    let hidden = pulse.simulate_encounter(&boss, &encounter, 1.0);
+    assert!(hidden.phase_damage[0] < response.phase_damage[0]);
+}
+
+#[test]
+fn test_dps_metrics() {
+    use crate::enemies::Enemy;
+    use crate::weapons::dps_calc::DPS_WINDOWS;
+
+    let pulse = Weapon::generate_weapon(0, 13, 69420, 1, 2303181850).unwrap();
+    let enemy = Enemy {
+        health: 1e12,
+        ..Default::default()
+    };
+    let dps = pulse.calc_dps(enemy.clone(), 1.0);
+    let ammo = pulse.calc_ammo_sizes(None, None, false);
+    let metrics = pulse.calc_dps_metrics(enemy, 1.0);
+
+    assert_eq!(metrics.window_dps.len(), DPS_WINDOWS.len());
+    assert!(cmp_floats(
+        metrics.window_dps[0].dps,
+        dps.get_dps_over_window(DPS_WINDOWS[0])
+    ));
+    assert!(cmp_floats(
+        metrics.sustained_dps,
+        dps.total_damage / dps.total_time
+    ));
+    //a primary brick refills a magazine
+    assert_eq!(pulse.calc_brick_size(&ammo), Some(ammo.mag_size));
+    assert!(cmp_floats(
+        metrics.damage_per_brick.unwrap(),
+        metrics.damage_per_mag
+    ));
+    //special and heavy bricks only come from weapon_formulas.json
+    let special = Weapon {
+        ammo_type: AmmoType::SPECIAL,
+        ..pulse.clone()
+    };
+    assert_eq!(special.calc_brick_size(&ammo), None);
+    assert!(
+        crate::formulas::load_formula_overrides(r#"{"brick_sizes": {"Pulse Rifle": 0.5}}"#, 1)
+            .is_err()
+    );
+    let report = crate::formulas::load_formula_overrides(
+        r#"{"brick_sizes": {"Pulse Rifle": 4}}"#,
+        1,
+    )
+    .unwrap();
+    assert_eq!(report.changed_brick_sizes, vec![13]);
+    assert_eq!(special.calc_brick_size(&ammo), Some(4));
+    crate::formulas::reset_formula_overrides();
+    //a rocket brick is one rocket
+    let rocket = Weapon::generate_weapon(
+        0, 10,         //rocket launcher
+        1294026524, //adaptive
+        3,          //heavy
+        1847026933, //solar
+    )
+    .unwrap();
+    let rocket_ammo = rocket.calc_ammo_sizes(None, None, false);
+    assert_eq!(rocket.calc_brick_size(&rocket_ammo), Some(1));
+    let rocket_metrics = rocket.calc_dps_metrics(Enemy::default(), 1.0);
+    assert!(cmp_floats(
+        rocket_metrics.damage_per_brick.unwrap(),
+        rocket_metrics.damage_per_mag / rocket_ammo.mag_size as f64
+    ));
+    assert!(metrics.damage_per_reserve > metrics.damage_per_mag);
+    assert!(metrics.time_to_empty_reserves > metrics.time_to_empty_mag);
+    assert!(metrics.reload_share > 0.0 && metrics.reload_share < 1.0);
+    assert!(cmp_floats(
+        metrics.reload_share,
+        dps.reload_time / dps.total_time
+    ));
+}
+
+#[test]
+fn test_armor_stats() {
+    use crate::activity::armor_stats::{
+        calc_armor_stats, load_armor_stat_data, reset_armor_stat_data, stat_tier, ArmorStats,
+        RESILIENCE_HEALTH,
+    };
+    use crate::activity::{Player, PlayerClass};
+
+    assert_eq!(stat_tier(-5), 0);
+    assert_eq!(stat_tier(69), 6);
+    assert_eq!(stat_tier(140), 10);
+    let stats = ArmorStats {
+        mobility: 100,
+        resilience: 100,
+        recovery: 30,
+        discipline: 100,
+        intellect: 0,
+        strength: 50,
+    };
+    //nothing is measured yet, resilience is all there is
+    let hunter = calc_armor_stats(PlayerClass::Hunter, &stats);
+    assert_eq!(hunter.grenade_cooldown, None);
+    assert_eq!(hunter.class_ability_cooldown, None);
+    assert_eq!(hunter.walk_speed, None);
+    assert_eq!(hunter.resilience_tier, 10);
+    assert!(cmp_floats(hunter.pvp_health, RESILIENCE_HEALTH[10]));
+
+    //test-only data, not real cooldowns
+    let scale = "[1.0, 0.9, 0.8, 0.7, 0.6, 0.5, 0.45, 0.4, 0.35, 0.3, 0.25]";
+    let bad = r#"{"cooldown_scale": [0.5, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1]}"#;
+    assert!(load_armor_stat_data(bad).is_err());
+    load_armor_stat_data(&format!(
+        r#"{{
+            "cooldown_scale": {scale},
+            "grenade_cooldown": 100.0, "melee_cooldown": 100.0, "super_cooldown": 400.0,
+            "hunter_class_cooldown": 30.0, "warlock_class_cooldown": 90.0,
+            "health_regen_time": [9, 8, 7, 6, 5, 4, 3.5, 3, 2.5, 2, 1.5],
+            "walk_speed": [5, 5.1, 5.2, 5.3, 5.4, 5.5, 5.6, 5.7, 5.8, 5.9, 6]
+        }}"#
+    ))
+    .unwrap();
+    let base = calc_armor_stats(PlayerClass::Unknown, &ArmorStats::default());
+    assert_eq!(base.class_ability_cooldown, None);
+    let hunter = calc_armor_stats(PlayerClass::Hunter, &stats);
+    let warlock = calc_armor_stats(PlayerClass::Warlock, &stats);
+    let titan = calc_armor_stats(PlayerClass::Titan, &stats);
+    assert!(hunter.grenade_cooldown < base.grenade_cooldown);
+    assert!(hunter.melee_cooldown < base.melee_cooldown);
+    assert_eq!(hunter.super_cooldown, base.super_cooldown);
+    //hunters dodge off mobility, warlocks rift off recovery
+    assert!(cmp_floats(hunter.class_ability_cooldown.unwrap(), 30.0 * 0.25));
+    assert!(cmp_floats(warlock.class_ability_cooldown.unwrap(), 90.0 * 0.7));
+    assert_eq!(titan.class_ability_cooldown, None);
+    assert!(hunter.health_regen_time < base.health_regen_time);
+    assert!(hunter.walk_speed > base.walk_speed);
+    assert!(hunter.damage_resistance > base.damage_resistance);
+    reset_armor_stat_data();
+
+    //the resilience tier feeds flinch, lower is less flinch
+    let player = Player {
+        armor: stats,
+        ..Default::default()
+    };
+    let pulse = Weapon::generate_weapon(0, 13, 69420, 1, 2303181850).unwrap();
+    assert!(cmp_floats(
+        pulse.calc_flinch_resist(None, player.resilience_tier(), true, None),
+        pulse.calc_flinch_resist(None, 0, true, None) * 0.9
+    ));
+    //and the health the pvp ttk has to chew through, getWeaponTtk without a tier keeps all 11
+    let tiers = crate::weapons::ttk_calc::resilience_tiers(None);
+    assert_eq!(pulse.calc_ttk_resisted(0.0, 0.0, 0.0, &tiers).len(), 11);
+    let tiers = crate::weapons::ttk_calc::resilience_tiers(Some(player.resilience_tier()));
+    let ttk = pulse.calc_ttk_resisted(0.0, 0.0, 0.0, &tiers);
+    assert_eq!(ttk.len(), 1);
+    assert_eq!(ttk[0].value, 10);
+    let all = pulse.calc_ttk(0.0);
+    assert!(cmp_floats(
+        ttk[0].optimal_ttk.time_taken,
+        all[10].optimal_ttk.time_taken
+    ));
+}
+
+#[test]
+fn test_fusion_charge() {
+    let mut fusion = Weapon::generate_weapon(
+        0, 11,         //fusion
+        1019291327, //high impact
+        2,          //special
+        2303181850, //arc
+    )
+    .unwrap();
+    let mut stats = HashMap::new();
+    stats.insert(StatHashes::CHARGE_TIME.into(), Stat::from(40));
+    stats.insert(StatHashes::MAGAZINE.into(), Stat::from(50));
+    fusion.set_stats(stats);
+    //charge time masterwork
+    fusion.add_perk(Perk {
+        stat_buffs: HashMap::from([(StatHashes::CHARGE_TIME.into(), 10)]),
+        enhanced: false,
+        value: 0,
+        hash: Perks::ChargetimeMW.into(),
+        raw_hash: Perks::ChargetimeMW.into(),
+    });
+
+    let charge = fusion
+        .calc_charge_profile(&Default::default())
+        .expect("fusions charge");
+    assert!(cmp_floats(charge.charge_time, 29.0 / 30.0 - 10.0 * 0.004));
+    assert_eq!(charge.bolts, 5);
+    assert!(!charge.hold);
+    //exotic frames keep their own charge time whatever the stat says
+    let mut exotic = fusion.clone();
+    exotic.intrinsic_hash = 2000;
+    let exotic_charge = exotic.calc_charge_profile(&Default::default()).unwrap();
+    assert!(cmp_floats(exotic_charge.charge_time, 29.0 / 30.0));
+
+    //rpm and burst delay come from the same cycle
+    let firing = fusion.calc_firing_data(None, None, true);
+    assert!(cmp_floats(firing.burst_delay, charge.charge_time));
+    assert!(cmp_floats(firing.rpm, 60.0 / charge.cycle_time()));
+
+    //the first charge counts towards time to kill
+    let ttk = fusion.calc_ttk(0.0).remove(0);
+    assert!(ttk.body_ttk.bodyshots <= charge.bolts);
+    assert!(cmp_floats(
+        ttk.body_ttk.time_taken,
+        charge.charge_time + charge.bolt_spacing * (ttk.body_ttk.bodyshots - 1) as f64
+    ));
+
+    fusion.add_perk(Perk {
+        stat_buffs: HashMap::new(),
+        enhanced: false,
+        value: 0,
+        hash: Perks::AcceleratedCoils.into(),
+        raw_hash: Perks::AcceleratedCoils.into(),
+    });
+    map_perks();
+    let firing = fusion.calc_firing_data(Some(fusion.static_calc_input()), None, true);
+    assert!(cmp_floats(firing.burst_delay, charge.charge_time - 0.04));
+
+    let vex = Weapon::generate_weapon(
+        4289226715, 11,         //fusion
+        3610750208, //vex mythoclast
+        1,          //primary
+        1847026933, //solar
+    )
+    .unwrap();
+    assert!(vex.calc_charge_profile(&Default::default()).is_none());
+}
+
 #[test]
 fn test_phase_mag() {
     //setup weapons
@@ -347,3 +1135,861 @@ fn test_phase_mag() {
     assert_eq!(lw_data.pvp_crit_mult, p_data.pvp_crit_mult);
     assert_eq!(f64::trunc(lw_data.pvp_impact_damage*1000.0)/1000.0, p_data.pvp_impact_damage);
 }
+
+const OVERRIDE_DOC: &str = r#"{
+    "Pulse Rifle": {
+        "69420": {"cat": "test", "subFam": "test", "magProf": "test"},
+        "1": {"cat": "test", "subFam": "test", "magProf": "test"},
+        "cat": {
+            "test": {
+                "range": {"vpp_start": 0.1, "offset_start": 10.0, "vpp_end": 0.2, "offset_end": 20.0, "floor_percent": 0.5},
+                "reload": {"evpp": 0.0, "vpp": -0.1, "offset": 12.0},
+                "handling": {"ready": {"vpp": -0.01, "offset": 6.0}, "stow": {"vpp": -0.01, "offset": 2.0}, "ads": {"vpp": -0.01, "offset": 3.5}},
+                "combatant_scalars": {"vehicle": 1.0, "boss": 1.0, "champion": 1.0, "miniboss": 1.0, "elite": 1.0, "minor": 1.0}
+            }
+        },
+        "subFam": {
+            "test": {"damage": 10.0, "crit_mult": 25.5, "pve_damage": 10.0, "pve_crit_mult": 25.5, "burst_delay": 2, "burst_size": 5, "inner_burst_delay": 2}
+        },
+        "magProf": {
+            "test": {"mag": {"evpp": 0.0, "vpp": 0.5, "offset": 25.0}, "reserve_id": 0}
+        }
+    }
+}"#;
+
+#[test]
+fn test_formula_overrides() {
+    let report = crate::formulas::load_formula_overrides(OVERRIDE_DOC, 1234).unwrap();
+    assert_eq!(report.changed.len(), 1);
+    assert_eq!(report.changed[0].hash, 69420);
+    assert_eq!(report.changed[0].components, vec!["reload".to_string()]);
+    assert_eq!(report.added.len(), 1);
+    assert_eq!(report.added[0].hash, 1);
+
+    setup_pulse();
+    PERS_DATA.with(|perm_data| {
+        let weapon = perm_data.borrow_mut().weapon.clone();
+        let response = weapon.calc_reload_time(None, None, true);
+        assert!(
+            cmp_floats(response.reload_time, 7.0),
+            "reload time: {}",
+            response.reload_time
+        );
+        assert_eq!(response.timestamp, 1234);
+        assert_ne!(weapon.handling_formula.timestamp, 1234);
+    });
+
+    //loading the same document again changes nothing
+    let report = crate::formulas::load_formula_overrides(OVERRIDE_DOC, 5678).unwrap();
+    assert_eq!(report.unchanged.len(), 2);
+
+    crate::formulas::reset_formula_overrides();
+    setup_pulse();
+    PERS_DATA.with(|perm_data| {
+        let weapon = perm_data.borrow_mut().weapon.clone();
+        let response = weapon.calc_reload_time(None, None, true);
+        assert!(
+            cmp_floats(response.reload_time, 5.0),
+            "reload time: {}",
+            response.reload_time
+        );
+    });
+}
+
+#[test]
+fn test_formula_override_validation() {
+    let doc = OVERRIDE_DOC
+        .replace("\"1\": {\"cat\": \"test\"", "\"1\": {\"cat\": \"missing\"")
+        .replace("Pulse Rifle", "Pulse Rifel");
+    let errors = crate::formulas::load_formula_overrides(&doc, 0).unwrap_err();
+    assert_eq!(
+        errors,
+        vec!["error: $[\"Pulse Rifel\"]: unknown weapon family".to_string()]
+    );
+
+    let doc = OVERRIDE_DOC.replace("\"1\": {\"cat\": \"test\"", "\"1\": {\"cat\": \"missing\"");
+    let errors = crate::formulas::load_formula_overrides(&doc, 0).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "error: $[\"Pulse Rifle\"][\"1\"][\"cat\"]: \"missing\" is not defined in cat"
+                .to_string()
+        ]
+    );
+    assert!(crate::formulas::get_weapon_formulas(13, 1, 1).is_none());
+}
+
+#[test]
+fn test_formula_validation() {
+    use crate::formulas::validate::{validate_weapon_formulas, Severity};
+
+    let diagnostics = validate_weapon_formulas(OVERRIDE_DOC);
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+
+    let doc = OVERRIDE_DOC
+        .replace("\"vpp\": -0.1, \"offset\": 12.0", "\"vpp\": 0.1, \"offset\": 12.0")
+        .replace(
+            "\"1\": {\"cat\": \"test\"",
+            "\"69420\": {\"cat\": \"test\", \"subFam\": \"test\", \"magProf\": \"test\"},\n\"2\": {\"cat\": \"test\"",
+        )
+        .replace("\"magProf\": {", "\"magProf\": {\"unused\": {\"mag\": {\"vpp\": 0.0, \"offset\": 1.0}},");
+    let diagnostics: Vec<String> = validate_weapon_formulas(&doc)
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+    assert_eq!(
+        diagnostics,
+        vec![
+            "error: $[\"Pulse Rifle\"][\"69420\"]: duplicate weapon hash",
+            "error: $[\"Pulse Rifle\"][\"cat\"][\"test\"][\"reload\"]: reload time gets worse going from 0 to 1 stat",
+            "warning: $[\"Pulse Rifle\"][\"magProf\"][\"unused\"]: defined but never used",
+        ]
+    );
+
+    let diagnostics = validate_weapon_formulas("{\"Pulse Rifle\": {\"1\": }}");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].path, "$");
+}
+
+#[test]
+fn test_reserve_formulas() {
+    use crate::weapons::reserve_calc::calc_reserves;
+
+    //sniper rifles switch curves at 100 mag stat
+    assert_eq!(calc_reserves(4.0, 50, 50, 121, 1.0), Some(20));
+    assert_eq!(calc_reserves(4.0, 100, 50, 121, 1.0), Some(23));
+    //shotgun offsets come from the mag size
+    assert_eq!(calc_reserves(5.0, 50, 0, 71, 1.0), Some(14));
+    //forerunner only rolls a few inventory stats
+    assert_eq!(calc_reserves(8.0, 50, 76, 2984682260, 1.0), Some(79));
+    assert_eq!(calc_reserves(8.0, 50, 77, 2984682260, 1.0), Some(87));
+    assert_eq!(calc_reserves(1.0, 50, 10, 3698448090, 1.0), Some(241));
+    //machine guns care how far the mag size rounds up
+    assert_eq!(calc_reserves(40.6, 50, 50, 81, 1.0), Some(338));
+    assert_eq!(calc_reserves(40.6, 50, 50, 82, 1.0), Some(520));
+    //adaptive burst linear fusions pick their offset by mag stat
+    assert_eq!(calc_reserves(3.0, 50, 50, 2202, 1.0), Some(21));
+    assert_eq!(calc_reserves(3.0, 95, 100, 2202, 1.0), Some(23));
+    assert_eq!(calc_reserves(30.0, 50, 50, 12345, 1.0), None);
+
+    let doc = r#"{"reserves": {"121": {"kind": "constant", "value": 5}}}"#;
+    let report = crate::formulas::load_formula_overrides(doc, 0).unwrap();
+    assert_eq!(report.changed_reserves, vec![121]);
+    assert_eq!(calc_reserves(4.0, 100, 50, 121, 2.0), Some(10));
+    crate::formulas::reset_formula_overrides();
+    assert_eq!(calc_reserves(4.0, 100, 50, 121, 1.0), Some(23));
+
+    let doc = r#"{"reserves": {"121": {"kind": "exact", "values": [[20, 5], [0, 4]], "default": 3}}}"#;
+    let errors = crate::formulas::load_formula_overrides(doc, 0).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "error: $[\"reserves\"][\"121\"][\"values\"][1]: table is not sorted by its first value"
+                .to_string(),
+            "error: $[\"reserves\"][\"121\"]: reserves go down with more inventory stat"
+                .to_string()
+        ]
+    );
+
+    //with a reserves section every reserve id has to resolve
+    let doc = OVERRIDE_DOC.replace("\"reserve_id\": 0", "\"reserve_id\": 221").replacen(
+        '{',
+        r#"{"reserves": {"0": {"kind": "constant", "value": 9999}},"#,
+        1,
+    );
+    let errors = crate::formulas::load_formula_overrides(&doc, 0).unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "error: $[\"Pulse Rifle\"][\"magProf\"][\"test\"][\"reserve_id\"]: 221 is not defined in reserves"
+                .to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_weapon_from_item() {
+    let item = crate::types::rs_types::ItemData {
+        hash: 1,
+        weapon_type: 13,       //pulse
+        intrinsic_hash: 69420, //test pulse
+        ammo_type: 1,          //primary
+        damage_type: 3373582085,
+        stat_group: 0,
+        investment_stats: &[],
+        display_stats: &[(4188031367, 50), (943549884, 50), (1240592695, 50)],
+    };
+    let mut weapon = Weapon::from_item_data(&item).unwrap();
+    assert_eq!(weapon.weapon_type, WeaponType::PULSERIFLE);
+    assert_eq!(weapon.ammo_type, AmmoType::PRIMARY);
+    assert_eq!(weapon.damage_type, DamageType::KINETIC);
+    assert_eq!(weapon.intrinsic_hash, 69420);
+    let stats = weapon.get_stats();
+    assert_eq!(stats.get(&StatHashes::RELOAD.into()).unwrap().val(), 50);
+    assert_eq!(stats.get(&StatHashes::HANDLING.into()).unwrap().val(), 50);
+    assert_eq!(stats.get(&StatHashes::RANGE.into()).unwrap().val(), 50);
+
+    let response = weapon.calc_reload_time(None, None, true);
+    assert!(
+        cmp_floats(response.reload_time, 5.0),
+        "reload time: {}",
+        response.reload_time
+    );
+
+    assert!(Weapon::from_item_hash(0).is_none());
+}
+
+#[test]
+#[ignore = "the vendored snapshot has no weapon items until an online build refreshes it"]
+fn test_item_table() {
+    assert!(!crate::database::ITEM_DATA.is_empty());
+    //Arbalest
+    let arbalest =
+        Weapon::from_item_hash(2564164194).expect("Arbalest is not in the item table");
+    assert_eq!(arbalest.weapon_type, WeaponType::LINEARFUSIONRIFLE);
+    for item in crate::database::ITEM_DATA.iter() {
+        let Some(mut weapon) = Weapon::from_item_hash(item.hash) else {
+            //only weapon types with formulas can be built
+            continue;
+        };
+        assert_eq!(weapon.hash, item.hash);
+        assert_eq!(
+            weapon.intrinsic_hash,
+            crate::perks::enhanced_check(item.intrinsic_hash).0
+        );
+        assert_eq!(
+            weapon.weapon_type,
+            WeaponType::from(item.weapon_type as u32)
+        );
+        assert!(
+            !weapon.get_stats().is_empty(),
+            "item {} has no stats",
+            item.hash
+        );
+    }
+}
+
+static TEST_STAT_GROUP: crate::types::rs_types::StatGroup = crate::types::rs_types::StatGroup {
+    hash: 1,
+    scaled_stats: &[
+        crate::types::rs_types::ScaledStat {
+            stat_hash: 4188031367, //reload
+            maximum_value: 100,
+            interpolation: &[(0, 0), (100, 50)],
+        },
+        crate::types::rs_types::ScaledStat {
+            stat_hash: 943549884, //handling
+            maximum_value: 80,
+            interpolation: &[(0, 10), (50, 60), (100, 70)],
+        },
+    ],
+};
+
+#[test]
+fn test_stat_group_interpolation() {
+    let reload = &TEST_STAT_GROUP.scaled_stats[0];
+    //banker's rounding, halves go to the even number
+    assert_eq!(reload.interpolate(41), 20);
+    assert_eq!(reload.interpolate(43), 22);
+    assert_eq!(reload.interpolate(44), 22);
+    let handling = &TEST_STAT_GROUP.scaled_stats[1];
+    assert_eq!(handling.interpolate(25), 35);
+    assert_eq!(handling.interpolate(75), 65);
+    //clamped to the maximum value
+    assert_eq!(handling.interpolate(95), 66);
+    //past the last point the last weight holds
+    let short = crate::types::rs_types::ScaledStat {
+        stat_hash: 155624089, //stability
+        maximum_value: 100,
+        interpolation: &[(10, 20), (50, 40)],
+    };
+    assert_eq!(short.interpolate(80), 40);
+    assert_eq!(short.interpolate(5), 20);
+    assert_eq!(TEST_STAT_GROUP.display_value(1240592695, 50), None);
+}
+
+#[test]
+#[ignore = "the vendored snapshot has no stat groups until an online build refreshes it"]
+fn test_stat_group_table() {
+    assert!(!crate::database::STAT_GROUP_DATA.is_empty());
+    for group in crate::database::STAT_GROUP_DATA.iter() {
+        for stat in group.scaled_stats {
+            assert!(
+                stat.interpolation.windows(2).all(|x| x[0].0 < x[1].0),
+                "stat group {} stat {} is not sorted",
+                group.hash,
+                stat.stat_hash
+            );
+            //every point of the curve below the clamp maps onto its own weight
+            for (value, weight) in stat.interpolation {
+                if *value > stat.maximum_value {
+                    continue;
+                }
+                assert_eq!(
+                    stat.interpolate(*value),
+                    *weight,
+                    "stat group {} stat {} at {}",
+                    group.hash,
+                    stat.stat_hash,
+                    value
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_investment_stats() {
+    setup_pulse();
+    let mut weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    weapon.set_investment_stats(
+        TEST_STAT_GROUP,
+        HashMap::from([
+            (StatHashes::RELOAD.into(), 80),
+            (StatHashes::HANDLING.into(), 25),
+            (StatHashes::RANGE.into(), 47),
+        ]),
+    );
+    weapon.add_perk(Perk {
+        stat_buffs: HashMap::from([
+            (StatHashes::RELOAD.into(), 20),
+            (StatHashes::STABILITY.into(), 10),
+        ]),
+        hash: 1,
+        raw_hash: 1,
+        ..Default::default()
+    });
+    let stats = weapon.get_stats();
+    let reload = stats.get(&StatHashes::RELOAD.into()).unwrap();
+    assert_eq!(reload.base_value, 40);
+    assert_eq!(reload.part_value, 10);
+    assert_eq!(reload.perk_val(), 50);
+    assert_eq!(stats.get(&StatHashes::HANDLING.into()).unwrap().val(), 35);
+    //not scaled by the group, passed through as is
+    assert_eq!(stats.get(&StatHashes::RANGE.into()).unwrap().val(), 47);
+    //no investment, the bump is kept unscaled
+    assert_eq!(stats.get(&StatHashes::STABILITY.into()).unwrap().perk_val(), 10);
+
+    let response = weapon.calc_reload_time(None, None, true);
+    assert!(
+        cmp_floats(response.reload_time, 5.0),
+        "reload time: {}",
+        response.reload_time
+    );
+}
+
+#[test]
+fn test_combatant_scaling() {
+    use crate::activity::combatant_scaling::get_combatant_scaling;
+    use crate::enemies::EnemyType;
+    use crate::types::rs_types::DamageMods;
+
+    let weapon = Weapon {
+        weapon_type: WeaponType::SNIPER,
+        damage_type: DamageType::KINETIC,
+        ammo_type: AmmoType::SPECIAL,
+        intrinsic_hash: 901,
+        damage_mods: DamageMods {
+            minor: 2.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let minor = get_combatant_scaling(&weapon, EnemyType::MINOR);
+    assert!(cmp_floats(minor.weapon_type, 1.6));
+    assert!(cmp_floats(minor.kinetic, 1.15));
+    assert!(cmp_floats(minor.total(), 1.6 * 1.15 * 2.0));
+    //kinetic scaling doesn't apply to bosses
+    assert!(cmp_floats(
+        get_combatant_scaling(&weapon, EnemyType::BOSS).total(),
+        1.0
+    ));
+    assert!(cmp_floats(
+        get_combatant_scaling(&weapon, EnemyType::PLAYER).total(),
+        1.0
+    ));
+
+    //weapon type scalars are data and can be overridden at runtime
+    let doc = r#"{"weapon_type_scalars": {"Sniper Rifle": {"minor": 2.5, "elite": 1.0, "miniboss": 1.0, "champion": 1.0, "boss": 1.0, "vehicle": 1.0}}}"#;
+    let report = crate::formulas::load_formula_overrides(doc, 0).unwrap();
+    assert_eq!(report.changed_type_scalars, vec![WeaponType::SNIPER as u32]);
+    assert!(cmp_floats(
+        get_combatant_scaling(&weapon, EnemyType::MINOR).weapon_type,
+        2.5
+    ));
+    crate::formulas::reset_formula_overrides();
+
+    //and are still part of the built in modifier in the summary
+    let mut input = weapon.static_calc_input();
+    input.enemy_type = &EnemyType::MINOR;
+    let summary = weapon.get_modifier_summary(Some(input), false, None);
+    let built_in = summary.get(&0).unwrap().dmr.clone().unwrap();
+    assert!(cmp_floats(built_in.impact_dmg_scale, minor.total()));
+}
+
+#[test]
+fn test_difficulty_tables() {
+    use crate::activity::{damage_calc, damage_calc::DifficultyOptions, Activity, NO_CAP};
+
+    let mut activity = Activity {
+        difficulty: DifficultyOptions::GRANDMASTER,
+        rpl: 1800,
+        ..Default::default()
+    };
+    activity.player.power = 1775;
+    let locked = activity.get_pl_delta();
+    //grandmasters lock power 25 below recommended
+    activity.player.power = 1830;
+    assert!(cmp_floats(activity.get_pl_delta(), locked));
+    activity.difficulty = DifficultyOptions::MASTER;
+    assert!(activity.get_pl_delta() > locked);
+    activity.cap = -25;
+    assert!(cmp_floats(activity.get_pl_delta(), locked));
+    //an activity cap only ever lowers the difficulty's
+    activity.difficulty = DifficultyOptions::GRANDMASTER;
+    activity.cap = 50;
+    assert!(cmp_floats(activity.get_pl_delta(), locked));
+    assert!(damage_calc::get_difficulty_table(DifficultyOptions::GRANDMASTER as u32)
+        .unwrap()
+        .estimate);
+    assert!(!damage_calc::get_difficulty_table(DifficultyOptions::MASTER as u32)
+        .unwrap()
+        .estimate);
+
+    activity.difficulty = DifficultyOptions::NORMAL;
+    activity.cap = NO_CAP;
+    activity.player.power = 1800;
+    let doc = r#"[{"id": 1, "name": "Flat", "cap": 0, "table": [[-99, 0.5], [0, 0.5]]}]"#;
+    assert_eq!(damage_calc::load_difficulty_tables(doc), Ok(vec![1]));
+    assert!(cmp_floats(activity.get_pl_delta(), 0.5));
+    let doc = r#"[{"id": 1, "name": "Flat", "cap": 0, "table": [[0, 0.5], [-99, 0.5]]}]"#;
+    assert!(damage_calc::load_difficulty_tables(doc).is_err());
+    damage_calc::reset_difficulty_tables();
+    assert!(cmp_floats(activity.get_pl_delta(), 1.0));
+}
+
+#[test]
+fn test_activity_presets() {
+    use crate::activity::{damage_calc::DifficultyOptions, presets, Activity};
+    use crate::enemies::{Enemy, EnemyType};
+
+    let presets = presets::list_activity_presets();
+    assert!(presets.windows(2).all(|x| x[0].id < x[1].id));
+    assert!(presets::get_activity_preset("not_a_preset").is_none());
+
+    let preset = presets::get_activity_preset("gm_nightfall").unwrap();
+    let mut activity = Activity::default();
+    let mut enemy = Enemy {
+        health: 1000.0,
+        ..Default::default()
+    };
+    activity.apply_preset(preset);
+    enemy.apply_preset(preset);
+    assert_eq!(activity.rpl, preset.rpl);
+    assert_eq!(activity.difficulty, DifficultyOptions::GRANDMASTER);
+    assert_eq!(activity.player.power, Activity::default().player.power);
+    assert_eq!(activity.cap, -25);
+    assert_eq!(enemy.type_, EnemyType::CHAMPION);
+    assert!(cmp_floats(enemy.health, preset.health));
+
+    //raids and dungeons sit at the raid's recommended power and cap
+    for preset in presets
+        .iter()
+        .filter(|x| x.difficulty == DifficultyOptions::RAID && !x.id.starts_with("pantheon"))
+    {
+        assert_eq!((preset.rpl, preset.cap), (2000, 20), "{}", preset.id);
+    }
+}
+
+#[test]
+fn test_damage_solver() {
+    use crate::activity::damage_solver::{solve_damage, DamageObservation};
+    use crate::enemies::EnemyType;
+    use crate::types::rs_types::FiringData;
+
+    map_perks();
+    //a legendary linear fusion so the built in 1.15 crit bonus applies
+    let mut weapon = Weapon {
+        weapon_type: WeaponType::LINEARFUSIONRIFLE,
+        damage_type: DamageType::SOLAR,
+        ammo_type: AmmoType::HEAVY,
+        firing_data: FiringData {
+            damage: 1000.0,
+            pve_crit_mult: 2.5,
+            burst_size: 1,
+            ..Default::default()
+        },
+        stats: HashMap::from([(StatHashes::CHARGE_TIME.into(), Stat::from(40))]),
+        ..Default::default()
+    };
+    weapon.add_perk(Perk {
+        stat_buffs: HashMap::new(),
+        enhanced: false,
+        value: 0,
+        hash: 0,
+        raw_hash: 0,
+    });
+    let observation = |damage, enemy_type, rpl, crit, perk_active| DamageObservation {
+        damage,
+        enemy_type,
+        rpl,
+        power: rpl,
+        difficulty: Default::default(),
+        cap: None,
+        buff: 1.0,
+        crit,
+        perk_active,
+    };
+    //a base of 1000 and a perk of 1.2 worked out by hand, at recommended power the rpl
+    //multiplier is 50.75 at 2000 and 45.75 at 1800 and a crit is 2.5 * 1.15
+    let observations = [
+        observation(50750.0, EnemyType::MINOR, 2000, false, false),
+        observation(145906.25, EnemyType::BOSS, 2000, true, false),
+        observation(54900.0, EnemyType::ELITE, 1800, false, true),
+        observation(157837.5, EnemyType::BOSS, 1800, true, true),
+    ];
+
+    let both = solve_damage(&weapon, &observations, None, None).unwrap();
+    assert!(cmp_floats(both.base_damage, 1000.0));
+    assert!(cmp_floats(both.perk_multiplier, 1.2));
+    assert!(both.rms_error < 1e-6);
+    let perk = solve_damage(&weapon, &observations, Some(1000.0), None).unwrap();
+    assert!(cmp_floats(perk.perk_multiplier, 1.2));
+    let base = solve_damage(&weapon, &observations, None, Some(1.2)).unwrap();
+    assert!(cmp_floats(base.base_damage, 1000.0));
+    //a wrong perk multiplier shows up in the residuals
+    let wrong = solve_damage(&weapon, &observations, Some(1000.0), Some(1.0)).unwrap();
+    assert!(wrong.rms_error > 1.0);
+
+    let inactive: Vec<DamageObservation> = observations
+        .iter()
+        .filter(|x| !x.perk_active)
+        .cloned()
+        .collect();
+    assert!(solve_damage(&weapon, &inactive, None, None).is_err());
+    assert!(solve_damage(&weapon, &[], None, None).is_err());
+}
+
+#[test]
+fn test_reference_corpus() {
+    use crate::activity::Activity;
+    use crate::reference::{run_cases, run_corpus, ReferenceCase};
+
+    let report = run_corpus(include_str!("../build_resources/reference_corpus.json")).unwrap();
+    assert!(!report.cases.is_empty());
+    assert_eq!(report.failures, 0, "\n{}", report);
+
+    let mut activity = Activity {
+        rpl: 1800,
+        ..Default::default()
+    };
+    activity.player.power = 1810;
+    //pulse, exotic primary and kinetic scalars against minors
+    let expected = 10.0
+        * 2.0
+        * activity.get_rpl_mult()
+        * activity.get_pl_delta()
+        * 1.15
+        * 1.3
+        * 1.1
+        * 1.25;
+    let bozo = serde_json::json!({
+        "hash": 1651470959,
+        "weaponType": 13,
+        "intrinsicHash": 69420,
+        "ammoType": 1,
+        "damageType": 3373582085u32,
+        "stats": {"4188031367": 50}
+    });
+    let cases: Vec<ReferenceCase> = serde_json::from_value(serde_json::json!([
+        {"name": "reload", "weapon": bozo, "measurement": {"kind": "reload", "time": 5.0}},
+        {"name": "slow reload", "weapon": bozo, "measurement": {"kind": "reload", "time": 5.2}},
+        {"name": "crit", "weapon": bozo, "measurement": {
+            "kind": "damage", "damage": expected, "enemyType": "MINOR",
+            "rpl": 1800, "power": 1810, "buff": 1.25, "crit": true
+        }},
+        {"name": "missing", "weapon": {
+            "hash": 0, "weaponType": 13, "intrinsicHash": 1, "ammoType": 1, "damageType": 0
+        }, "measurement": {"kind": "reload", "time": 5.0}, "tolerance": 100.0}
+    ]))
+    .unwrap();
+    let report = run_cases(&cases);
+    assert_eq!(report.failures, 2, "\n{}", report);
+    let failed: Vec<&str> = report.failed().map(|x| x.name.as_str()).collect();
+    assert_eq!(failed, ["slow reload", "missing"]);
+    assert!(cmp_floats(report.cases[1].error.unwrap(), -0.2));
+    assert!(report.cases[3].predicted.is_none());
+    assert!(report.cases[3].message.is_some());
+}
+
+#[test]
+fn test_weapon_comparison() {
+    use crate::activity::Activity;
+    use crate::enemies::Enemy;
+    use crate::weapons::comparison::{compare_weapons, Better, WeaponComparison};
+    use crate::weapons::weapon_constructor::WeaponConfig;
+
+    let pulse = |reload: i32| {
+        serde_json::from_value::<WeaponConfig>(serde_json::json!({
+            "hash": 1651470959,
+            "weaponType": 13,
+            "intrinsicHash": 69420,
+            "ammoType": 1,
+            "damageType": 3373582085u32,
+            "stats": {"4188031367": reload, "4043523819": 50}
+        }))
+        .unwrap()
+        .build()
+        .unwrap()
+    };
+    let weapons = [pulse(50), pulse(80)];
+    let activity = Activity::default();
+    let enemy = Enemy::default();
+    let find = |comparison: &WeaponComparison, section: &str, name: &str| {
+        comparison
+            .rows
+            .iter()
+            .find(|x| x.section == section && x.name == name)
+            .unwrap_or_else(|| panic!("no {} row {}", section, name))
+            .clone()
+    };
+
+    let comparison = compare_weapons(&weapons, &activity, &enemy, true, false).unwrap();
+    assert_eq!(comparison.weapons, 2);
+    let stat = find(&comparison, "stats", "reload");
+    assert_eq!(stat.values, [Some(50.0), Some(80.0)]);
+    assert_eq!(stat.deltas, [Some(0.0), Some(30.0)]);
+    assert!(cmp_floats(stat.percent_deltas[1].unwrap(), 60.0));
+    assert_eq!(stat.winner, Some(1));
+    let reload = find(&comparison, "reload", "reload_time");
+    assert_eq!(reload.better, Better::Lower);
+    assert!(reload.deltas[1].unwrap() < 0.0);
+    assert_eq!(reload.winner, Some(1));
+    //identical on both sides
+    assert_eq!(find(&comparison, "handling", "ready_time").winner, None);
+    assert_eq!(find(&comparison, "firing", "burst_size").winner, None);
+    find(&comparison, "dps", "dps");
+    assert!(!comparison.rows.iter().any(|x| x.section == "ttk"));
+
+    let comparison = compare_weapons(&weapons, &activity, &enemy, false, true).unwrap();
+    let ttk = find(&comparison, "ttk", "optimal_ttk_0");
+    assert_eq!(ttk.values[0], ttk.values[1]);
+    assert!(!comparison.rows.iter().any(|x| x.section == "dps"));
+
+    assert!(compare_weapons(&weapons[..1], &activity, &enemy, false, false).is_err());
+}
+
+#[test]
+fn test_roll_ranking() {
+    use crate::activity::Activity;
+    use crate::enemies::Enemy;
+    use crate::weapons::roll_ranking::{rank_rolls, RollRankingOptions};
+    use crate::weapons::weapon_constructor::PerkConfig;
+
+    setup_pulse();
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    let perk = |hash: u32, stat: StatHashes, value: i32| PerkConfig {
+        hash,
+        value: 0,
+        stat_buffs: HashMap::from([(stat.into(), value)]),
+    };
+    let columns = vec![
+        vec![
+            perk(1, StatHashes::RELOAD, 0),
+            perk(2, StatHashes::RELOAD, 30),
+        ],
+        vec![
+            perk(3, StatHashes::RELOAD, 10),
+            perk(4, StatHashes::HANDLING, 20),
+            perk(5, StatHashes::RELOAD, 5),
+        ],
+    ];
+    let options: RollRankingOptions =
+        serde_json::from_value(serde_json::json!({"metric": "reloadTime", "top": 2})).unwrap();
+    let activity = Activity::default();
+    let enemy = Enemy::default();
+    let ranking = rank_rolls(&weapon, &columns, &options, &activity, &enemy).unwrap();
+    assert_eq!(ranking.combinations, 6);
+    assert_eq!(ranking.rolls.len(), 2);
+    assert_eq!(ranking.rolls[0].perks, [2, 3]);
+    assert_eq!(ranking.rolls[1].perks, [2, 5]);
+    assert!(ranking.rolls[0].score < ranking.rolls[1].score);
+    assert!(ranking.rolls[0].notes.starts_with("reload "));
+
+    let wishlist = ranking.to_wishlist(weapon.hash);
+    let lines: Vec<&str> = wishlist.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(&format!("dimwishlist:item={}&perks=2,3#notes:", weapon.hash)));
+
+    //an enhanced perk also gets a line with its base perk
+    if let Some((enhanced, base)) = crate::database::ENHANCE_PERK_MAPPING.first() {
+        let columns = vec![vec![perk(*enhanced, StatHashes::RELOAD, 0)]];
+        let ranking = rank_rolls(&weapon, &columns, &options, &activity, &enemy).unwrap();
+        let wishlist = ranking.to_wishlist(weapon.hash);
+        assert!(wishlist.contains(&format!("&perks={}#", enhanced)));
+        assert!(wishlist.contains(&format!("&perks={}#", base)));
+    }
+
+    assert!(rank_rolls(&weapon, &[vec![]], &options, &activity, &enemy).is_err());
+}
+
+#[test]
+fn test_ts_definitions() {
+    use crate::types::ts_types::{generate_definitions, DEFINITIONS_PATH};
+
+    let (generated, _) = generate_definitions();
+    if std::env::var("UPDATE_TS_DEFINITIONS").is_ok() {
+        std::fs::write(DEFINITIONS_PATH, &generated).unwrap();
+    }
+    let current = std::fs::read_to_string(DEFINITIONS_PATH).unwrap();
+    assert!(
+        current == generated,
+        "output_definitions/d2_calculation_api.d.ts is out of date, \
+         rerun the tests with UPDATE_TS_DEFINITIONS=1"
+    );
+}
+
+#[test]
+fn test_ts_serde_fields() {
+    use crate::activity::{combatant_scaling::CombatantScaling, damage_solver::DamageSolution};
+    use crate::formulas::FormulaOverrideReport;
+    use crate::perks::lib::ModifierResponseSummary;
+    use crate::perks::perk_options_handler::PerkOptionData;
+    use crate::types::js_types::{JsResillienceSummary, JsStat};
+    use crate::types::ts_types::{generate_definitions, TsBody, TsType};
+    use serde::Serialize;
+
+    let (_, declarations) = generate_definitions();
+    //every declared interface field is a key serde writes and the other way around
+    fn check<T: Serialize + TsType>(
+        declarations: &crate::types::ts_types::Declarations,
+        value: &T,
+    ) {
+        let name = T::ts_name();
+        let Some(TsBody::Interface(fields)) = declarations.get(&name).map(|x| &x.body) else {
+            panic!("{} isn't declared", name);
+        };
+        let mut declared: Vec<&str> = fields.iter().map(|x| x.0.as_str()).collect();
+        let value = serde_json::to_value(value).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(|x| x.as_str())
+            .collect();
+        declared.sort_unstable();
+        keys.sort_unstable();
+        assert_eq!(declared, keys, "{}", name);
+    }
+
+    setup_pulse();
+    let weapon = PERS_DATA.with(|perm_data| perm_data.borrow().weapon.clone());
+    check(&declarations, &weapon);
+    check(
+        &declarations,
+        &JsResillienceSummary::from(weapon.calc_ttk(0.0).remove(0)),
+    );
+    check(&declarations, &JsStat::default());
+    check(&declarations, &PerkOptionData::static_());
+    check(&declarations, &FormulaOverrideReport::default());
+    check(&declarations, &CombatantScaling::default());
+    check(&declarations, &crate::activity::presets::list_activity_presets()[0]);
+    check(
+        &declarations,
+        &DamageSolution {
+            base_damage: 1.0,
+            perk_multiplier: 1.0,
+            residuals: vec![],
+            rms_error: 0.0,
+        },
+    );
+    check(&declarations, &crate::reference::run_cases(&[]));
+    check(&declarations, &weapon.calc_stat_curve_set(false));
+    check(&declarations, &weapon.calc_stat_curves(false, false));
+    let comparison = crate::weapons::comparison::compare_weapons(
+        &[weapon.clone(), weapon.clone()],
+        &Default::default(),
+        &Default::default(),
+        false,
+        true,
+    )
+    .unwrap();
+    check(&declarations, &comparison.rows[0]);
+    check(&declarations, &comparison);
+    check(
+        &declarations,
+        &crate::weapons::weapon_constructor::WeaponConfig::default(),
+    );
+    check(
+        &declarations,
+        &crate::weapons::weapon_constructor::PerkConfig::default(),
+    );
+    let options: crate::weapons::roll_ranking::RollRankingOptions =
+        serde_json::from_value(serde_json::json!({"metric": "magSize"})).unwrap();
+    check(&declarations, &options);
+    let ranking = crate::weapons::roll_ranking::rank_rolls(
+        &weapon,
+        &[],
+        &options,
+        &Default::default(),
+        &Default::default(),
+    )
+    .unwrap();
+    check(&declarations, &ranking.rolls[0]);
+    check(&declarations, &ranking);
+    check(
+        &declarations,
+        &weapon.calc_group_damage(&Default::default(), 1.0),
+    );
+    let champion_mod = crate::enemies::champions::ChampionMod {
+        champion: crate::enemies::champions::ChampionType::Barrier,
+        weapon_type: 13,
+    };
+    check(&declarations, &champion_mod);
+    let encounter: crate::activity::encounter::Encounter = serde_json::from_value(serde_json::json!({
+        "health": 1e6,
+        "phaseLength": 10.0,
+        "immuneWindows": [{"start": 0.0, "duration": 1.0}]
+    }))
+    .unwrap();
+    check(&declarations, &encounter);
+    check(&declarations, &encounter.immune_windows[0]);
+    check(
+        &declarations,
+        &weapon.simulate_encounter(&Default::default(), &encounter, 1.0),
+    );
+    let player = crate::activity::Player {
+        class: crate::activity::PlayerClass::Titan,
+        ..Default::default()
+    };
+    check(&declarations, &player.armor);
+    check(&declarations, &player.calc_armor_stats());
+    let metrics = weapon.calc_dps_metrics(Default::default(), 1.0);
+    check(&declarations, &metrics.window_dps[0]);
+    check(&declarations, &metrics);
+    check(
+        &declarations,
+        &crate::enemies::champions::ChampionTtk {
+            champion: champion_mod.champion,
+            can_stun: true,
+            unstunned: None,
+            stunned: Some(1.0),
+        },
+    );
+    check(
+        &declarations,
+        &ModifierResponseSummary {
+            rmr: Some(Default::default()),
+            dmr: Some(Default::default()),
+            hmr: Some(Default::default()),
+            fmr: Some(Default::default()),
+            flmr: Some(Default::default()),
+            rsmr: Some(Default::default()),
+            mmr: Some(Default::default()),
+            imr: Some(Default::default()),
+            drmr: Some(Default::default()),
+            statbump: Some(HashMap::new()),
+        },
+    );
+}
@@ -338,6 +338,9 @@ pub struct JsScalarResponse {
     pub mag_size_scalar: f64,
     #[wasm_bindgen(js_name = "reserveSizeScalar", readonly)]
     pub reserve_size_scalar: f64,
+    ///pve damage multiplier against the current encounter's combatant, see getCombatantScaling
+    #[wasm_bindgen(js_name = "combatantScalar", readonly)]
+    pub combatant_scalar: f64,
 }
 
 #[derive(Debug, Clone)]
@@ -346,6 +349,9 @@ pub enum JsDifficultyOptions {
     NORMAL = 1,
     RAID = 2,
     MASTER = 3,
+    GRANDMASTER = 4,
+    CONTEST = 5,
+    LEGEND = 6,
 }
 impl From<JsDifficultyOptions> for DifficultyOptions {
     fn from(val: JsDifficultyOptions) -> Self {
@@ -353,6 +359,9 @@ impl From<JsDifficultyOptions> for DifficultyOptions {
             JsDifficultyOptions::NORMAL => DifficultyOptions::NORMAL,
             JsDifficultyOptions::RAID => DifficultyOptions::RAID,
             JsDifficultyOptions::MASTER => DifficultyOptions::MASTER,
+            JsDifficultyOptions::GRANDMASTER => DifficultyOptions::GRANDMASTER,
+            JsDifficultyOptions::CONTEST => DifficultyOptions::CONTEST,
+            JsDifficultyOptions::LEGEND => DifficultyOptions::LEGEND,
         }
     }
 }
@@ -1,3 +1,5 @@
 pub mod js_types;
 
 pub mod rs_types;
+#[cfg(test)]
+pub mod ts_types;
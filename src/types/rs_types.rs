@@ -1,8 +1,8 @@
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{enemies::EnemyType, perks::clamp};
+use crate::{activity::damage_calc::DifficultyOptions, enemies::EnemyType, perks::clamp};
 
 #[derive(Debug, Clone, Copy)]
 pub struct DataPointers {
@@ -17,7 +17,269 @@ pub struct DataPointers {
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct WeaponPath(pub u32, pub u32);
 
-#[derive(Debug, Clone, Default, Copy, Serialize)]
+#[derive(Debug, Clone, Copy)]
+pub struct ItemData {
+    pub hash: u32,
+    pub weapon_type: u8,
+    pub intrinsic_hash: u32,
+    pub ammo_type: u32,
+    pub damage_type: u32,
+    pub stat_group: u32,
+    pub investment_stats: &'static [(u32, i32)],
+    pub display_stats: &'static [(u32, i32)],
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ScaledStat {
+    pub stat_hash: u32,
+    pub maximum_value: i32,
+    ///(investment value, displayed weight) pairs from `displayInterpolation`
+    pub interpolation: &'static [(i32, i32)],
+}
+impl ScaledStat {
+    ///Converts an investment value into what the game shows,
+    ///same interpolation and banker's rounding the game and DIM use
+    pub fn interpolate(&self, investment: i32) -> i32 {
+        if self.interpolation.is_empty() {
+            return investment;
+        }
+        let value = investment.min(self.maximum_value);
+        //values outside the points clamp to the first or last weight, same as DIM
+        let (first_value, first_weight) = self.interpolation[0];
+        let (last_value, last_weight) = self.interpolation[self.interpolation.len() - 1];
+        if value <= first_value {
+            return first_weight;
+        }
+        if value >= last_value {
+            return last_weight;
+        }
+        let end = self
+            .interpolation
+            .iter()
+            .position(|(x, _)| *x > value)
+            .unwrap_or(self.interpolation.len() - 1);
+        let (start_value, start_weight) = self.interpolation[end - 1];
+        let (end_value, end_weight) = self.interpolation[end];
+        let t = (value - start_value) as f64 / (end_value - start_value) as f64;
+        bankers_round(start_weight as f64 + t * (end_weight - start_weight) as f64)
+    }
+}
+
+fn bankers_round(x: f64) -> i32 {
+    let floor = x.floor();
+    if x - floor == 0.5 {
+        if floor % 2.0 == 0.0 {
+            floor as i32
+        } else {
+            floor as i32 + 1
+        }
+    } else {
+        x.round() as i32
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct StatGroup {
+    pub hash: u32,
+    pub scaled_stats: &'static [ScaledStat],
+}
+impl StatGroup {
+    ///None if the group doesn't scale this stat
+    pub fn display_value(&self, stat_hash: u32, investment: i32) -> Option<i32> {
+        self.scaled_stats
+            .iter()
+            .find(|x| x.stat_hash == stat_hash)
+            .map(|x| x.interpolate(investment))
+    }
+}
+
+///Reserve curve from the "reserves" section of weapon_formulas.json, keyed by `AmmoFormula::reserve_id`.
+///Tables are sorted by their first value
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReserveFormula {
+    Constant {
+        value: f64,
+    },
+    ///`full_mag` is the (vpp, offset) pair used once the mag stat reaches 100
+    Linear {
+        vpp: f64,
+        offset: f64,
+        full_mag: Option<(f64, f64)>,
+    },
+    ///reserves scale with the rounded up mag size
+    PerMag {
+        vpp: f64,
+        offset: f64,
+    },
+    ///offset picked by rounded up mag size, max inventory is `offset * inv_mult`
+    MagTable {
+        offsets: Cow<'static, [(i32, f64)]>,
+        default: f64,
+        inv_mult: f64,
+        bonus: f64,
+    },
+    ///(inventory stat, reserves) for the exact stats a weapon can roll
+    Exact {
+        values: Cow<'static, [(i32, f64)]>,
+        default: f64,
+    },
+    ///(max inventory stat, reserves), the first step at or above the stat is used
+    Steps {
+        steps: Cow<'static, [(i32, f64)]>,
+        default: f64,
+    },
+    ///`base` plus an offset that grows with how far the unrounded mag size rounds up,
+    ///scaled by `1 + mag_stat_scale * mag stat / 100`, up to `inv_mult` times at 100 inventory
+    MagRounding {
+        base: f64,
+        offset: f64,
+        round_scale: f64,
+        mag_stat_scale: f64,
+        inv_mult: f64,
+    },
+    ///(max mag stat, offset), the first step at or above the mag stat is used,
+    ///max inventory is `offset * inv_mult`
+    MagStatSteps {
+        steps: Cow<'static, [(i32, f64)]>,
+        default: f64,
+        inv_mult: f64,
+    },
+}
+impl ReserveFormula {
+    pub fn solve(&self, mag_size: f64, mag_stat: i32, inv_stat: i32) -> f64 {
+        let inv = inv_stat as f64;
+        match self {
+            ReserveFormula::Constant { value } => *value,
+            ReserveFormula::Linear {
+                vpp,
+                offset,
+                full_mag,
+            } => match full_mag {
+                Some((vpp, offset)) if mag_stat >= 100 => vpp * inv + offset,
+                _ => vpp * inv + offset,
+            },
+            ReserveFormula::PerMag { vpp, offset } => mag_size.ceil() * (vpp * inv + offset),
+            ReserveFormula::MagTable {
+                offsets,
+                default,
+                inv_mult,
+                bonus,
+            } => {
+                let mag = mag_size.ceil() as i32;
+                let offset = offsets
+                    .iter()
+                    .find(|(size, _)| *size == mag)
+                    .map_or(*default, |(_, offset)| *offset);
+                let vpp = (offset * inv_mult - offset) / 100.0;
+                vpp * inv + offset + bonus
+            }
+            ReserveFormula::Exact { values, default } => values
+                .iter()
+                .find(|(stat, _)| *stat == inv_stat)
+                .map_or(*default, |(_, value)| *value),
+            ReserveFormula::Steps { steps, default } => steps
+                .iter()
+                .find(|(max_stat, _)| *max_stat >= inv_stat)
+                .map_or(*default, |(_, value)| *value),
+            ReserveFormula::MagRounding {
+                base,
+                offset,
+                round_scale,
+                mag_stat_scale,
+                inv_mult,
+            } => {
+                let round_amount = mag_size.ceil() - mag_size;
+                let offset = (offset + round_amount * round_scale)
+                    * (1.0 + mag_stat_scale * mag_stat as f64 / 100.0);
+                let size = base + offset;
+                (size * inv_mult - size) / 100.0 * inv + size
+            }
+            ReserveFormula::MagStatSteps {
+                steps,
+                default,
+                inv_mult,
+            } => {
+                let offset = steps
+                    .iter()
+                    .find(|(max_stat, _)| *max_stat >= mag_stat)
+                    .map_or(*default, |(_, offset)| *offset);
+                (offset * inv_mult - offset) / 100.0 * inv + offset
+            }
+        }
+    }
+}
+
+///Named encounter from build_resources/activity_presets.json
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityPreset {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub rpl: u32,
+    ///lowers the difficulty's power cap when it's below it
+    pub cap: i32,
+    pub difficulty: DifficultyOptions,
+    pub enemy_type: EnemyType,
+    pub health: f64,
+    ///the health is a placeholder until it's measured
+    pub estimate: bool,
+}
+
+///Sword frame data from the `sword_frames` section of weapon_formulas.json
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+pub struct SwordFrame {
+    pub combo: i32,
+    pub finisher_scale: f64,
+    pub heavy_scale: f64,
+    pub heavy_time: f64,
+    pub swing_speed: StatQuadraticFormula,
+    pub energy_regen: StatQuadraticFormula,
+    pub guard_resistance: StatQuadraticFormula,
+    pub guard_efficiency: StatQuadraticFormula,
+    pub timestamp: u64,
+}
+
+///Glaive frame data from the `glaive_frames` section of weapon_formulas.json
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+pub struct GlaiveFrame {
+    pub melee_scale: f64,
+    pub melee_time: f64,
+    pub melees_per_shot: i32,
+    pub shield_resistance_pve: f64,
+    pub shield_resistance_pvp: f64,
+    pub timestamp: u64,
+}
+
+///Bow frame data from the `bow_frames` section of weapon_formulas.json
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+pub struct BowFrame {
+    pub draw_time: StatQuadraticFormula,
+    pub perfect_draw: Option<StatQuadraticFormula>,
+    pub timestamp: u64,
+}
+
+///Blast data from the `blast_radii` section of weapon_formulas.json
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+pub struct BlastFormula {
+    pub radius: StatQuadraticFormula,
+    pub edge_damage: f64,
+    pub timestamp: u64,
+}
+
+///Power delta table for a difficulty tier from build_resources/difficulty_tables.json
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyTable {
+    pub id: u32,
+    pub name: Cow<'static, str>,
+    ///the table and cap are borrowed from a similar tier instead of measured
+    pub estimate: bool,
+    ///highest power delta that counts, negative when the activity locks power below recommended
+    pub cap: i32,
+    ///(power delta, damage multiplier) points sorted by power delta
+    pub table: Cow<'static, [(f64, f64)]>,
+}
+
+#[derive(Debug, Clone, Default, Copy, Serialize, PartialEq)]
 pub struct FiringData {
     pub damage: f64,
     pub crit_mult: f64,
@@ -31,7 +293,7 @@ pub struct FiringData {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Copy, Serialize)]
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
 pub struct DamageMods {
     pub pve: f64,
     pub minor: f64,
@@ -70,7 +332,7 @@ impl DamageMods {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
 pub struct RangeFormula {
     pub start: StatQuadraticFormula,
     pub end: StatQuadraticFormula,
@@ -81,7 +343,7 @@ pub struct RangeFormula {
 }
 
 //even if just linear use this
-#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
 pub struct StatQuadraticFormula {
     pub evpp: f64,
     pub vpp: f64,
@@ -98,14 +360,14 @@ impl StatQuadraticFormula {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
 pub struct ReloadFormula {
     pub reload_data: StatQuadraticFormula,
     pub ammo_percent: f64,
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
 pub struct HandlingFormula {
     pub ready: StatQuadraticFormula,
     pub stow: StatQuadraticFormula,
@@ -113,7 +375,7 @@ pub struct HandlingFormula {
     pub timestamp: u64,
 }
 
-#[derive(Debug, Clone, Copy, Default, Serialize)]
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
 pub struct AmmoFormula {
     pub mag: StatQuadraticFormula,
     pub round_to: i32,
@@ -142,6 +404,7 @@ pub struct HandlingResponse {
 #[derive(Debug, Clone, Default)]
 pub struct AmmoResponse {
     pub mag_size: i32,
+    ///every round the weapon carries, the loaded magazine included
     pub reserve_size: i32,
     pub timestamp: u64,
 }
@@ -167,6 +430,10 @@ pub struct DpsResponse {
     pub total_damage: f64,
     pub total_time: f64,
     pub total_shots: i32,
+    ///part of total_time spent reloading, or whatever stands in for it
+    pub reload_time: f64,
+    ///part of total_damage dealt without spending ammo, like glaive melees
+    pub melee_damage: f64,
 }
 impl DpsResponse {
     pub fn apply_rpl(&mut self, rpl: f64) {
@@ -177,6 +444,7 @@ impl DpsResponse {
             *damage *= rpl;
         }
         self.total_damage *= rpl;
+        self.melee_damage *= rpl;
     }
     pub fn get_dps_over_time(&self) -> Vec<(f64, f64)> {
         let dps_data = &self.time_damage_data;
@@ -190,6 +458,17 @@ impl DpsResponse {
         }
         dps_lst
     }
+    ///Damage landed in the first `_seconds` over `_seconds`, the weapon doing nothing once
+    ///its reserves are gone
+    pub fn get_dps_over_window(&self, _seconds: f64) -> f64 {
+        let damage: f64 = self
+            .time_damage_data
+            .iter()
+            .filter(|x| x.0 < _seconds)
+            .map(|x| x.1)
+            .sum();
+        damage / _seconds
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -0,0 +1,1111 @@
+//! Generates output_definitions/d2_calculation_api.d.ts.
+//!
+//! Exported functions are read from the `#[wasm_bindgen]` items in lib.rs and classes and
+//! enums from js_types.rs, so signatures can't drift from the Rust side. Anything crossing
+//! the boundary as a `JsValue` goes through serde, its TypeScript comes from the [`TsType`]
+//! of the type actually converted, listed in [`js_value_type`]. Struct declarations
+//! destructure every field of the Rust type so adding or removing one fails to compile
+//! until the declaration here is updated.
+//!
+//! `cargo test` fails when the checked in file is out of date, rerun it with
+//! `UPDATE_TS_DEFINITIONS=1` to regenerate.
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::{
+    activity::{
+        armor_stats::{ArmorStatSummary, ArmorStats},
+        combatant_scaling::CombatantScaling,
+        damage_calc::DifficultyOptions,
+        damage_solver::{DamageObservation, DamageSolution},
+        encounter::{Downtime, Encounter, EncounterResponse},
+        PlayerClass,
+    },
+    d2_enums::{AmmoType, DamageSource, DamageType, WeaponType},
+    enemies::{
+        champions::{ChampionMod, ChampionTtk, ChampionType},
+        EnemyType,
+    },
+    formulas::{FormulaOverrideReport, WeaponPathChange},
+    perks::{
+        lib::{
+            DamageModifierResponse, DamageResistModifierResponse, FiringModifierResponse,
+            FlinchModifierResponse, HandlingModifierResponse, InventoryModifierResponse,
+            MagazineModifierResponse, ModifierResponseSummary, RangeModifierResponse,
+            ReloadModifierResponse,
+        },
+        perk_options_handler::{PerkOptionData, PerkValueVariant},
+        Perk,
+    },
+    reference::{CaseResult, CorpusReport},
+    types::{
+        js_types::{JsBodyKillData, JsOptimalKillData, JsResillienceSummary, JsStat},
+        rs_types::{
+            ActivityPreset, AmmoFormula, DamageMods, FiringData, HandlingFormula, RangeFormula,
+            ReloadFormula, StatQuadraticFormula,
+        },
+    },
+    weapons::{
+        aoe_calc::GroupDamageResponse,
+        comparison::{Better, ComparisonRow, WeaponComparison},
+        dps_calc::{DpsMetrics, DpsWindow},
+        roll_ranking::{RankedRoll, RollMetric, RollRanking, RollRankingOptions},
+        stat_curves::{StatCurveSet, StatCurves},
+        weapon_constructor::{PerkConfig, WeaponConfig},
+        Stat, Weapon,
+    },
+};
+
+const LIB_SOURCE: &str = include_str!("../lib.rs");
+const JS_TYPES_SOURCE: &str = include_str!("js_types.rs");
+
+pub const DEFINITIONS_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/output_definitions/d2_calculation_api.d.ts"
+);
+
+///A named TypeScript declaration a serde type needs, in the order they're first used
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsDeclaration {
+    pub name: &'static str,
+    pub body: TsBody,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TsBody {
+    ///(name, type, optional)
+    Interface(Vec<(String, String, bool)>),
+    ///string literals
+    Union(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Declarations {
+    pub list: Vec<TsDeclaration>,
+}
+impl Declarations {
+    //true the first time a name is seen, recursive types stop here
+    fn begin(&mut self, name: &'static str) -> bool {
+        if self.list.iter().any(|x| x.name == name) {
+            return false;
+        }
+        self.list.push(TsDeclaration {
+            name,
+            body: TsBody::Union(Vec::new()),
+        });
+        true
+    }
+
+    fn finish(&mut self, name: &'static str, body: TsBody) {
+        if let Some(declaration) = self.list.iter_mut().find(|x| x.name == name) {
+            declaration.body = body;
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TsDeclaration> {
+        self.list.iter().find(|x| x.name == name)
+    }
+}
+
+pub trait TsType {
+    ///how the type is written where it's used
+    fn ts_name() -> String;
+    ///adds this type's declarations and those of everything it contains
+    fn declare(_out: &mut Declarations) {}
+    ///serde leaves None out, so these become optional fields
+    fn optional() -> bool {
+        false
+    }
+}
+
+macro_rules! ts_primitive {
+    ($name:literal: $($t:ty),*) => {
+        $(impl TsType for $t {
+            fn ts_name() -> String {
+                $name.to_owned()
+            }
+        })*
+    };
+}
+ts_primitive!("number": f64, f32, i32, u32, u8, u16, i64, u64, usize);
+ts_primitive!("boolean": bool);
+ts_primitive!("string": String, &'static str, Cow<'static, str>);
+
+impl<T: TsType> TsType for Vec<T> {
+    //None can't be left out of an array, it comes through as undefined
+    fn ts_name() -> String {
+        if T::optional() {
+            format!("Array<{} | undefined>", T::ts_name())
+        } else {
+            format!("Array<{}>", T::ts_name())
+        }
+    }
+    fn declare(out: &mut Declarations) {
+        T::declare(out)
+    }
+}
+
+impl<T: TsType> TsType for Option<T> {
+    fn ts_name() -> String {
+        T::ts_name()
+    }
+    fn declare(out: &mut Declarations) {
+        T::declare(out)
+    }
+    fn optional() -> bool {
+        true
+    }
+}
+
+//serde_wasm_bindgen turns maps into ES Maps
+impl<K: TsType, V: TsType> TsType for HashMap<K, V> {
+    fn ts_name() -> String {
+        format!("Map<{}, {}>", K::ts_name(), V::ts_name())
+    }
+    fn declare(out: &mut Declarations) {
+        K::declare(out);
+        V::declare(out);
+    }
+}
+
+impl<A: TsType, B: TsType> TsType for (A, B) {
+    fn ts_name() -> String {
+        format!("[{}, {}]", A::ts_name(), B::ts_name())
+    }
+    fn declare(out: &mut Declarations) {
+        A::declare(out);
+        B::declare(out);
+    }
+}
+
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+///`ts_interface!(Type as "Name", case { field: Type, ... } optional { ... } skipped { ... } renamed { field: "name" })`
+///where case is `snake_case` or `camelCase` like the type's serde `rename_all`. Fields
+///with serde defaults go under optional, `#[serde(skip)]` ones under skipped.
+macro_rules! ts_interface {
+    (@case snake_case $field:ident) => {
+        $field.to_owned()
+    };
+    (@case camelCase $field:ident) => {
+        camel_case($field)
+    };
+    (
+        $t:path as $name:literal, $case:ident { $($field:ident: $ft:ty),* $(,)? }
+        $(optional { $($opt:ident),* $(,)? })?
+        $(skipped { $($skip:ident),* $(,)? })?
+        $(renamed { $($from:ident: $to:literal),* $(,)? })?
+    ) => {
+        impl TsType for $t {
+            fn ts_name() -> String {
+                $name.to_owned()
+            }
+            fn declare(out: &mut Declarations) {
+                #[allow(dead_code)]
+                fn every_field_listed(x: &$t) {
+                    let $t { $($field: _,)* $($($skip: _,)*)? } = x;
+                }
+                if !out.begin($name) {
+                    return;
+                }
+                let optional: &[&str] = &[$($(stringify!($opt)),*)?];
+                let renamed: &[(&str, &str)] = &[$($((stringify!($from), $to)),*)?];
+                let fields = vec![$({
+                    let field = stringify!($field);
+                    let name = match renamed.iter().find(|x| x.0 == field) {
+                        Some((_, to)) => to.to_string(),
+                        None => ts_interface!(@case $case field),
+                    };
+                    let optional = optional.contains(&field) || <$ft as TsType>::optional();
+                    (name, <$ft as TsType>::ts_name(), optional)
+                }),*];
+                out.finish($name, TsBody::Interface(fields));
+                $(<$ft as TsType>::declare(out);)*
+            }
+        }
+    };
+}
+
+///String unions for enums serde writes out by variant name. The match makes a new
+///variant a compile error here
+macro_rules! ts_union {
+    ($t:ident as $name:literal [$($variant:ident),* $(,)?]) => {
+        impl TsType for $t {
+            fn ts_name() -> String {
+                $name.to_owned()
+            }
+            fn declare(out: &mut Declarations) {
+                #[allow(dead_code)]
+                fn every_variant_listed(x: &$t) {
+                    match x {
+                        $($t::$variant => (),)*
+                    }
+                }
+                if out.begin($name) {
+                    let variants = [$($t::$variant),*]
+                        .iter()
+                        .map(|x| serde_json::to_value(x).unwrap().as_str().unwrap().to_owned())
+                        .collect();
+                    out.finish($name, TsBody::Union(variants));
+                }
+            }
+        }
+    };
+}
+
+ts_union!(EnemyType as "EnemyTypeName" [MINOR, ELITE, MINIBOSS, BOSS, VEHICLE, ENCLAVE, PLAYER, CHAMPION]);
+ts_union!(DifficultyOptions as "DifficultyName" [NORMAL, RAID, MASTER, GRANDMASTER, CONTEST, LEGEND]);
+ts_union!(PerkValueVariant as "PerkValueVariant" [STATIC, TOGGLE, SLIDER, OPTIONS]);
+ts_union!(DamageType as "DamageTypeName" [ARC, VOID, SOLAR, STASIS, KINETIC, STRAND, UNKNOWN]);
+ts_union!(DamageSource as "DamageSourceName" [SNIPER, MELEE, EXPLOSION, ENVIRONMENTAL, UNKNOWN]);
+ts_union!(AmmoType as "AmmoTypeName" [PRIMARY, SPECIAL, HEAVY, UNKNOWN]);
+ts_union!(Better as "Better" [Higher, Lower, Neither]);
+ts_union!(ChampionType as "ChampionType" [Barrier, Overload, Unstoppable]);
+ts_union!(PlayerClass as "PlayerClass" [Unknown, Titan, Hunter, Warlock]);
+ts_union!(RollMetric as "RollMetric" [
+    ReloadTime, ReadyTime, AdsTime, Range, MagSize, ReserveSize, OptimalTtk, BodyTtk, Damage, Dps,
+]);
+ts_union!(WeaponType as "WeaponTypeName" [
+    AUTORIFLE, BOW, FUSIONRIFLE, GLAIVE, GRENADELAUNCHER, HANDCANNON, LINEARFUSIONRIFLE,
+    MACHINEGUN, PULSERIFLE, ROCKET, SCOUTRIFLE, SHOTGUN, SIDEARM, SNIPER, SUBMACHINEGUN, SWORD,
+    TRACERIFLE, UNKNOWN,
+]);
+
+//serde forms of the wasm classes, named apart from the classes themselves
+ts_interface!(JsStat as "StatValues", snake_case {
+    base_value: i32, part_value: i32, trait_value: i32
+} renamed { base_value: "baseValue", part_value: "partValue", trait_value: "traitValue" });
+ts_interface!(JsOptimalKillData as "OptimalTtk", snake_case {
+    headshots: i32, bodyshots: i32, time_taken: f64
+} renamed { time_taken: "timeTaken" });
+ts_interface!(JsBodyKillData as "BodyTtk", snake_case {
+    bodyshots: i32, time_taken: f64
+} renamed { time_taken: "timeTaken" });
+ts_interface!(JsResillienceSummary as "ResillienceTtkSummary", snake_case {
+    value: i32, body_ttk: JsBodyKillData, optimal_ttk: JsOptimalKillData
+} renamed { value: "resillienceValue", body_ttk: "bodyTtk", optimal_ttk: "optimalTtk" });
+
+ts_interface!(PerkOptionData as "PerkOptionData", snake_case {
+    stacks: (u32, u32), options: Vec<String>, option_type: PerkValueVariant
+} renamed { option_type: "optionType" });
+
+ts_interface!(WeaponPathChange as "WeaponPathChange", camelCase {
+    weapon_type_id: u32, hash: u32, components: Vec<String>
+});
+ts_interface!(FormulaOverrideReport as "FormulaOverrideReport", camelCase {
+    added: Vec<WeaponPathChange>,
+    changed: Vec<WeaponPathChange>,
+    unchanged: Vec<WeaponPathChange>,
+    changed_reserves: Vec<u32>,
+    changed_type_scalars: Vec<u32>,
+    changed_sword_frames: Vec<u32>,
+    changed_glaive_frames: Vec<u32>,
+    changed_blast_radii: Vec<u32>,
+    changed_cleave_radii: Vec<u32>,
+    changed_brick_sizes: Vec<u32>,
+    changed_bow_frames: Vec<u32>,
+    changed_bow_strings: Vec<u32>,
+    warnings: Vec<String>,
+});
+
+ts_interface!(CombatantScaling as "CombatantScaling", camelCase {
+    weapon_type: f64, exotic_primary: f64, kinetic: f64, weapon: f64
+});
+ts_interface!(ActivityPreset as "ActivityPreset", camelCase {
+    id: &'static str,
+    name: &'static str,
+    rpl: u32,
+    cap: i32,
+    difficulty: DifficultyOptions,
+    enemy_type: EnemyType,
+    health: f64,
+    estimate: bool,
+});
+ts_interface!(DamageObservation as "DamageObservation", camelCase {
+    damage: f64,
+    enemy_type: EnemyType,
+    rpl: u32,
+    power: u32,
+    difficulty: DifficultyOptions,
+    cap: Option<i32>,
+    buff: f64,
+    crit: bool,
+    perk_active: bool,
+} optional { difficulty, buff, crit, perk_active });
+ts_interface!(DamageSolution as "DamageSolution", camelCase {
+    base_damage: f64, perk_multiplier: f64, residuals: Vec<f64>, rms_error: f64
+});
+ts_interface!(CaseResult as "CaseResult", camelCase {
+    name: String,
+    kind: &'static str,
+    measured: f64,
+    predicted: Option<f64>,
+    error: Option<f64>,
+    tolerance: f64,
+    passed: bool,
+    message: Option<String>,
+});
+ts_interface!(CorpusReport as "CorpusReport", camelCase {
+    cases: Vec<CaseResult>, failures: usize
+});
+ts_interface!(StatCurves as "StatCurves", camelCase {
+    reload_time: Vec<f64>,
+    ammo_time: Vec<f64>,
+    ready_time: Vec<f64>,
+    stow_time: Vec<f64>,
+    ads_time: Vec<f64>,
+    hip_falloff_start: Vec<f64>,
+    hip_falloff_end: Vec<f64>,
+    ads_falloff_start: Vec<f64>,
+    ads_falloff_end: Vec<f64>,
+    mag_size: Vec<i32>,
+    reserve_size: Vec<i32>,
+});
+ts_interface!(StatCurveSet as "StatCurveSet", camelCase {
+    base: StatCurves, dynamic: StatCurves
+});
+ts_interface!(PerkConfig as "PerkConfig", camelCase {
+    hash: u32, value: u32, stat_buffs: HashMap<u32, i32>
+} optional { value, stat_buffs });
+ts_interface!(WeaponConfig as "WeaponConfig", camelCase {
+    hash: u32,
+    weapon_type: u8,
+    intrinsic_hash: u32,
+    ammo_type: u32,
+    damage_type: u32,
+    stats: HashMap<u32, i32>,
+    stat_group: Option<u32>,
+    perks: Vec<PerkConfig>,
+} optional { stats, perks });
+ts_interface!(ComparisonRow as "ComparisonRow", camelCase {
+    section: &'static str,
+    name: String,
+    values: Vec<Option<f64>>,
+    better: Better,
+    deltas: Vec<Option<f64>>,
+    percent_deltas: Vec<Option<f64>>,
+    winner: Option<usize>,
+});
+ts_interface!(WeaponComparison as "WeaponComparison", camelCase {
+    weapons: usize, rows: Vec<ComparisonRow>
+});
+ts_interface!(RollRankingOptions as "RollRankingOptions", camelCase {
+    metric: RollMetric, top: usize, pvp: bool, dynamic_traits: bool, resilience: i32
+} optional { top, pvp, dynamic_traits, resilience });
+ts_interface!(RankedRoll as "RankedRoll", camelCase {
+    perks: Vec<u32>, score: f64, notes: String
+});
+ts_interface!(RollRanking as "RollRanking", camelCase {
+    metric: RollMetric, combinations: usize, rolls: Vec<RankedRoll>
+});
+ts_interface!(GroupDamageResponse as "GroupDamageResponse", camelCase {
+    total_damage: f64,
+    targets_killed: usize,
+    clear_time: Option<f64>,
+    kill_times: Vec<Option<f64>>,
+    total_shots: i32,
+});
+ts_interface!(ChampionMod as "ChampionMod", camelCase {
+    champion: ChampionType, weapon_type: u8
+});
+ts_interface!(ChampionTtk as "ChampionTtk", camelCase {
+    champion: ChampionType, can_stun: bool, unstunned: Option<f64>, stunned: Option<f64>
+});
+ts_interface!(Downtime as "Downtime", camelCase {
+    start: f64, duration: f64
+});
+ts_interface!(Encounter as "Encounter", camelCase {
+    health: f64,
+    phase_length: f64,
+    immune_windows: Vec<Downtime>,
+    add_waves: Vec<Downtime>,
+    crit_exposure: f64,
+    ammo_refill: f64,
+} optional { immune_windows, add_waves, crit_exposure, ammo_refill });
+ts_interface!(EncounterResponse as "EncounterResponse", camelCase {
+    phase_damage: Vec<f64>, phases_to_kill: Option<usize>, kill_time: Option<f64>
+});
+ts_interface!(DpsWindow as "DpsWindow", camelCase {
+    seconds: f64, dps: f64
+});
+ts_interface!(DpsMetrics as "DpsMetrics", camelCase {
+    window_dps: Vec<DpsWindow>,
+    sustained_dps: f64,
+    damage_per_mag: f64,
+    damage_per_brick: Option<f64>,
+    damage_per_reserve: f64,
+    time_to_empty_mag: f64,
+    time_to_empty_reserves: f64,
+    reload_share: f64,
+});
+ts_interface!(ArmorStats as "ArmorStats", camelCase {
+    mobility: i32, resilience: i32, recovery: i32, discipline: i32, intellect: i32, strength: i32
+});
+ts_interface!(ArmorStatSummary as "ArmorStatSummary", camelCase {
+    grenade_cooldown: Option<f64>,
+    melee_cooldown: Option<f64>,
+    class_ability_cooldown: Option<f64>,
+    super_cooldown: Option<f64>,
+    health_regen_time: Option<f64>,
+    walk_speed: Option<f64>,
+    resilience_tier: i32,
+    pvp_health: f64,
+    damage_resistance: f64,
+});
+
+ts_interface!(ModifierResponseSummary as "ModifierResponseSummary", snake_case {
+    rmr: Option<RangeModifierResponse>,
+    dmr: Option<DamageModifierResponse>,
+    hmr: Option<HandlingModifierResponse>,
+    fmr: Option<FiringModifierResponse>,
+    flmr: Option<FlinchModifierResponse>,
+    rsmr: Option<ReloadModifierResponse>,
+    mmr: Option<MagazineModifierResponse>,
+    imr: Option<InventoryModifierResponse>,
+    drmr: Option<DamageResistModifierResponse>,
+    statbump: Option<HashMap<u32, i32>>,
+});
+ts_interface!(RangeModifierResponse as "RangeModifierResponse", snake_case {
+    range_stat_add: i32, range_all_scale: f64, range_hip_scale: f64, range_zoom_scale: f64
+});
+ts_interface!(DamageModifierResponse as "DamageModifierResponse", snake_case {
+    impact_dmg_scale: f64, explosive_dmg_scale: f64, crit_scale: f64
+});
+ts_interface!(HandlingModifierResponse as "HandlingModifierResponse", snake_case {
+    stat_add: i32,
+    stow_add: i32,
+    draw_add: i32,
+    ads_add: i32,
+    stow_scale: f64,
+    draw_scale: f64,
+    ads_scale: f64,
+});
+ts_interface!(FiringModifierResponse as "FiringModifierResponse", snake_case {
+    burst_delay_scale: f64, burst_delay_add: f64, inner_burst_scale: f64, burst_size_add: f64
+});
+ts_interface!(FlinchModifierResponse as "FlinchModifierResponse", snake_case {
+    flinch_scale: f64
+});
+ts_interface!(ReloadModifierResponse as "ReloadModifierResponse", snake_case {
+    reload_stat_add: i32, reload_time_scale: f64
+});
+ts_interface!(MagazineModifierResponse as "MagazineModifierResponse", snake_case {
+    magazine_stat_add: i32, magazine_scale: f64, magazine_add: f64
+});
+ts_interface!(InventoryModifierResponse as "InventoryModifierResponse", snake_case {
+    inv_stat_add: i32, inv_scale: f64, inv_add: i32
+});
+ts_interface!(DamageResistModifierResponse as "DamageResistModifierResponse", snake_case {
+    body_shot_resist: f64,
+    head_shot_resist: f64,
+    element: Option<DamageType>,
+    source: Option<DamageSource>,
+});
+
+ts_interface!(Weapon as "Weapon", snake_case {
+    hash: u32,
+    intrinsic_hash: u32,
+    perks: HashMap<u32, Perk>,
+    stats: HashMap<u32, Stat>,
+    investment_stats: HashMap<u32, i32>,
+    damage_mods: DamageMods,
+    firing_data: FiringData,
+    range_formula: RangeFormula,
+    ammo_formula: AmmoFormula,
+    handling_formula: HandlingFormula,
+    reload_formula: ReloadFormula,
+    weapon_type: WeaponType,
+    damage_type: DamageType,
+    ammo_type: AmmoType,
+} skipped { stat_group, perk_value_map });
+ts_interface!(Perk as "Perk", snake_case {
+    stat_buffs: HashMap<u32, i32>, enhanced: bool, value: u32, hash: u32, raw_hash: u32
+});
+ts_interface!(Stat as "WeaponStat", snake_case {
+    base_value: i32, part_value: i32, perk_value: i32
+});
+ts_interface!(DamageMods as "DamageMods", snake_case {
+    pve: f64,
+    minor: f64,
+    elite: f64,
+    miniboss: f64,
+    champion: f64,
+    boss: f64,
+    vehicle: f64,
+    timestamp: u64,
+});
+ts_interface!(FiringData as "FiringData", snake_case {
+    damage: f64,
+    crit_mult: f64,
+    pve_damage: f64,
+    pve_crit_mult: f64,
+    burst_delay: f64,
+    inner_burst_delay: f64,
+    burst_size: i32,
+    one_ammo: bool,
+    charge: bool,
+    timestamp: u64,
+});
+ts_interface!(StatQuadraticFormula as "StatQuadraticFormula", snake_case {
+    evpp: f64, vpp: f64, offset: f64
+});
+ts_interface!(RangeFormula as "RangeFormula", snake_case {
+    start: StatQuadraticFormula,
+    end: StatQuadraticFormula,
+    floor_percent: f64,
+    pve_floor_percent: Option<f64>,
+    fusion: bool,
+    timestamp: u64,
+});
+ts_interface!(ReloadFormula as "ReloadFormula", snake_case {
+    reload_data: StatQuadraticFormula, ammo_percent: f64, timestamp: u64
+});
+ts_interface!(HandlingFormula as "HandlingFormula", snake_case {
+    ready: StatQuadraticFormula, stow: StatQuadraticFormula, ads: StatQuadraticFormula, timestamp: u64
+});
+ts_interface!(AmmoFormula as "AmmoFormula", snake_case {
+    mag: StatQuadraticFormula, round_to: i32, reserve_id: u32, timestamp: u64
+});
+
+type Declare = fn(&mut Declarations) -> String;
+
+fn ts<T: TsType>(out: &mut Declarations) -> String {
+    T::declare(out);
+    T::ts_name()
+}
+
+///The serde type behind every `JsValue` that isn't an error, by (js name, parameter name
+///or "return"). Getters on classes use "Class.getter" as the js name
+pub fn js_value_type(js_name: &str, slot: &str) -> Option<Declare> {
+    Some(match (js_name, slot) {
+        ("stringifyWeapon", "return") => ts::<Weapon>,
+        ("loadFormulaOverrides", "return") => ts::<FormulaOverrideReport>,
+        ("getStats", "return") => ts::<HashMap<u32, JsStat>>,
+        ("setStats" | "setInvestmentStats" | "addTrait", "_stats") => ts::<HashMap<u32, i32>>,
+        ("getTraitOptions", "return") => ts::<HashMap<u32, PerkOptionData>>,
+        ("getWeaponTtk", "return") => ts::<Vec<JsResillienceSummary>>,
+        ("getGroupDamage", "return") => ts::<GroupDamageResponse>,
+        ("setChampionMods", "_mods") => ts::<Vec<ChampionMod>>,
+        ("getChampionTtk", "return") => ts::<Vec<ChampionTtk>>,
+        ("loadChampionProfiles", "return") => ts::<Vec<ChampionType>>,
+        ("simulateEncounter", "_encounter") => ts::<Encounter>,
+        ("simulateEncounter", "return") => ts::<EncounterResponse>,
+        ("getDpsMetrics", "return") => ts::<DpsMetrics>,
+        ("setArmorStats", "_class") => ts::<PlayerClass>,
+        ("setArmorStats", "_stats") => ts::<ArmorStats>,
+        ("getArmorStats", "return") => ts::<ArmorStatSummary>,
+        ("getMiscData", "return") => ts::<HashMap<String, f64>>,
+        ("getModifierResponseSummary", "return") => ts::<HashMap<u32, ModifierResponseSummary>>,
+        ("getCombatantScaling", "return") => ts::<CombatantScaling>,
+        ("runReferenceCorpus", "return") => ts::<CorpusReport>,
+        ("solveDamage", "_observations") => ts::<Vec<DamageObservation>>,
+        ("solveDamage", "return") => ts::<DamageSolution>,
+        ("getStatCurves", "return") => ts::<StatCurveSet>,
+        ("compareWeapons", "_weapons") => ts::<Vec<WeaponConfig>>,
+        ("compareWeapons", "return") => ts::<WeaponComparison>,
+        ("rankRolls" | "exportWishlist", "_columns") => ts::<Vec<Vec<PerkConfig>>>,
+        ("rankRolls" | "exportWishlist", "_options") => ts::<RollRankingOptions>,
+        ("rankRolls", "return") => ts::<RollRanking>,
+        ("getActivityPresets", "return") => ts::<Vec<ActivityPreset>>,
+        ("DpsResponse.timeDamageData", "return") => ts::<Vec<(f64, f64)>>,
+        ("DpsResponse.dpsPerMag", "return") => ts::<Vec<f64>>,
+        _ => return None,
+    })
+}
+
+//---------------SOURCE PARSING---------------//
+
+#[derive(Debug, Clone, Default)]
+struct Item {
+    docs: Vec<String>,
+    attrs: Vec<String>,
+    //everything from `pub` up to the opening brace, or the whole item for fields
+    header: String,
+}
+
+fn attr_value<'a>(attr: &'a str, key: &str) -> Option<&'a str> {
+    let start = attr.find(&format!("{} = \"", key))? + key.len() + 4;
+    let end = attr[start..].find('"')? + start;
+    Some(&attr[start..end])
+}
+
+fn attr_flag(attr: &str, flag: &str) -> bool {
+    attr.trim_start_matches("#[wasm_bindgen(")
+        .trim_end_matches(")]")
+        .split(',')
+        .any(|x| x.trim() == flag)
+}
+
+fn wasm_attr(item: &Item) -> Option<&str> {
+    item.attrs
+        .iter()
+        .map(|x| x.as_str())
+        .find(|x| x.starts_with("#[wasm_bindgen"))
+}
+
+//splits on commas outside of brackets
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in text.chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                out.push(current.trim().to_owned());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        out.push(current.trim().to_owned());
+    }
+    out
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find("//") {
+        Some(index) if !line.trim_start().starts_with("///") => &line[..index],
+        _ => line,
+    }
+}
+
+///Top level `pub fn`s with a `#[wasm_bindgen(js_name = ...)]`
+fn parse_functions(source: &str) -> Vec<Item> {
+    let mut items = Vec::new();
+    let mut pending = Item::default();
+    let mut lines = source.lines();
+    while let Some(line) = lines.next() {
+        if let Some(doc) = line.strip_prefix("///") {
+            pending.docs.push(doc.to_owned());
+        } else if line.starts_with("#[") {
+            pending.attrs.push(line.to_owned());
+        } else if line.starts_with("pub fn ") {
+            let mut header = String::new();
+            let mut current = line;
+            loop {
+                header.push_str(strip_comment(current));
+                header.push(' ');
+                if current.trim_end().ends_with('{') || current.trim_end().ends_with(';') {
+                    break;
+                }
+                match lines.next() {
+                    Some(next) => current = next,
+                    None => break,
+                }
+            }
+            pending.header = header;
+            let item = std::mem::take(&mut pending);
+            if wasm_attr(&item).map_or(false, |x| attr_value(x, "js_name").is_some()) {
+                items.push(item);
+            }
+        } else {
+            pending = Item::default();
+        }
+    }
+    items
+}
+
+struct JsClass {
+    name: String,
+    inspectable: bool,
+    docs: Vec<String>,
+    //(docs, name, rust type, readonly)
+    fields: Vec<(Vec<String>, String, String, bool)>,
+    //(docs, js name, rendered signature)
+    methods: Vec<(Vec<String>, String, String)>,
+}
+
+struct JsEnum {
+    name: String,
+    docs: Vec<String>,
+    variants: Vec<(String, Option<String>)>,
+}
+
+struct JsTypes {
+    //rust ident to js name
+    names: HashMap<String, String>,
+    classes: Vec<JsClass>,
+    enums: Vec<JsEnum>,
+}
+
+fn parse_js_types(source: &str) -> JsTypes {
+    let mut out = JsTypes {
+        names: HashMap::new(),
+        classes: Vec::new(),
+        enums: Vec::new(),
+    };
+    let mut pending = Item::default();
+    let mut lines = source.lines().peekable();
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if let Some(doc) = trimmed.strip_prefix("///") {
+            pending.docs.push(doc.to_owned());
+            continue;
+        }
+        if trimmed.starts_with("#[") {
+            pending.attrs.push(trimmed.to_owned());
+            continue;
+        }
+        let item = std::mem::take(&mut pending);
+        let Some(attr) = wasm_attr(&item).map(|x| x.to_owned()) else {
+            continue;
+        };
+        if let Some(rest) = line.strip_prefix("pub struct ") {
+            let ident = rest.trim_end_matches('{').trim().to_owned();
+            let name = attr_value(&attr, "js_name").unwrap_or(&ident).to_owned();
+            let mut class = JsClass {
+                name: name.clone(),
+                inspectable: attr_flag(&attr, "inspectable"),
+                docs: item.docs,
+                fields: Vec::new(),
+                methods: Vec::new(),
+            };
+            let mut field = Item::default();
+            for line in lines.by_ref() {
+                let trimmed = line.trim();
+                if trimmed == "}" {
+                    break;
+                } else if let Some(doc) = trimmed.strip_prefix("///") {
+                    field.docs.push(doc.to_owned());
+                } else if trimmed.starts_with("#[") {
+                    field.attrs.push(trimmed.to_owned());
+                } else if let Some(rest) = trimmed.strip_prefix("pub ") {
+                    let field_item = std::mem::take(&mut field);
+                    let (ident, rust_type) = rest.trim_end_matches(',').split_once(": ").unwrap();
+                    let field_attr = wasm_attr(&field_item).unwrap_or("").to_owned();
+                    if attr_flag(&field_attr, "skip") {
+                        continue;
+                    }
+                    class.fields.push((
+                        field_item.docs,
+                        attr_value(&field_attr, "js_name")
+                            .unwrap_or(ident)
+                            .to_owned(),
+                        rust_type.to_owned(),
+                        attr_flag(&field_attr, "readonly"),
+                    ));
+                }
+            }
+            out.names.insert(ident, name);
+            out.classes.push(class);
+        } else if let Some(rest) = line.strip_prefix("pub enum ") {
+            let ident = rest.trim_end_matches('{').trim().to_owned();
+            let name = attr_value(&attr, "js_name").unwrap_or(&ident).to_owned();
+            let mut variants = Vec::new();
+            for line in lines.by_ref() {
+                let trimmed = line.trim().trim_end_matches(',');
+                if trimmed == "}" {
+                    break;
+                }
+                match trimmed.split_once(" = ") {
+                    Some((variant, value)) => {
+                        variants.push((variant.to_owned(), Some(value.to_owned())))
+                    }
+                    None => variants.push((trimmed.to_owned(), None)),
+                }
+            }
+            out.names.insert(ident, name.clone());
+            out.enums.push(JsEnum {
+                name,
+                docs: item.docs,
+                variants,
+            });
+        } else if line.starts_with("impl ") {
+            let Some(class_name) = attr_value(&attr, "js_class").map(|x| x.to_owned()) else {
+                continue;
+            };
+            let mut method = Item::default();
+            for line in lines.by_ref() {
+                let trimmed = line.trim();
+                if line == "}" {
+                    break;
+                } else if let Some(doc) = trimmed.strip_prefix("///") {
+                    method.docs.push(doc.to_owned());
+                } else if trimmed.starts_with("#[") {
+                    method.attrs.push(trimmed.to_owned());
+                } else if trimmed.starts_with("pub fn ") {
+                    method.header = trimmed.to_owned();
+                    out.classes
+                        .iter_mut()
+                        .find(|x| x.name == class_name)
+                        .expect("js_class impl before its struct")
+                        .methods
+                        .push(render_method(&class_name, std::mem::take(&mut method)));
+                }
+            }
+        }
+    }
+    out
+}
+
+fn render_method(class_name: &str, item: Item) -> (Vec<String>, String, String) {
+    let attr = wasm_attr(&item).unwrap_or("");
+    let ident = item.header["pub fn ".len()..].split('(').next().unwrap();
+    let name = attr_value(attr, "js_name").unwrap_or(ident).to_owned();
+    let rust_return = item
+        .header
+        .split_once("->")
+        .map_or("()", |x| x.1.trim_end_matches('{').trim());
+    let js_name = format!("{}.{}", class_name, name);
+    let signature = if attr_flag(attr, "getter") {
+        format!(
+            "readonly {}: {}",
+            name,
+            ts_from_rust(&js_name, "return", rust_return)
+        )
+    } else {
+        format!(
+            "{}(): {}",
+            name,
+            ts_from_rust(&js_name, "return", rust_return)
+        )
+    };
+    (item.docs, name, signature)
+}
+
+thread_local! {
+    static JS_NAMES: std::cell::RefCell<HashMap<String, String>> = Default::default();
+    static DECLARATIONS: std::cell::RefCell<Declarations> = Default::default();
+}
+
+///TypeScript for a Rust type written in a wasm_bindgen signature, the way wasm_bindgen
+///converts it
+fn ts_from_rust(js_name: &str, slot: &str, rust: &str) -> String {
+    let rust = rust.trim();
+    if let Some(inner) = rust
+        .strip_prefix("Result<")
+        .and_then(|x| x.strip_suffix('>'))
+    {
+        let ok = split_top_level(inner)[0].clone();
+        return ts_from_rust(js_name, slot, &ok);
+    }
+    if let Some(inner) = rust
+        .strip_prefix("Option<")
+        .and_then(|x| x.strip_suffix('>'))
+    {
+        return format!("{} | undefined", ts_from_rust(js_name, slot, inner));
+    }
+    match rust {
+        "()" => "void".to_owned(),
+        "f64" | "f32" | "i32" | "u32" | "u8" | "u16" | "i16" | "i8" | "usize" | "isize" => {
+            "number".to_owned()
+        }
+        "u64" | "i64" => "bigint".to_owned(),
+        "bool" => "boolean".to_owned(),
+        "String" | "&str" | "&'static str" => "string".to_owned(),
+        "Vec<u32>" => "Uint32Array".to_owned(),
+        "Vec<f64>" => "Float64Array".to_owned(),
+        "JsValue" => {
+            let declare = js_value_type(js_name, slot).unwrap_or_else(|| {
+                panic!(
+                    "no TsType for the JsValue at {} {}, add it to js_value_type",
+                    js_name, slot
+                )
+            });
+            DECLARATIONS.with(|out| declare(&mut out.borrow_mut()))
+        }
+        _ => JS_NAMES
+            .with(|x| x.borrow().get(rust).cloned())
+            .unwrap_or_else(|| panic!("no TypeScript for {} in {} {}", rust, js_name, slot)),
+    }
+}
+
+fn render_docs(out: &mut String, indent: &str, docs: &[String], tags: &[String]) {
+    out.push_str(&format!("{}/**\n", indent));
+    for line in docs.iter().chain(tags) {
+        out.push_str(&format!("{}*{}\n", indent, line));
+    }
+    out.push_str(&format!("{}*/\n", indent));
+}
+
+fn render_function(out: &mut String, item: &Item) {
+    let js_name = attr_value(wasm_attr(item).unwrap(), "js_name").unwrap();
+    let header = item.header.trim();
+    let open = header.find('(').unwrap();
+    let mut depth = 0;
+    let close = open
+        + header[open..]
+            .find(|c| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                depth == 0
+            })
+            .unwrap();
+    let params: Vec<(String, String)> = split_top_level(&header[open + 1..close])
+        .iter()
+        .map(|x| {
+            let (name, rust) = x.split_once(':').unwrap();
+            let name = name.trim().trim_start_matches("mut ").to_owned();
+            (name.clone(), ts_from_rust(js_name, &name, rust))
+        })
+        .collect();
+    let rust_return = header[close + 1..]
+        .trim()
+        .trim_end_matches('{')
+        .trim()
+        .strip_prefix("->")
+        .unwrap_or("()");
+    let returns = ts_from_rust(js_name, "return", rust_return);
+
+    //trailing optional parameters can be left out
+    let trailing_optional = params
+        .iter()
+        .rev()
+        .take_while(|x| x.1.ends_with(" | undefined"))
+        .count();
+    let mut tags: Vec<String> = params
+        .iter()
+        .map(|(name, ts)| format!(" @param {{{}}} {}", ts, name))
+        .collect();
+    if returns != "void" {
+        tags.push(format!(" @returns {{{}}}", returns));
+    }
+    render_docs(out, "", &item.docs, &tags);
+    let rendered: Vec<String> = params
+        .iter()
+        .enumerate()
+        .map(|(index, (name, ts))| {
+            if index >= params.len() - trailing_optional {
+                format!("{}?: {}", name, ts.trim_end_matches(" | undefined"))
+            } else {
+                format!("{}: {}", name, ts)
+            }
+        })
+        .collect();
+    out.push_str(&format!(
+        "export function {}({}): {};\n",
+        js_name,
+        rendered.join(", "),
+        returns
+    ));
+}
+
+const FOOTER: &str = "
+export type InitInput = RequestInfo | URL | Response | BufferSource | WebAssembly.Module;
+
+export interface InitOutput {
+  readonly memory: WebAssembly.Memory;
+}
+
+export type SyncInitInput = BufferSource | WebAssembly.Module;
+/**
+* Instantiates the given `module`, which can either be bytes or
+* a precompiled `WebAssembly.Module`.
+*
+* @param {SyncInitInput} module
+*
+* @returns {InitOutput}
+*/
+export function initSync(module: SyncInitInput): InitOutput;
+
+/**
+* If `module_or_path` is {RequestInfo} or {URL}, makes a request and
+* for everything else, calls `WebAssembly.instantiate` directly.
+*
+* @param {InitInput | Promise<InitInput>} module_or_path
+*
+* @returns {Promise<InitOutput>}
+*/
+export default function init(module_or_path?: InitInput | Promise<InitInput>): Promise<InitOutput>;
+";
+
+///Renders the whole d.ts, also returns the serde declarations that went into it
+pub fn generate_definitions() -> (String, Declarations) {
+    let js_types = parse_js_types(JS_TYPES_SOURCE);
+    JS_NAMES.with(|x| *x.borrow_mut() = js_types.names.clone());
+    DECLARATIONS.with(|x| *x.borrow_mut() = Declarations::default());
+
+    let mut out =
+        String::from("// generated by `UPDATE_TS_DEFINITIONS=1 cargo test`, do not edit\n");
+    for item in parse_functions(LIB_SOURCE) {
+        render_function(&mut out, &item);
+    }
+    for js_enum in &js_types.enums {
+        render_docs(&mut out, "", &js_enum.docs, &[]);
+        out.push_str(&format!("export enum {} {{\n", js_enum.name));
+        for (index, (variant, value)) in js_enum.variants.iter().enumerate() {
+            let value = value.clone().unwrap_or_else(|| index.to_string());
+            out.push_str(&format!("  {} = {},\n", variant, value));
+        }
+        out.push_str("}\n");
+    }
+    for class in &js_types.classes {
+        render_docs(&mut out, "", &class.docs, &[]);
+        out.push_str(&format!(
+            "export class {} {{\n  free(): void;\n",
+            class.name
+        ));
+        let has_method = |name: &str| class.methods.iter().any(|x| x.1 == name);
+        if class.inspectable && !has_method("toJSON") {
+            out.push_str("  toJSON(): Object;\n");
+        }
+        if class.inspectable && !has_method("toString") {
+            out.push_str("  toString(): string;\n");
+        }
+        let mut members: Vec<(&Vec<String>, &str, String)> = class
+            .fields
+            .iter()
+            .map(|(docs, name, rust, readonly)| {
+                let ts = ts_from_rust(&class.name, name, rust);
+                let prefix = if *readonly { "readonly " } else { "" };
+                (docs, name.as_str(), format!("{}{}: {}", prefix, name, ts))
+            })
+            .collect();
+        members.extend(
+            class
+                .methods
+                .iter()
+                .map(|(docs, name, signature)| (docs, name.as_str(), signature.clone())),
+        );
+        for (docs, _, signature) in members {
+            render_docs(&mut out, "  ", docs, &[]);
+            out.push_str(&format!("  {};\n", signature));
+        }
+        out.push_str("}\n");
+    }
+
+    let declarations = DECLARATIONS.with(|x| std::mem::take(&mut *x.borrow_mut()));
+    for declaration in &declarations.list {
+        match &declaration.body {
+            TsBody::Interface(fields) => {
+                out.push_str(&format!("export interface {} {{\n", declaration.name));
+                for (name, ts, optional) in fields {
+                    let optional = if *optional { "?" } else { "" };
+                    out.push_str(&format!("  {}{}: {};\n", name, optional, ts));
+                }
+                out.push_str("}\n");
+            }
+            TsBody::Union(variants) => {
+                let variants: Vec<String> = variants.iter().map(|x| format!("\"{}\"", x)).collect();
+                out.push_str(&format!(
+                    "export type {} = {};\n",
+                    declaration.name,
+                    variants.join(" | ")
+                ));
+            }
+        }
+    }
+    out.push_str(FOOTER);
+    (out, declarations)
+}
@@ -0,0 +1,278 @@
+//! Damage against a group of targets: blast radius, falloff and spreading extra damage.
+//!
+//! Impact damage only hits the target being shot. Explosions hit everything within the blast
+//! radius, losing damage towards the edge, and extra damage from perks spreads according to
+//! its [`ExtraDamageSpread`]. Damage past a target's remaining health is wasted, so the total
+//! is what the group actually lost. Blast radius and falloff come from the `blast_radii`
+//! section of weapon_formulas.json, see [`get_blast_formula`], and cleave radii from its
+//! `cleave_radii` section. The shipped values are unmeasured estimates, without blast data an
+//! explosion only hits the target being shot.
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::{
+    dps_calc::{calc_refund, ExtraDamageBuffInfo},
+    Stat, Weapon,
+};
+use crate::{
+    activity::combatant_scaling::get_combatant_scaling,
+    d2_enums::{Seconds, StatHashes},
+    enemies::TargetGroup,
+    formulas::get_blast_formula,
+    perks::{
+        get_dmg_modifier, get_extra_damage, get_firing_modifier, get_refund_modifier,
+        lib::ExtraDamageSpread,
+    },
+};
+
+///keeps the simulation finite for weapons that refund ammo
+const MAX_SHOTS: i32 = 500;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupDamageResponse {
+    ///damage the group actually took, overkill isn't counted
+    pub total_damage: f64,
+    pub targets_killed: usize,
+    ///when the last target died, None if the ammo ran out first
+    pub clear_time: Option<Seconds>,
+    ///when each target died in the order they stand, None for survivors
+    pub kill_times: Vec<Option<Seconds>>,
+    pub total_shots: i32,
+}
+
+struct GroupHealth {
+    health: Vec<f64>,
+    kill_times: Vec<Option<Seconds>>,
+    total_damage: f64,
+}
+impl GroupHealth {
+    fn alive(&self, _target: usize) -> bool {
+        self.health[_target] > 0.0
+    }
+
+    fn hit(&mut self, _target: usize, _damage: f64, _landed_at: Seconds) {
+        if !self.alive(_target) {
+            return;
+        }
+        let dealt = _damage.min(self.health[_target]);
+        self.health[_target] -= dealt;
+        self.total_damage += dealt;
+        if !self.alive(_target) {
+            self.kill_times[_target] = Some(_landed_at);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlastProfile {
+    ///meters an explosion reaches
+    pub radius: f64,
+    ///explosion damage at the very edge of the blast, rising linearly to full at the center
+    pub edge_damage: f64,
+}
+impl BlastProfile {
+    ///Damage scale of an explosion `_offset` meters from its center
+    pub fn falloff(&self, _offset: f64) -> f64 {
+        if _offset <= 0.0 {
+            return 1.0;
+        }
+        if _offset > self.radius {
+            return 0.0;
+        }
+        1.0 - (1.0 - self.edge_damage) * _offset / self.radius
+    }
+}
+
+//living targets an extra damage response reaches from the target being shot
+fn spread_targets(
+    _group: &TargetGroup,
+    _health: &GroupHealth,
+    _aimed: usize,
+    _spread: ExtraDamageSpread,
+) -> Vec<usize> {
+    let alive = (0.._group.count).filter(|x| _health.alive(*x));
+    match _spread {
+        ExtraDamageSpread::Single => vec![_aimed],
+        ExtraDamageSpread::Cleave { radius } => alive
+            .filter(|x| _group.offset(_aimed, *x) <= radius)
+            .collect(),
+    }
+}
+
+impl Weapon {
+    ///None for weapons that don't explode or whose weapon type has no blast data
+    pub fn calc_blast_profile(&self) -> Option<BlastProfile> {
+        if self.get_damage_profile(false).explosion_dmg <= 0.0 {
+            return None;
+        }
+        let formula = get_blast_formula(self.weapon_type as u32)?;
+        let blast_radius = self
+            .stats
+            .get(&StatHashes::BLAST_RADIUS.into())
+            .unwrap_or(&Stat::new())
+            .perk_val();
+        Some(BlastProfile {
+            radius: formula.radius.solve_at_i(blast_radius).max(0.0),
+            edge_damage: formula.edge_damage,
+        })
+    }
+}
+
+///Shoots the nearest living target of the group until everyone is dead or the ammo runs out
+pub fn calc_group_damage(
+    weapon: &Weapon,
+    _group: &TargetGroup,
+    _pl_dmg_mult: f64,
+) -> GroupDamageResponse {
+    let mut pers_calc_data: HashMap<String, f64> = HashMap::new();
+    let perks = weapon.list_perks();
+    let damage_profile = weapon.get_damage_profile(false);
+    let combatant_mult = get_combatant_scaling(weapon, _group.enemy.type_).total();
+    let blast = weapon.calc_blast_profile();
+    let base_mag = weapon.calc_ammo_sizes(None, None, false).mag_size;
+    let ammo = weapon.calc_ammo_sizes(
+        Some(weapon.static_calc_input()),
+        Some(&mut pers_calc_data),
+        false,
+    );
+    //the loaded magazine comes out of the reserves like in complex_dps_calc
+    let mut mag = ammo.mag_size.min(ammo.reserve_size);
+    let mut reserve = ammo.reserve_size - mag;
+    let burst_size = weapon.firing_data.burst_size.max(1);
+
+    let mut group = GroupHealth {
+        health: vec![_group.enemy.health; _group.count],
+        kill_times: vec![None; _group.count],
+        total_damage: 0.0,
+    };
+    let mut total_time = 0.0_f64;
+    let mut shots = 0_i32;
+
+    while mag > 0 && shots < MAX_SHOTS {
+        let Some(aimed) = (0.._group.count).find(|x| group.alive(*x)) else {
+            break;
+        };
+        let mut calc_input = weapon.sparse_calc_input(shots, total_time);
+        calc_input.enemy_type = &_group.enemy.type_;
+        calc_input.base_mag = base_mag as f64;
+        calc_input.curr_mag = mag as f64;
+        calc_input.reserves_left = reserve as f64;
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+
+        total_time += if let Some(charge) = weapon.calc_charge_profile(&firing_mods) {
+            charge.delay_before(shots as f64)
+        } else if shots == 0 {
+            0.0
+        } else if shots % burst_size > 0 {
+            weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale
+        } else if let Some(sword) = weapon.calc_sword_profile(&firing_mods) {
+            sword.light_time
+        } else {
+            (weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
+                * firing_mods.burst_delay_scale
+        };
+
+        let scale = _pl_dmg_mult * combatant_mult * weapon.damage_mods.pve;
+        let crit = damage_profile.crit_mult * dmg_mods.crit_scale;
+        let impact = damage_profile.impact_dmg * dmg_mods.impact_dmg_scale * crit * scale;
+        let explosion = damage_profile.explosion_dmg * dmg_mods.explosive_dmg_scale * scale;
+        let landed_at = total_time
+            + weapon.calc_travel_time(
+                _group.enemy.distance,
+                Some(calc_input.clone()),
+                false,
+                Some(&mut pers_calc_data),
+            );
+        //who the explosion reaches is decided before the impact kills anyone
+        let in_blast: Vec<(usize, f64)> = match blast {
+            Some(blast) => (0.._group.count)
+                .filter(|x| group.alive(*x))
+                .map(|x| (x, blast.falloff(_group.offset(aimed, x))))
+                .filter(|x| x.1 > 0.0)
+                .collect(),
+            None => vec![(aimed, 1.0)],
+        };
+        group.hit(aimed, impact, landed_at);
+        for (target, falloff) in in_blast {
+            group.hit(
+                target,
+                explosion * falloff,
+                landed_at + damage_profile.damage_delay,
+            );
+        }
+
+        if !weapon.firing_data.one_ammo || shots % burst_size == 0 {
+            mag -= 1;
+        }
+        shots += 1;
+        let refunds = get_refund_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let refund = calc_refund(shots, refunds);
+        mag += refund.0;
+        reserve += refund.1;
+
+        let buffs = ExtraDamageBuffInfo {
+            pl_buff: _pl_dmg_mult,
+            impact_buff: dmg_mods.impact_dmg_scale,
+            explosive_buff: dmg_mods.explosive_dmg_scale,
+            pve_buff: weapon.damage_mods.pve,
+            crit_buff: crit,
+            combatant_buff: combatant_mult,
+        };
+        for entry in get_extra_damage(perks.clone(), &calc_input, false, &mut pers_calc_data) {
+            let damage = entry.additive_damage * buffs.get_buff_amount(&entry);
+            let targets = spread_targets(_group, &group, aimed, entry.spread);
+            for hit in 0..entry.times_to_hit {
+                let hit_at = if entry.hit_at_same_time {
+                    landed_at + entry.time_for_additive_damage
+                } else {
+                    landed_at + entry.time_for_additive_damage * hit as f64
+                };
+                for target in targets.iter() {
+                    group.hit(*target, damage, hit_at);
+                }
+            }
+            if entry.increment_total_time {
+                total_time += entry.time_for_additive_damage;
+            }
+        }
+
+        if mag <= 0 && reserve > 0 {
+            mag = weapon
+                .calc_ammo_sizes(Some(calc_input.clone()), Some(&mut pers_calc_data), false)
+                .mag_size
+                .min(reserve);
+            reserve -= mag;
+            total_time += weapon
+                .calc_reload_time(Some(calc_input), Some(&mut pers_calc_data), false)
+                .reload_time;
+        }
+    }
+
+    let targets_killed = group.kill_times.iter().flatten().count();
+    let clear_time = if targets_killed == _group.count {
+        group.kill_times.iter().flatten().copied().reduce(f64::max)
+    } else {
+        None
+    };
+    GroupDamageResponse {
+        total_damage: group.total_damage,
+        targets_killed,
+        clear_time,
+        kill_times: group.kill_times,
+        total_shots: shots,
+    }
+}
+
+impl Weapon {
+    pub fn calc_group_damage(
+        &self,
+        _group: &TargetGroup,
+        _pl_dmg_mult: f64,
+    ) -> GroupDamageResponse {
+        calc_group_damage(self, _group, _pl_dmg_mult)
+    }
+}
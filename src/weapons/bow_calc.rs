@@ -0,0 +1,64 @@
+//! Drawing, releasing and nocking bows.
+//!
+//! A bow is a held charge (see [`super::charge_calc`]). The draw is the charge time, taken from
+//! the frame's curve over the Draw Time stat in the `bow_frames` section of weapon_formulas.json
+//! and moved by strings (its `bow_strings` section) and draw time perks through their firing
+//! modifiers. Once drawn the arrow can be held for the perfect draw window before
+//! accuracy falls off. Reload only covers part of nocking the next arrow, the rest is a fixed
+//! animation.
+use super::{charge_calc::ChargeProfile, Stat, Weapon};
+use crate::{
+    d2_enums::{Seconds, StatHashes, WeaponType},
+    formulas::get_bow_frame,
+    perks::Perks,
+};
+
+///reload stat past this doesn't nock any faster
+pub const MAX_NOCK_RELOAD_STAT: i32 = 85;
+///end of the nock that no reload stat or perk touches
+const NOCK_FIXED_TIME: Seconds = 0.1;
+///share of the rest of the nock reload perks speed up
+const NOCK_PERK_SHARE: f64 = 0.3;
+
+impl Weapon {
+    ///Draw time before any perks, on top of whatever the frame's firing data has
+    pub fn calc_draw_time(&self) -> Seconds {
+        if self.weapon_type != WeaponType::BOW {
+            return 0.0;
+        }
+        let draw_stat = self
+            .stats
+            .get(&StatHashes::DRAW_TIME.into())
+            .unwrap_or(&Stat::new())
+            .perk_val() as f64;
+        //bows without frame data draw as fast as their firing data says
+        get_bow_frame(self.raw_intrinsic_hash(), self.hash)
+            .map_or(0.0, |frame| frame.draw_time.solve_at(draw_stat).max(0.0))
+    }
+
+    ///How long a full draw can be held before accuracy falls off
+    pub fn calc_perfect_draw(&self) -> Seconds {
+        let stability: f64 = self
+            .stats
+            .get(&StatHashes::STABILITY.into())
+            .unwrap_or(&Stat::new())
+            .perk_val()
+            .clamp(0, 100)
+            .into();
+        if self.perks.contains_key(&Perks::Oathkeeper.into()) && self.weapon_type == WeaponType::BOW
+        {
+            return Seconds::INFINITY;
+        }
+        get_bow_frame(self.raw_intrinsic_hash(), self.hash)
+            .and_then(|frame| frame.perfect_draw)
+            .map_or(Seconds::NAN, |curve| curve.solve_at(stability))
+    }
+
+    ///Reload of a bow, `reload_time` is the reload formula at the capped stat
+    pub fn calc_nock_time(&self, reload_time: Seconds, reload_time_scale: f64) -> Seconds {
+        let nock = reload_time - NOCK_FIXED_TIME;
+        nock * NOCK_PERK_SHARE * reload_time_scale
+            + nock * (1.0 - NOCK_PERK_SHARE)
+            + NOCK_FIXED_TIME
+    }
+}
@@ -0,0 +1,110 @@
+//! The charge cycle of fusions, linear fusions and anything else with `charge` set in its
+//! firing data.
+//!
+//! A charged shot is the frame's charge time (the firing data's burst delay), shortened on
+//! legendary frames by Charge Time stat from parts and mods (the charge time masterwork
+//! included) and moved by charge time perks (Accelerated Coils, Liquid Coils, adept charge
+//! time mods) through their firing modifiers. The bolts then come out `bolt_spacing` apart,
+//! and the weapon can't start charging again until `recovery` has passed. Bows are a charge
+//! that holds, their draw comes from [`super::bow_calc`]. Firing data, TTK and DPS all read
+//! the cycle from here.
+use super::Weapon;
+use crate::{
+    d2_enums::{Seconds, StatHashes, WeaponType},
+    perks::lib::FiringModifierResponse,
+};
+
+///seconds of charge time per point of Charge Time stat on legendary frames
+fn charge_time_per_stat(weapon_type: WeaponType) -> Seconds {
+    match weapon_type {
+        WeaponType::FUSIONRIFLE => 0.0040,
+        WeaponType::LINEARFUSIONRIFLE => 0.0033,
+        _ => 0.0,
+    }
+}
+
+///time after the last bolt before the next charge can start, these are the same delays the
+///displayed rpm has always used
+fn charge_recovery(weapon_type: WeaponType) -> Seconds {
+    match weapon_type {
+        WeaponType::FUSIONRIFLE => 0.45,
+        WeaponType::LINEARFUSIONRIFLE => 0.95,
+        _ => 0.0,
+    }
+}
+
+///whether a full charge waits for the trigger to be released instead of firing on its own
+fn charge_holds(weapon_type: WeaponType) -> bool {
+    matches!(weapon_type, WeaponType::BOW)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChargeProfile {
+    pub charge_time: Seconds,
+    pub bolts: i32,
+    pub bolt_spacing: Seconds,
+    pub recovery: Seconds,
+    ///the charge can be held at full, so the first shot of an engagement is ready straight
+    ///away. Otherwise the first charge counts towards time to kill
+    pub hold: bool,
+}
+impl ChargeProfile {
+    ///trigger pull to last bolt of one shot
+    pub fn shot_time(&self) -> Seconds {
+        self.charge_time + self.bolt_spacing * (self.bolts - 1).max(0) as f64
+    }
+
+    ///one shot including the recovery after it
+    pub fn cycle_time(&self) -> Seconds {
+        self.shot_time() + self.recovery
+    }
+
+    ///time between the previous bolt and this one, counting bolts over the whole engagement
+    pub fn delay_before(&self, bolts_fired: f64) -> Seconds {
+        if bolts_fired == 0.0 {
+            if self.hold {
+                0.0
+            } else {
+                self.charge_time
+            }
+        } else if bolts_fired % self.bolts.max(1) as f64 > 0.0 {
+            self.bolt_spacing
+        } else {
+            self.recovery + self.charge_time
+        }
+    }
+}
+
+impl Weapon {
+    ///None for weapons that don't charge
+    pub fn calc_charge_profile(
+        &self,
+        _modifiers: &FiringModifierResponse,
+    ) -> Option<ChargeProfile> {
+        if !self.firing_data.charge {
+            return None;
+        }
+        let charge_time = if self.weapon_type == WeaponType::BOW {
+            self.firing_data.burst_delay + self.calc_draw_time()
+        } else if self.intrinsic_hash >= 1000 {
+            //exotic charge times are their own, the stat doesn't move them
+            self.firing_data.burst_delay
+        } else {
+            //the frame's charge time is at its base stat, parts and mods move it from there
+            let bonus = self
+                .stats
+                .get(&StatHashes::CHARGE_TIME.into())
+                .map_or(0, |x| x.perk_val() - x.base_value) as f64;
+            self.firing_data.burst_delay - bonus * charge_time_per_stat(self.weapon_type)
+        };
+        let charge_time =
+            ((charge_time + _modifiers.burst_delay_add) * _modifiers.burst_delay_scale).max(0.0);
+        Some(ChargeProfile {
+            charge_time,
+            bolts: self.firing_data.burst_size + _modifiers.burst_size_add as i32,
+            bolt_spacing: self.firing_data.inner_burst_delay * _modifiers.inner_burst_scale,
+            recovery: charge_recovery(self.weapon_type),
+            hold: charge_holds(self.weapon_type),
+        })
+    }
+}
@@ -0,0 +1,327 @@
+//! Side by side diffs of every calculated value for two or more weapons.
+//!
+//! Every weapon is run through the same calculators the single weapon getters use and the
+//! results are lined up into rows. Deltas are against the first weapon, and each row says
+//! whether higher or lower is better so the winner can be picked.
+use serde::Serialize;
+
+use super::Weapon;
+use crate::{
+    activity::{combatant_scaling::get_combatant_scaling, Activity},
+    d2_enums::StatHashes,
+    enemies::Enemy,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Better {
+    Higher,
+    Lower,
+    ///nothing to win, like burst size
+    Neither,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComparisonRow {
+    ///stats, range, handling, reload, ammo, firing, flinch, ttk, misc or dps
+    pub section: &'static str,
+    pub name: String,
+    ///one per weapon, None when a weapon doesn't have the value
+    pub values: Vec<Option<f64>>,
+    pub better: Better,
+    ///against the first weapon
+    pub deltas: Vec<Option<f64>>,
+    ///against the first weapon, None when the first weapon's value is 0
+    pub percent_deltas: Vec<Option<f64>>,
+    ///index of the weapon with the best value, None on a tie
+    pub winner: Option<usize>,
+}
+impl ComparisonRow {
+    pub fn new(
+        section: &'static str,
+        name: impl Into<String>,
+        values: Vec<Option<f64>>,
+        better: Better,
+    ) -> ComparisonRow {
+        let first = values.first().copied().flatten();
+        let deltas: Vec<Option<f64>> = values.iter().map(|x| Some(x.as_ref()? - first?)).collect();
+        let percent_deltas = values
+            .iter()
+            .map(|x| match first {
+                Some(first) if first != 0.0 => Some((x.as_ref()? - first) / first.abs() * 100.0),
+                _ => None,
+            })
+            .collect();
+        ComparisonRow {
+            section,
+            name: name.into(),
+            winner: find_winner(&values, better),
+            values,
+            better,
+            deltas,
+            percent_deltas,
+        }
+    }
+}
+
+fn find_winner(values: &[Option<f64>], better: Better) -> Option<usize> {
+    let sign = match better {
+        Better::Higher => 1.0,
+        Better::Lower => -1.0,
+        Better::Neither => return None,
+    };
+    let present: Vec<(usize, f64)> = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| Some((i, (*x)? * sign)))
+        .collect();
+    if present.len() < 2 {
+        return None;
+    }
+    let best = present
+        .iter()
+        .map(|x| x.1)
+        .fold(f64::NEG_INFINITY, f64::max);
+    let mut at_best = present.iter().filter(|x| (x.1 - best).abs() < 1e-9);
+    let winner = at_best.next()?.0;
+    match at_best.next() {
+        Some(_) => None,
+        None => Some(winner),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponComparison {
+    pub weapons: usize,
+    pub rows: Vec<ComparisonRow>,
+}
+
+///Each section's values for one weapon, rows are built once every weapon has been run
+struct Column {
+    values: Vec<(&'static str, String, f64, Better)>,
+}
+impl Column {
+    fn push(&mut self, section: &'static str, name: impl Into<String>, value: f64, better: Better) {
+        self.values.push((section, name.into(), value, better));
+    }
+    fn extend<const N: usize>(&mut self, section: &'static str, rows: [(&str, f64, Better); N]) {
+        for (name, value, better) in rows {
+            self.push(section, name, value, better);
+        }
+    }
+}
+
+fn stat_better(stat: StatHashes) -> Better {
+    match stat {
+        StatHashes::CHARGE_TIME | StatHashes::DRAW_TIME => Better::Lower,
+        StatHashes::RPM | StatHashes::RECOIL_DIR => Better::Neither,
+        _ => Better::Higher,
+    }
+}
+
+fn misc_better(name: &str) -> Better {
+    match name {
+        "percent_energy_drained_per_shot" | "shots_to_proc_restoration" => Better::Lower,
+        _ => Better::Higher,
+    }
+}
+
+fn calc_column(
+    weapon: &Weapon,
+    activity: &Activity,
+    enemy: &Enemy,
+    _dynamic_traits: bool,
+    _pvp: bool,
+) -> Column {
+    let mut column = Column { values: Vec::new() };
+    let calc_input = || {
+        _dynamic_traits.then(|| {
+            let mut calc_input = weapon.static_calc_input();
+            calc_input.enemy_type = &enemy.type_;
+            calc_input
+        })
+    };
+
+    let mut stats: Vec<(u32, i32)> = weapon
+        .stats
+        .iter()
+        .map(|(hash, stat)| (*hash, stat.perk_val()))
+        .collect();
+    stats.sort_unstable();
+    for (hash, value) in stats {
+        let stat = StatHashes::from(hash);
+        let name = match stat {
+            StatHashes::UNKNOWN => hash.to_string(),
+            _ => format!("{:?}", stat).to_lowercase(),
+        };
+        column.push("stats", name, value as f64, stat_better(stat));
+    }
+
+    let range = weapon.calc_range_falloff(calc_input(), None, _pvp);
+    column.extend(
+        "range",
+        [
+            ("hip_falloff_start", range.hip_falloff_start, Better::Higher),
+            ("hip_falloff_end", range.hip_falloff_end, Better::Higher),
+            ("ads_falloff_start", range.ads_falloff_start, Better::Higher),
+            ("ads_falloff_end", range.ads_falloff_end, Better::Higher),
+        ],
+    );
+
+    let handling = weapon.calc_handling_times(calc_input(), None, _pvp);
+    column.extend(
+        "handling",
+        [
+            ("ready_time", handling.ready_time, Better::Lower),
+            ("stow_time", handling.stow_time, Better::Lower),
+            ("ads_time", handling.ads_time, Better::Lower),
+        ],
+    );
+
+    let reload = weapon.calc_reload_time(calc_input(), None, _pvp);
+    column.extend(
+        "reload",
+        [
+            ("reload_time", reload.reload_time, Better::Lower),
+            ("ammo_time", reload.ammo_time, Better::Lower),
+        ],
+    );
+
+    let ammo = weapon.calc_ammo_sizes(calc_input(), None, _pvp);
+    column.extend(
+        "ammo",
+        [
+            ("mag_size", ammo.mag_size as f64, Better::Higher),
+            ("reserve_size", ammo.reserve_size as f64, Better::Higher),
+        ],
+    );
+
+    let mut firing = weapon.calc_firing_data(calc_input(), None, _pvp);
+    firing.apply_pve_bonuses(
+        activity.get_rpl_mult(),
+        activity.get_pl_delta(),
+        weapon.damage_mods.pve,
+        get_combatant_scaling(weapon, enemy.type_).total(),
+    );
+    let (impact, explosion, crit) = if _pvp {
+        (
+            firing.pvp_impact_damage,
+            firing.pvp_explosion_damage,
+            firing.pvp_crit_mult,
+        )
+    } else {
+        (
+            firing.pve_impact_damage,
+            firing.pve_explosion_damage,
+            firing.pve_crit_mult,
+        )
+    };
+    column.extend(
+        "firing",
+        [
+            ("impact_damage", impact, Better::Higher),
+            ("explosion_damage", explosion, Better::Higher),
+            ("crit_mult", crit, Better::Higher),
+            ("rpm", firing.rpm, Better::Higher),
+            ("burst_delay", firing.burst_delay, Better::Lower),
+            ("inner_burst_delay", firing.inner_burst_delay, Better::Lower),
+            ("burst_size", firing.burst_size as f64, Better::Neither),
+        ],
+    );
+
+    //the scaler on flinch taken, so lower is better
+    let flinch = weapon.calc_flinch_resist(calc_input(), 0, _pvp, None);
+    column.push("flinch", "flinch", flinch, Better::Lower);
+
+    if _pvp {
+        for summary in weapon.calc_ttk(0.0) {
+            column.push(
+                "ttk",
+                format!("optimal_ttk_{}", summary.value),
+                summary.optimal_ttk.time_taken,
+                Better::Lower,
+            );
+            column.push(
+                "ttk",
+                format!("body_ttk_{}", summary.value),
+                summary.body_ttk.time_taken,
+                Better::Lower,
+            );
+        }
+    }
+
+    let mut misc: Vec<(String, f64)> = weapon
+        .get_misc_stats(calc_input(), _pvp)
+        .into_iter()
+        .collect();
+    misc.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+    for (name, value) in misc {
+        let better = misc_better(&name);
+        column.push("misc", name, value, better);
+    }
+
+    if !_pvp {
+        let mut dps = weapon.calc_dps(enemy.clone(), activity.get_pl_delta());
+        dps.apply_rpl(activity.get_rpl_mult());
+        if dps.total_time > 0.0 {
+            column.extend(
+                "dps",
+                [
+                    ("dps", dps.total_damage / dps.total_time, Better::Higher),
+                    ("total_damage", dps.total_damage, Better::Higher),
+                ],
+            );
+        }
+    }
+    column
+}
+
+///Compares every weapon against the first, `_pvp` picks which damage and ttk rows are built
+///(pvp adds ttk per resilience, pve adds dps against `enemy`)
+pub fn compare_weapons(
+    weapons: &[Weapon],
+    activity: &Activity,
+    enemy: &Enemy,
+    _dynamic_traits: bool,
+    _pvp: bool,
+) -> Result<WeaponComparison, String> {
+    if weapons.len() < 2 {
+        return Err("at least two weapons are needed to compare".to_owned());
+    }
+    let columns: Vec<Column> = weapons
+        .iter()
+        .map(|weapon| calc_column(weapon, activity, enemy, _dynamic_traits, _pvp))
+        .collect();
+
+    //rows in the order they first show up, a row missing from a weapon is None for it
+    let mut keys: Vec<(&'static str, String, Better)> = Vec::new();
+    for column in &columns {
+        for (section, name, _, better) in &column.values {
+            if !keys.iter().any(|x| x.0 == *section && x.1 == *name) {
+                keys.push((section, name.clone(), *better));
+            }
+        }
+    }
+    let rows = keys
+        .into_iter()
+        .map(|(section, name, better)| {
+            let values = columns
+                .iter()
+                .map(|column| {
+                    column
+                        .values
+                        .iter()
+                        .find(|x| x.0 == section && x.1 == name)
+                        .map(|x| x.2)
+                })
+                .collect();
+            ComparisonRow::new(section, name, values, better)
+        })
+        .collect();
+    Ok(WeaponComparison {
+        weapons: weapons.len(),
+        rows,
+    })
+}
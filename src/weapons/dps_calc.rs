@@ -2,13 +2,15 @@ use std::collections::HashMap;
 use std::{cell::RefCell, rc::Rc};
 
 use super::Weapon;
+use crate::activity::combatant_scaling::get_combatant_scaling;
 use crate::d2_enums::{AmmoType, WeaponType};
 use crate::enemies::Enemy;
 use crate::perks::lib::{
     CalculationInput, ExtraDamageResponse, RefundResponse, ReloadOverrideResponse,
 };
 use crate::perks::*;
-use crate::types::rs_types::DpsResponse;
+use crate::types::rs_types::{AmmoResponse, DpsResponse};
+use serde::Serialize;
 
 //first entry in tuple is refund to mag, second is too reserves
 pub fn calc_refund(_shots_hit_this_mag: i32, _refunds: Vec<RefundResponse>) -> (i32, i32) {
@@ -114,6 +116,27 @@ pub fn calc_extra_dmg(
     }
 }
 
+///Puts a shot's damage on the timeline when it lands instead of when it was fired, with the
+///explosion following the impact by `_explosion_delay`. Returns when the last of it lands
+pub fn push_landed_damage(
+    _time_damage_data: &mut Vec<(f64, f64)>,
+    _fired_at: f64,
+    _impact: f64,
+    _explosion: f64,
+    _travel_time: f64,
+    _explosion_delay: f64,
+) -> f64 {
+    let impact_at = _fired_at + _travel_time;
+    if _explosion > 0.0 && _explosion_delay > 0.0 {
+        _time_damage_data.push((impact_at, _impact));
+        _time_damage_data.push((impact_at + _explosion_delay, _explosion));
+        impact_at + _explosion_delay
+    } else {
+        _time_damage_data.push((impact_at, _impact + _explosion));
+        impact_at
+    }
+}
+
 pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> DpsResponse {
     let weapon = Rc::new(_weapon.clone());
     let stats = weapon.stats.clone();
@@ -124,7 +147,8 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
     let impact_dmg = tmp_dmg_prof.impact_dmg;
     let explosion_dmg = tmp_dmg_prof.explosion_dmg;
     let crit_mult = tmp_dmg_prof.crit_mult;
-    // let damage_delay = tmp_dmg_prof.damage_delay;
+    let combatant_mult = get_combatant_scaling(&weapon, _enemy.type_).total();
+    let damage_delay = tmp_dmg_prof.damage_delay;
 
     let base_mag = weapon.calc_ammo_sizes(None, None, false).mag_size;
     let maximum_shots = if base_mag * 5 < 15 { 15 } else { base_mag * 5 };
@@ -138,6 +162,9 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
 
     let mut total_damage = 0.0_f64;
     let mut total_time = 0.0_f64;
+    let mut total_reload_time = 0.0_f64;
+    //projectiles can still be in the air once the last shot is fired
+    let mut last_landing = 0.0_f64;
 
     let mut time_damage_data: Vec<(f64, f64)> = Vec::new(); //used for chart stuff
     let mut dps_per_mag: Vec<f64> = Vec::new(); //used for chart stuff
@@ -217,19 +244,33 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             );
             ///////////////////////////////
 
-            let dmg = {
-                ((impact_dmg * dmg_mods.impact_dmg_scale) * (crit_mult * dmg_mods.crit_scale)
-                    + (explosion_dmg * dmg_mods.explosive_dmg_scale))
-                    * _pl_dmg_mult
-                    * weapon.damage_mods.get_mod(&_enemy.type_)
-                    * weapon.damage_mods.pve
-            };
+            let dmg_scale = _pl_dmg_mult * combatant_mult * weapon.damage_mods.pve;
+            let impact_part = (impact_dmg * dmg_mods.impact_dmg_scale)
+                * (crit_mult * dmg_mods.crit_scale)
+                * dmg_scale;
+            let explosion_part = explosion_dmg * dmg_mods.explosive_dmg_scale * dmg_scale;
+            let dmg = impact_part + explosion_part;
+            let travel_time = weapon.calc_travel_time(
+                _enemy.distance,
+                Some(before_shot_input_data.clone()),
+                false,
+                Some(&mut pers_calc_data),
+            );
 
             let shot_burst_delay =
                 (burst_delay + firing_mods.burst_delay_add) * firing_mods.burst_delay_scale;
             let shot_inner_burst_delay = inner_burst_delay * firing_mods.inner_burst_scale;
             let shot_burst_size = burst_size + firing_mods.burst_size_add;
 
+            //charged weapons charge before each burst instead of waiting out a burst delay
+            let charge = weapon.calc_charge_profile(&firing_mods);
+            let burst_start = firing_settings.one_ammo || shots_this_mag % burst_size as i32 == 0;
+            if let Some(charge) = charge {
+                if burst_start && (total_shots_fired > 0 || !charge.hold) {
+                    total_time += charge.charge_time;
+                }
+            }
+
             // if total_shots_fired == 0 && firing_settings.is_charge {
             //     total_time += shot_burst_delay*0.5;
             // }
@@ -240,11 +281,20 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 total_shots_hit += shot_burst_size as i32;
                 total_damage += dmg * shot_burst_size;
                 for i in 0..shot_burst_size as i32 {
-                    time_damage_data.push((total_time + shot_inner_burst_delay * i as f64, dmg));
+                    last_landing = push_landed_damage(
+                        &mut time_damage_data,
+                        total_time + shot_inner_burst_delay * i as f64,
+                        impact_part,
+                        explosion_part,
+                        travel_time,
+                        damage_delay,
+                    );
                 }
                 total_time += inner_burst_delay * (shot_burst_size - 1.0);
             } else {
-                let spec_delay = if shots_this_mag % burst_size as i32 == 0 {
+                let spec_delay = if charge.is_some() && burst_start {
+                    0.0
+                } else if shots_this_mag % burst_size as i32 == 0 {
                     shot_burst_delay
                 } else {
                     shot_inner_burst_delay
@@ -252,13 +302,20 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 total_shots_fired += 1;
                 shots_this_mag += 1;
                 total_shots_hit += 1;
-                if inner_burst_delay == 0.0 {
-                    total_damage += dmg * burst_size;
-                    time_damage_data.push((total_time, dmg * burst_size));
+                let landed_scale = if inner_burst_delay == 0.0 {
+                    burst_size
                 } else {
-                    total_damage += dmg;
-                    time_damage_data.push((total_time, dmg));
-                }
+                    1.0
+                };
+                total_damage += dmg * landed_scale;
+                last_landing = push_landed_damage(
+                    &mut time_damage_data,
+                    total_time,
+                    impact_part * landed_scale,
+                    explosion_part * landed_scale,
+                    travel_time,
+                    damage_delay,
+                );
                 if total_shots_fired > 0 {
                     total_time += spec_delay;
                 }
@@ -317,7 +374,7 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
                 explosive_buff: dmg_mods.explosive_dmg_scale,
                 pve_buff: weapon.damage_mods.pve,
                 crit_buff: crit_mult * dmg_mods.crit_scale,
-                combatant_buff: weapon.damage_mods.get_mod(&_enemy.type_),
+                combatant_buff: combatant_mult,
             };
             let tmp_out_data = calc_extra_dmg(total_time, extra_dmg_responses, buffs);
             total_damage += tmp_out_data.extra_dmg;
@@ -362,11 +419,11 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
             //         }
             //     }
             // }
-            if mag != 0 {
-                if weapon.weapon_type == WeaponType::FUSIONRIFLE {
-                    total_time += shot_burst_delay * 0.45
-                } else if weapon.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-                    total_time += shot_burst_delay * 0.95
+            //reloading covers the recovery after the last shot in a mag
+            if let Some(charge) = charge {
+                if mag != 0 && (firing_settings.one_ammo || shots_this_mag % burst_size as i32 == 0)
+                {
+                    total_time += charge.recovery;
                 }
             }
             ///////////////////////////////
@@ -413,9 +470,11 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
         let reload_responses =
             weapon.calc_reload_time(Some(reload_input_data), Some(&mut pers_calc_data), false);
         total_time += reload_responses.reload_time;
+        total_reload_time += reload_responses.reload_time;
         ///////////////////////////////
         num_reloads += 1;
     }
+    total_time = total_time.max(last_landing);
     //sort time_damage_data by time
     time_damage_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
     DpsResponse {
@@ -424,5 +483,83 @@ pub fn complex_dps_calc(_weapon: Weapon, _enemy: Enemy, _pl_dmg_mult: f64) -> Dp
         total_damage,
         total_time,
         total_shots: total_shots_fired,
+        reload_time: total_reload_time,
+        melee_damage: 0.0,
+    }
+}
+
+///seconds from the first shot the burst dps windows cover
+pub const DPS_WINDOWS: [f64; 3] = [5.0, 10.0, 30.0];
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DpsWindow {
+    pub seconds: f64,
+    pub dps: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DpsMetrics {
+    ///dps over each of [`DPS_WINDOWS`]
+    pub window_dps: Vec<DpsWindow>,
+    ///dps until the reserves run out
+    pub sustained_dps: f64,
+    pub damage_per_mag: f64,
+    ///None for special and heavy weapons without brick data
+    pub damage_per_brick: Option<f64>,
+    ///full reserves, the loaded magazine included
+    pub damage_per_reserve: f64,
+    pub time_to_empty_mag: f64,
+    pub time_to_empty_reserves: f64,
+    ///share of the total time spent reloading
+    pub reload_share: f64,
+}
+
+///Metrics from a dps simulation. Per magazine, brick and reserve figures scale the average
+///damage and time of one ammo, which also covers primaries the simulation stops early on.
+///Damage dealt without spending ammo is left out of them
+pub fn calc_dps_metrics(
+    _dps: &DpsResponse,
+    _ammo: &AmmoResponse,
+    _brick_size: Option<i32>,
+) -> DpsMetrics {
+    let shots = _dps.total_shots.max(1) as f64;
+    let damage_per_ammo = (_dps.total_damage - _dps.melee_damage) / shots;
+    let full_reserve = _ammo.reserve_size as f64;
+    let (sustained_dps, reload_share) = if _dps.total_time > 0.0 {
+        (
+            _dps.total_damage / _dps.total_time,
+            _dps.reload_time / _dps.total_time,
+        )
+    } else {
+        (0.0, 0.0)
+    };
+    DpsMetrics {
+        window_dps: DPS_WINDOWS
+            .iter()
+            .map(|seconds| DpsWindow {
+                seconds: *seconds,
+                dps: _dps.get_dps_over_window(*seconds),
+            })
+            .collect(),
+        sustained_dps,
+        damage_per_mag: damage_per_ammo * _ammo.mag_size as f64,
+        damage_per_brick: _brick_size.map(|x| damage_per_ammo * x as f64),
+        damage_per_reserve: damage_per_ammo * full_reserve,
+        time_to_empty_mag: (_dps.total_time - _dps.reload_time) / shots * _ammo.mag_size as f64,
+        time_to_empty_reserves: _dps.total_time / shots * full_reserve,
+        reload_share,
+    }
+}
+
+impl Weapon {
+    pub fn calc_dps_metrics(&self, _enemy: Enemy, _pl_dmg_mult: f64) -> DpsMetrics {
+        let ammo = self.calc_ammo_sizes(None, None, false);
+        calc_dps_metrics(
+            &self.calc_dps(_enemy, _pl_dmg_mult),
+            &ammo,
+            self.calc_brick_size(&ammo),
+        )
     }
 }
@@ -0,0 +1,238 @@
+//! Glaives: projectile shots, the melee that charges them and the shield.
+//!
+//! A glaive's firing data is its projectile. Melee hits build energy and every
+//! `melees_per_shot` hits charge one shot from reserves into the magazine, so an empty glaive
+//! melees instead of reloading. The shield's damage resistance goes on whoever holds it, see
+//! [`crate::enemies::Enemy::add_damage_resistance`] and [`Weapon::calc_ttk_resisted`]. The melee
+//! and shield come from the `glaive_frames` section of weapon_formulas.json, see
+//! [`get_glaive_frame`]. The shipped frames are unmeasured estimates, without one a glaive
+//! reloads like a gun.
+use std::collections::HashMap;
+
+use super::{
+    dps_calc::{calc_extra_dmg, calc_refund, push_landed_damage, ExtraDamageBuffInfo},
+    Weapon,
+};
+use crate::{
+    activity::combatant_scaling::get_combatant_scaling,
+    d2_enums::{Seconds, WeaponType},
+    enemies::Enemy,
+    formulas::get_glaive_frame,
+    perks::{
+        get_dmg_modifier, get_extra_damage, get_firing_modifier, get_refund_modifier,
+        lib::FiringModifierResponse,
+    },
+    types::rs_types::DpsResponse,
+};
+
+///keeps the simulation finite for glaives that refund ammo
+const MAX_ACTIONS: i32 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlaiveProfile {
+    ///one projectile, burst delay and all
+    pub shot_time: Seconds,
+    pub melee_scale: f64,
+    pub melee_time: Seconds,
+    pub melees_per_shot: i32,
+}
+impl GlaiveProfile {
+    pub fn recharge_time(&self) -> Seconds {
+        self.melee_time * self.melees_per_shot as f64
+    }
+
+    ///Melees on an empty glaive until the next shot is charged or `health_left` runs out,
+    ///returns (time, damage, killed). `shot_damage` is the impact a shot would deal right now
+    pub fn melee_recharge(&self, shot_damage: f64, health_left: f64) -> (Seconds, f64, bool) {
+        let melee_damage = shot_damage * self.melee_scale;
+        let mut damage = 0.0;
+        for hit in 1..=self.melees_per_shot {
+            damage += melee_damage;
+            if damage >= health_left {
+                return (self.melee_time * hit as f64, damage, true);
+            }
+        }
+        (self.recharge_time(), damage, false)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlaiveShield {
+    ///damage reduction for whoever holds the shield up, 0.0-1.0
+    pub damage_resistance: f64,
+    pub duration: Seconds,
+}
+
+impl Weapon {
+    ///None for anything that isn't a glaive or a glaive without frame data
+    pub fn calc_glaive_profile(
+        &self,
+        _modifiers: &FiringModifierResponse,
+    ) -> Option<GlaiveProfile> {
+        if self.weapon_type != WeaponType::GLAIVE {
+            return None;
+        }
+        let frame = get_glaive_frame(self.raw_intrinsic_hash(), self.hash)?;
+        Some(GlaiveProfile {
+            shot_time: (self.firing_data.burst_delay + _modifiers.burst_delay_add)
+                * _modifiers.burst_delay_scale,
+            melee_scale: frame.melee_scale,
+            melee_time: frame.melee_time,
+            melees_per_shot: frame.melees_per_shot.max(1),
+        })
+    }
+
+    ///None for anything that isn't a glaive or a glaive without frame data
+    pub fn calc_glaive_shield(&self, _pvp: bool) -> Option<GlaiveShield> {
+        if self.weapon_type != WeaponType::GLAIVE {
+            return None;
+        }
+        let frame = get_glaive_frame(self.raw_intrinsic_hash(), self.hash)?;
+        Some(GlaiveShield {
+            damage_resistance: if _pvp {
+                frame.shield_resistance_pvp
+            } else {
+                frame.shield_resistance_pve
+            },
+            duration: self.calc_shield_duration(),
+        })
+    }
+}
+
+///Fires the magazine, then melees to charge each following shot until reserves run out.
+///Bursts fire like they do in [`super::dps_calc::complex_dps_calc`], a one ammo burst spends a
+///single round on every projectile
+pub fn calc_glaive_dps(weapon: &Weapon, _enemy: &Enemy, _pl_dmg_mult: f64) -> DpsResponse {
+    let mut pers_calc_data: HashMap<String, f64> = HashMap::new();
+    let perks = weapon.list_perks();
+    let damage_profile = weapon.get_damage_profile(false);
+    let combatant_mult = get_combatant_scaling(weapon, _enemy.type_).total();
+    let base_mag = weapon.calc_ammo_sizes(None, None, false).mag_size;
+    let ammo = weapon.calc_ammo_sizes(
+        Some(weapon.static_calc_input()),
+        Some(&mut pers_calc_data),
+        false,
+    );
+    //the loaded magazine comes out of the reserves like in complex_dps_calc
+    let mut mag = ammo.mag_size.min(ammo.reserve_size);
+    let mut reserve = ammo.reserve_size - mag;
+
+    let mut total_damage = 0.0_f64;
+    let mut total_time = 0.0_f64;
+    //meleeing an empty glaive is its reload
+    let mut melee_time = 0.0_f64;
+    let mut melee_damage = 0.0_f64;
+    let mut last_landing = 0.0_f64;
+    let mut time_damage_data: Vec<(f64, f64)> = Vec::new();
+    let mut dps_per_mag: Vec<f64> = Vec::new();
+    let mut shots = 0_i32;
+    let mut actions = 0_i32;
+    let mut energy = 0_i32;
+
+    while (mag > 0 || reserve > 0) && actions < MAX_ACTIONS {
+        actions += 1;
+        let mut calc_input = weapon.sparse_calc_input(shots, total_time);
+        calc_input.enemy_type = &_enemy.type_;
+        calc_input.base_mag = base_mag as f64;
+        calc_input.curr_mag = mag as f64;
+        calc_input.reserves_left = reserve as f64;
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let profile = weapon
+            .calc_glaive_profile(&firing_mods)
+            .expect("only glaives are simulated here");
+        let scale = _pl_dmg_mult * combatant_mult * weapon.damage_mods.pve;
+        let impact = damage_profile.impact_dmg * dmg_mods.impact_dmg_scale;
+
+        if mag == 0 {
+            let dmg = impact * profile.melee_scale * scale;
+            total_damage += dmg;
+            melee_damage += dmg;
+            time_damage_data.push((total_time, dmg));
+            total_time += profile.melee_time;
+            melee_time += profile.melee_time;
+            energy += 1;
+            if energy >= profile.melees_per_shot {
+                energy = 0;
+                mag += 1;
+                reserve -= 1;
+                dps_per_mag.push(total_damage / total_time);
+            }
+            continue;
+        }
+
+        let explosion = damage_profile.explosion_dmg * dmg_mods.explosive_dmg_scale;
+        let travel_time = weapon.calc_travel_time(
+            _enemy.distance,
+            Some(calc_input.clone()),
+            false,
+            Some(&mut pers_calc_data),
+        );
+        let burst_size = (weapon.firing_data.burst_size + firing_mods.burst_size_add as i32).max(1);
+        let inner_burst_delay =
+            weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
+        let projectiles = if weapon.firing_data.one_ammo {
+            burst_size
+        } else {
+            1
+        };
+        for i in 0..projectiles {
+            total_damage += (impact + explosion) * scale;
+            last_landing = push_landed_damage(
+                &mut time_damage_data,
+                total_time + inner_burst_delay * i as f64,
+                impact * scale,
+                explosion * scale,
+                travel_time,
+                damage_profile.damage_delay,
+            );
+        }
+        //one ammo bursts wait out the whole burst, otherwise only the last round of a burst
+        //waits for the burst delay
+        let shot_time = if weapon.firing_data.one_ammo {
+            inner_burst_delay * (projectiles - 1) as f64 + profile.shot_time
+        } else if (shots + 1) % burst_size == 0 {
+            profile.shot_time
+        } else {
+            inner_burst_delay
+        };
+        shots += 1;
+        mag -= 1;
+        let refunds = get_refund_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let refund = calc_refund(shots, refunds);
+        mag += refund.0;
+        reserve += refund.1;
+
+        let extra_dmg_responses =
+            get_extra_damage(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let buffs = ExtraDamageBuffInfo {
+            pl_buff: _pl_dmg_mult,
+            impact_buff: dmg_mods.impact_dmg_scale,
+            explosive_buff: dmg_mods.explosive_dmg_scale,
+            pve_buff: weapon.damage_mods.pve,
+            crit_buff: damage_profile.crit_mult * dmg_mods.crit_scale,
+            combatant_buff: combatant_mult,
+        };
+        let extra = calc_extra_dmg(total_time, extra_dmg_responses, buffs);
+        total_damage += extra.extra_dmg;
+        total_time += extra.extra_time;
+        time_damage_data.extend(extra.extra_time_dmg);
+        total_time += shot_time;
+    }
+
+    total_time = total_time.max(last_landing);
+    if dps_per_mag.is_empty() && total_time > 0.0 {
+        dps_per_mag.push(total_damage / total_time);
+    }
+    time_damage_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    DpsResponse {
+        dps_per_mag,
+        time_damage_data,
+        total_damage,
+        total_time,
+        total_shots: shots,
+        reload_time: melee_time,
+        melee_damage,
+    }
+}
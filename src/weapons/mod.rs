@@ -1,6 +1,14 @@
+pub mod aoe_calc;
+pub mod bow_calc;
+pub mod charge_calc;
+pub mod comparison;
 pub mod dps_calc;
+pub mod glaive_calc;
 pub mod reserve_calc;
+pub mod roll_ranking;
 pub mod stat_calc;
+pub mod stat_curves;
+pub mod sword_calc;
 pub mod ttk_calc;
 pub mod weapon_constructor;
 
@@ -10,15 +18,19 @@ use serde::{Deserialize, Serialize};
 
 use crate::d2_enums::{AmmoType, DamageType, StatHashes, WeaponType};
 use crate::enemies::Enemy;
+use crate::formulas::{get_glaive_frame, get_sword_frame};
 use crate::perks::{
     get_magazine_modifier, get_reserve_modifier, get_stat_bumps, lib::CalculationInput, Perk,
 };
 
 use crate::types::rs_types::{
     AmmoFormula, DamageMods, DpsResponse, FiringData, HandlingFormula, RangeFormula, ReloadFormula,
+    StatGroup,
 };
 
 use self::dps_calc::complex_dps_calc;
+use self::glaive_calc::calc_glaive_dps;
+use self::sword_calc::calc_sword_dps;
 
 #[derive(Debug, Clone)]
 pub struct PsuedoWeapon {}
@@ -68,6 +80,10 @@ pub struct Weapon {
 
     pub perks: HashMap<u32, Perk>,
     pub stats: HashMap<u32, Stat>,
+    ///when set stats are derived from these through the stat group, see set_investment_stats
+    pub investment_stats: HashMap<u32, i32>,
+    #[serde(skip)]
+    pub stat_group: Option<StatGroup>,
     #[serde(skip)]
     pub perk_value_map: HashMap<u32, u32>,
 
@@ -125,11 +141,23 @@ impl Weapon {
     }
     pub fn set_stats(&mut self, _stats: HashMap<u32, Stat>) {
         self.stats = _stats;
+        self.investment_stats = HashMap::new();
+        self.stat_group = None;
+        self.update_stats()
+    }
+    ///Sets stats from investment values, displayed values come from the stat group
+    ///with perk and part bumps applied as investment before interpolating
+    pub fn set_investment_stats(&mut self, _stat_group: StatGroup, _stats: HashMap<u32, i32>) {
+        self.stats = HashMap::new();
+        self.investment_stats = _stats;
+        self.stat_group = Some(_stat_group);
         self.update_stats()
     }
     pub fn reset(&mut self) {
         self.perks = HashMap::new();
         self.stats = HashMap::new();
+        self.investment_stats = HashMap::new();
+        self.stat_group = None;
         self.hash = 0;
         self.damage_mods = DamageMods::default();
         self.firing_data = FiringData::default();
@@ -139,11 +167,15 @@ impl Weapon {
         self.reload_formula = ReloadFormula::default();
     }
 
-    pub fn static_calc_input(&self) -> CalculationInput {
+    pub fn static_calc_input(&self) -> CalculationInput<'_> {
         CalculationInput::construct_static(self)
     }
 
-    pub fn sparse_calc_input(&self, _total_shots_fired: i32, _total_time: f64) -> CalculationInput {
+    pub fn sparse_calc_input(
+        &self,
+        _total_shots_fired: i32,
+        _total_time: f64,
+    ) -> CalculationInput<'_> {
         CalculationInput::construct_pve_sparse(
             self.intrinsic_hash,
             &self.firing_data,
@@ -165,7 +197,7 @@ impl Weapon {
         _total_shots_hit: f64,
         _total_time: f64,
         _overshield: bool,
-    ) -> CalculationInput {
+    ) -> CalculationInput<'_> {
         let base_mag = self.calc_ammo_sizes(None, None, true).mag_size as f64;
         let mut tmp = CalculationInput::construct_pvp(
             self.intrinsic_hash,
@@ -204,8 +236,65 @@ impl Weapon {
                 stat.perk_value = *value;
             }
         }
+        if let Some(stat_group) = &self.stat_group {
+            for (key, investment) in &self.investment_stats {
+                let with_parts = investment + static_stats.get(key).unwrap_or(&0);
+                let with_perks = with_parts + dynamic_stats.get(key).unwrap_or(&0);
+                //stats the group doesn't scale are displayed as their investment value
+                let display = |value: i32| stat_group.display_value(*key, value).unwrap_or(value);
+                let (base, parts, perks) = (
+                    display(*investment),
+                    display(with_parts),
+                    display(with_perks),
+                );
+                self.stats.insert(
+                    *key,
+                    Stat {
+                        base_value: base,
+                        part_value: parts - base,
+                        perk_value: perks - parts,
+                    },
+                );
+            }
+            //bumps to stats the item has no investment in are carried through as is
+            for key in static_stats.keys().chain(dynamic_stats.keys()) {
+                if self.investment_stats.contains_key(key) {
+                    continue;
+                }
+                self.stats.insert(
+                    *key,
+                    Stat {
+                        base_value: 0,
+                        part_value: *static_stats.get(key).unwrap_or(&0),
+                        perk_value: *dynamic_stats.get(key).unwrap_or(&0),
+                    },
+                );
+            }
+        }
     }
+    ///the intrinsic the weapon was built from, before enhanced and procedural intrinsics are
+    ///folded into `intrinsic_hash`. Weapon formulas are keyed by this one
+    pub fn raw_intrinsic_hash(&self) -> u32 {
+        self.perks
+            .get(&self.intrinsic_hash)
+            .map_or(self.intrinsic_hash, |x| x.raw_hash)
+    }
+
     pub fn calc_dps(&self, _enemy: Enemy, _pl_dmg_mult: f64) -> DpsResponse {
+        match self.weapon_type {
+            //swords and glaives without frame data can only be treated like guns
+            WeaponType::SWORD
+                if get_sword_frame(self.raw_intrinsic_hash(), self.hash).is_some() =>
+            {
+                return calc_sword_dps(self, &_enemy, _pl_dmg_mult)
+            }
+            WeaponType::GLAIVE
+                if get_glaive_frame(self.raw_intrinsic_hash(), self.hash).is_some() =>
+            {
+                return calc_glaive_dps(self, &_enemy, _pl_dmg_mult)
+            }
+            _ => (),
+        }
         complex_dps_calc(self.clone(), _enemy, _pl_dmg_mult)
     }
 }
@@ -217,6 +306,8 @@ impl Default for Weapon {
 
             perks: HashMap::new(),
             stats: HashMap::new(),
+            investment_stats: HashMap::new(),
+            stat_group: None,
             perk_value_map: HashMap::new(),
 
             damage_mods: DamageMods::default(),
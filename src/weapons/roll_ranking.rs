@@ -0,0 +1,299 @@
+//! Scores every perk combination of a weapon and exports the best as a DIM wishlist.
+//!
+//! The base weapon holds whatever never changes (intrinsic, masterwork and so on) and the
+//! rest comes as perk columns. Every combination of one perk per column is added on top,
+//! scored by a single [`RollMetric`] and the top rolls are written out as `dimwishlist:`
+//! lines with the computed numbers in their notes.
+use std::fmt::Write;
+
+use serde::{Deserialize, Serialize};
+
+use super::{comparison::Better, weapon_constructor::PerkConfig, Weapon};
+use crate::{
+    activity::{combatant_scaling::get_combatant_scaling, Activity},
+    enemies::Enemy,
+    perks::{enhanced_check, lib::CalculationInput},
+};
+
+///dps is by far the slowest metric, this keeps a single ranking to a few seconds of it
+pub const MAX_COMBINATIONS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RollMetric {
+    ReloadTime,
+    ReadyTime,
+    AdsTime,
+    ///hip fire falloff start
+    Range,
+    MagSize,
+    ReserveSize,
+    ///pvp ttk against the options' resilience
+    OptimalTtk,
+    BodyTtk,
+    ///impact plus explosion damage of one body shot
+    Damage,
+    ///pve dps against the current encounter
+    Dps,
+}
+impl RollMetric {
+    pub fn better(&self) -> Better {
+        match self {
+            RollMetric::ReloadTime
+            | RollMetric::ReadyTime
+            | RollMetric::AdsTime
+            | RollMetric::OptimalTtk
+            | RollMetric::BodyTtk => Better::Lower,
+            _ => Better::Higher,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            RollMetric::ReloadTime => "reload",
+            RollMetric::ReadyTime => "ready",
+            RollMetric::AdsTime => "ads",
+            RollMetric::Range => "range",
+            RollMetric::MagSize => "mag",
+            RollMetric::ReserveSize => "reserves",
+            RollMetric::OptimalTtk => "optimal ttk",
+            RollMetric::BodyTtk => "body ttk",
+            RollMetric::Damage => "damage",
+            RollMetric::Dps => "dps",
+        }
+    }
+}
+
+const fn default_top() -> usize {
+    10
+}
+
+const fn default_true() -> bool {
+    true
+}
+
+const fn default_resilience() -> i32 {
+    10
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollRankingOptions {
+    pub metric: RollMetric,
+    ///how many rolls to keep
+    #[serde(default = "default_top")]
+    pub top: usize,
+    #[serde(default)]
+    pub pvp: bool,
+    #[serde(default = "default_true")]
+    pub dynamic_traits: bool,
+    ///used by the ttk metrics
+    #[serde(default = "default_resilience")]
+    pub resilience: i32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedRoll {
+    ///raw hashes as given, enhanced perks included
+    pub perks: Vec<u32>,
+    pub score: f64,
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollRanking {
+    pub metric: RollMetric,
+    pub combinations: usize,
+    pub rolls: Vec<RankedRoll>,
+}
+impl RollRanking {
+    ///One `dimwishlist:` line per roll, best first. Rolls with enhanced perks get a second
+    ///line with the base perks so either version matches
+    pub fn to_wishlist(&self, item_hash: u32) -> String {
+        let mut out = String::new();
+        for roll in &self.rolls {
+            let mut lines = vec![roll.perks.clone()];
+            let base: Vec<u32> = roll.perks.iter().map(|x| enhanced_check(*x).0).collect();
+            if base != roll.perks {
+                lines.push(base);
+            }
+            for perks in lines {
+                let perks: Vec<String> = perks.iter().map(|x| x.to_string()).collect();
+                writeln!(
+                    out,
+                    "dimwishlist:item={}&perks={}#notes:{}",
+                    item_hash,
+                    perks.join(","),
+                    roll.notes
+                )
+                .unwrap();
+            }
+        }
+        out
+    }
+}
+
+//the same input for the score and the notes, so the notes show what was scored
+fn calc_input<'a>(
+    weapon: &'a Weapon,
+    options: &RollRankingOptions,
+    enemy: &'a Enemy,
+) -> Option<CalculationInput<'a>> {
+    options.dynamic_traits.then(|| {
+        let mut calc_input = weapon.static_calc_input();
+        calc_input.enemy_type = &enemy.type_;
+        calc_input
+    })
+}
+
+fn score(
+    weapon: &Weapon,
+    options: &RollRankingOptions,
+    activity: &Activity,
+    enemy: &Enemy,
+) -> Result<f64, String> {
+    let calc_input = || calc_input(weapon, options, enemy);
+    let pvp = options.pvp;
+    Ok(match options.metric {
+        RollMetric::ReloadTime => weapon.calc_reload_time(calc_input(), None, pvp).reload_time,
+        RollMetric::ReadyTime => {
+            weapon
+                .calc_handling_times(calc_input(), None, pvp)
+                .ready_time
+        }
+        RollMetric::AdsTime => weapon.calc_handling_times(calc_input(), None, pvp).ads_time,
+        RollMetric::Range => {
+            weapon
+                .calc_range_falloff(calc_input(), None, pvp)
+                .hip_falloff_start
+        }
+        RollMetric::MagSize => weapon.calc_ammo_sizes(calc_input(), None, pvp).mag_size as f64,
+        RollMetric::ReserveSize => {
+            weapon.calc_ammo_sizes(calc_input(), None, pvp).reserve_size as f64
+        }
+        RollMetric::OptimalTtk | RollMetric::BodyTtk => {
+            let summary = weapon
+                .calc_ttk(0.0)
+                .into_iter()
+                .find(|x| x.value == options.resilience)
+                .ok_or_else(|| format!("no ttk for resilience {}", options.resilience))?;
+            if options.metric == RollMetric::OptimalTtk {
+                summary.optimal_ttk.time_taken
+            } else {
+                summary.body_ttk.time_taken
+            }
+        }
+        RollMetric::Damage => {
+            let mut firing = weapon.calc_firing_data(calc_input(), None, pvp);
+            if pvp {
+                firing.pvp_impact_damage + firing.pvp_explosion_damage
+            } else {
+                firing.apply_pve_bonuses(
+                    activity.get_rpl_mult(),
+                    activity.get_pl_delta(),
+                    weapon.damage_mods.pve,
+                    get_combatant_scaling(weapon, enemy.type_).total(),
+                );
+                firing.pve_impact_damage + firing.pve_explosion_damage
+            }
+        }
+        RollMetric::Dps => {
+            let mut dps = weapon.calc_dps(enemy.clone(), activity.get_pl_delta());
+            dps.apply_rpl(activity.get_rpl_mult());
+            if dps.total_time > 0.0 {
+                dps.total_damage / dps.total_time
+            } else {
+                0.0
+            }
+        }
+    })
+}
+
+fn notes(weapon: &Weapon, options: &RollRankingOptions, enemy: &Enemy, score: f64) -> String {
+    let calc_input = || calc_input(weapon, options, enemy);
+    let pvp = options.pvp;
+    let reload = weapon.calc_reload_time(calc_input(), None, pvp);
+    let handling = weapon.calc_handling_times(calc_input(), None, pvp);
+    let range = weapon.calc_range_falloff(calc_input(), None, pvp);
+    let ammo = weapon.calc_ammo_sizes(calc_input(), None, pvp);
+    format!(
+        "{} {:.3} | reload {:.2}s, ready {:.2}s, ads {:.2}s, range {:.1}m, mag {}, reserves {}",
+        options.metric.name(),
+        score,
+        reload.reload_time,
+        handling.ready_time,
+        handling.ads_time,
+        range.hip_falloff_start,
+        ammo.mag_size,
+        ammo.reserve_size
+    )
+}
+
+///Every combination of one perk from each column added to `base`, best `options.top` first
+pub fn rank_rolls(
+    base: &Weapon,
+    columns: &[Vec<PerkConfig>],
+    options: &RollRankingOptions,
+    activity: &Activity,
+    enemy: &Enemy,
+) -> Result<RollRanking, String> {
+    if let Some(i) = columns.iter().position(|x| x.is_empty()) {
+        return Err(format!("perk column {} is empty", i));
+    }
+    let combinations = columns
+        .iter()
+        .try_fold(1_usize, |acc, x| acc.checked_mul(x.len()))
+        .filter(|x| *x <= MAX_COMBINATIONS)
+        .ok_or_else(|| format!("more than {} perk combinations", MAX_COMBINATIONS))?;
+
+    let mut scored: Vec<(Vec<usize>, f64)> = Vec::with_capacity(combinations);
+    let mut picks = vec![0_usize; columns.len()];
+    for _ in 0..combinations {
+        let mut weapon = base.clone();
+        for (column, pick) in columns.iter().zip(&picks) {
+            weapon.add_perk(column[*pick].to_perk());
+        }
+        scored.push((picks.clone(), score(&weapon, options, activity, enemy)?));
+        //odometer over the columns, last column turns fastest
+        for (i, pick) in picks.iter_mut().enumerate().rev() {
+            *pick += 1;
+            if *pick < columns[i].len() {
+                break;
+            }
+            *pick = 0;
+        }
+    }
+
+    let sign = match options.metric.better() {
+        Better::Lower => 1.0,
+        _ => -1.0,
+    };
+    scored.sort_by(|a, b| {
+        (a.1 * sign)
+            .partial_cmp(&(b.1 * sign))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let rolls = scored
+        .into_iter()
+        .take(options.top)
+        .map(|(picks, score)| {
+            let perks: Vec<&PerkConfig> = columns.iter().zip(&picks).map(|(x, i)| &x[*i]).collect();
+            let mut weapon = base.clone();
+            for perk in &perks {
+                weapon.add_perk(perk.to_perk());
+            }
+            RankedRoll {
+                perks: perks.iter().map(|x| x.hash).collect(),
+                score,
+                notes: notes(&weapon, options, enemy, score),
+            }
+        })
+        .collect();
+    Ok(RollRanking {
+        metric: options.metric,
+        combinations,
+        rolls,
+    })
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
-use super::{reserve_calc::calc_reserves, Stat, Weapon};
+use super::{bow_calc::MAX_NOCK_RELOAD_STAT, reserve_calc::calc_reserves, Stat, Weapon};
 use crate::{
-    d2_enums::{MetersPerSecond, Seconds, StatHashes, WeaponType},
+    d2_enums::{AmmoType, MetersPerSecond, Seconds, StatHashes, WeaponType},
+    formulas::get_brick_size,
     perks::{
         get_dmg_modifier, get_explosion_data, get_firing_modifier, get_flinch_modifier,
         get_handling_modifier, get_magazine_modifier, get_range_modifier, get_reload_modifier,
@@ -48,7 +49,7 @@ impl Weapon {
             .perk_val();
 
         if self.weapon_type == WeaponType::BOW {
-            reload_stat = reload_stat.clamp(0, 85);
+            reload_stat = reload_stat.clamp(0, MAX_NOCK_RELOAD_STAT);
         }
 
         let modifiers = if let Some(calc_input) = _calc_input {
@@ -59,13 +60,8 @@ impl Weapon {
 
         let mut out = self.reload_formula.calc_reload_time_formula(reload_stat);
 
-        //this is some weird math that oddly works?
-        //dont ask me why?????
-        //i HATE BOWS
         if self.weapon_type == WeaponType::BOW {
-            let reload_time = out.reload_time - 0.1;
-            out.reload_time =
-                reload_time * 0.3 * modifiers.reload_time_scale + reload_time * 0.7 + 0.1;
+            out.reload_time = self.calc_nock_time(out.reload_time, modifiers.reload_time_scale);
         } else {
             out.reload_time *= modifiers.reload_time_scale;
         }
@@ -219,13 +215,15 @@ impl AmmoFormula {
 
         let mut reserve_size = 1;
         if _calc_inv {
+            //generate_weapon refuses weapons whose reserve id has no formula
             reserve_size = calc_reserves(
                 raw_mag_size,
                 _mag_stat,
                 inv_stat as i32,
                 _inv_id,
                 _inv_modifiers.inv_scale,
-            );
+            )
+            .unwrap_or_default();
         }
         AmmoResponse {
             mag_size,
@@ -241,9 +239,15 @@ impl Weapon {
         _cached_data: Option<&mut HashMap<String, f64>>,
         _pvp: bool,
     ) -> AmmoResponse {
+        //a sword's whole ammo pool is its magazine
+        let mag_hash = if self.weapon_type == WeaponType::SWORD {
+            StatHashes::AMMO_CAPACITY
+        } else {
+            StatHashes::MAGAZINE
+        };
         let mag_stat = self
             .stats
-            .get(&StatHashes::MAGAZINE.into())
+            .get(&mag_hash.into())
             .unwrap_or(&Stat::new())
             .val();
         let inv_stat = self
@@ -294,6 +298,15 @@ impl Weapon {
         }
         out
     }
+
+    ///Ammo one pickup gives, a magazine for primaries and the weapon type's `brick_sizes`
+    ///entry in weapon_formulas.json for everything else. None without one
+    pub fn calc_brick_size(&self, _ammo: &AmmoResponse) -> Option<i32> {
+        match self.ammo_type {
+            AmmoType::PRIMARY => Some(_ammo.mag_size),
+            _ => get_brick_size(self.weapon_type as u32).map(|x| x as i32),
+        }
+    }
 }
 
 impl Weapon {
@@ -338,15 +351,17 @@ impl Weapon {
         let crit_mult = tmp_dmg_prof.crit_mult;
 
         let fd = self.firing_data;
-        let extra_charge_delay = if self.weapon_type == WeaponType::FUSIONRIFLE {
-            0.45
-        } else if self.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-            0.95
-        } else {
-            0.0
+        let charge = self.calc_charge_profile(&firing_modifiers);
+        let extra_charge_delay = charge.map_or(0.0, |x| x.recovery);
+        let sword = self.calc_sword_profile(&firing_modifiers);
+        let burst_delay = match (charge, sword) {
+            (Some(charge), _) => charge.charge_time,
+            (_, Some(sword)) => sword.light_time,
+            _ => {
+                (fd.burst_delay + firing_modifiers.burst_delay_add)
+                    * firing_modifiers.burst_delay_scale
+            }
         };
-        let burst_delay = (fd.burst_delay + firing_modifiers.burst_delay_add)
-            * firing_modifiers.burst_delay_scale;
         let burst_size = fd.burst_size + firing_modifiers.burst_size_add as i32;
         let inner_burst_delay = fd.inner_burst_delay * firing_modifiers.inner_burst_scale;
         let raw_rpm = 60.0
@@ -582,25 +597,20 @@ impl Weapon {
         }
         velocity
     }
-}
 
-impl Weapon {
-    pub fn calc_perfect_draw(&self) -> Seconds {
-        let stability: f64 = self
-            .stats
-            .get(&StatHashes::STABILITY.into())
-            .unwrap_or(&Stat::new())
-            .perk_val()
-            .clamp(0, 100)
-            .into();
-        if self.perks.get(&1449897496).is_some() && self.weapon_type == WeaponType::BOW {
-            return Seconds::INFINITY;
-        }
-        match self.intrinsic_hash {
-            906 => stability * 1.0 / 400.0 + 0.3,
-            905 => stability * 3.0 / 1000.0 + 0.5,
-            _ => Seconds::NAN,
+    ///Time a projectile takes to cover `_distance` meters, 0.0 for hitscan weapons
+    pub fn calc_travel_time(
+        &self,
+        _distance: f64,
+        _calc_input: Option<CalculationInput>,
+        _pvp: bool,
+        _cached_data: Option<&mut HashMap<String, f64>>,
+    ) -> Seconds {
+        let velocity = self.calc_projectile_velocity(_calc_input, _pvp, _cached_data);
+        if velocity <= 0.0 || _distance <= 0.0 {
+            return 0.0;
         }
+        _distance / velocity
     }
 }
 
@@ -644,14 +654,34 @@ impl Weapon {
         ) {
             buffer.insert(
                 "velocity".to_string(),
-                self.calc_projectile_velocity(_calc_input, _pvp, Some(&mut cached_data)),
+                self.calc_projectile_velocity(_calc_input.clone(), _pvp, Some(&mut cached_data)),
             );
         };
 
-        if matches!(self.weapon_type, WeaponType::GLAIVE) {
-            // add "| WeaponType::SWORD" to matches when swords work
+        if matches!(self.weapon_type, WeaponType::GLAIVE | WeaponType::SWORD) {
             buffer.insert("shield_duration".to_string(), self.calc_shield_duration());
         }
+        if let Some(glaive) = self.calc_glaive_profile(&FiringModifierResponse::default()) {
+            let firing = self.calc_firing_data(_calc_input.clone(), None, _pvp);
+            let impact = if _pvp {
+                firing.pvp_impact_damage
+            } else {
+                firing.pve_impact_damage
+            };
+            buffer.insert("melee_damage".to_string(), impact * glaive.melee_scale);
+            buffer.insert("melee_time".to_string(), glaive.melee_time);
+            buffer.insert("melees_per_shot".to_string(), glaive.melees_per_shot as f64);
+        }
+        if let Some(shield) = self.calc_glaive_shield(_pvp) {
+            buffer.insert(
+                "shield_damage_resistance".to_string(),
+                shield.damage_resistance,
+            );
+        }
+        if let Some(guard) = self.calc_guard() {
+            buffer.insert("guard_resistance".to_string(), guard.resistance);
+            buffer.insert("guard_efficiency".to_string(), guard.efficiency);
+        }
         if matches!(self.intrinsic_hash, 912) {
             buffer.insert(
                 "health_per_shot".to_string(),
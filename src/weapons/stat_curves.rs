@@ -0,0 +1,164 @@
+//! Reload, handling, range and ammo evaluated at every value of the stat that drives them.
+//!
+//! Each curve has one entry per stat value from 0 to 100, so `reload_time[40]` is the reload
+//! time at 40 reload. Only the stat being swept changes, everything else (including the other
+//! stats ammo depends on) stays as the weapon has it.
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use super::{Stat, Weapon};
+use crate::d2_enums::StatHashes;
+
+pub const STAT_CURVE_POINTS: usize = 101;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatCurves {
+    ///against reload
+    pub reload_time: Vec<f64>,
+    ///against reload
+    pub ammo_time: Vec<f64>,
+    ///against handling
+    pub ready_time: Vec<f64>,
+    ///against handling
+    pub stow_time: Vec<f64>,
+    ///against handling
+    pub ads_time: Vec<f64>,
+    ///against range
+    pub hip_falloff_start: Vec<f64>,
+    ///against range
+    pub hip_falloff_end: Vec<f64>,
+    ///against range
+    pub ads_falloff_start: Vec<f64>,
+    ///against range
+    pub ads_falloff_end: Vec<f64>,
+    ///against magazine
+    pub mag_size: Vec<i32>,
+    ///against inventory size
+    pub reserve_size: Vec<i32>,
+}
+impl StatCurves {
+    const COLUMNS: [&'static str; 11] = [
+        "reload_time",
+        "ammo_time",
+        "ready_time",
+        "stow_time",
+        "ads_time",
+        "hip_falloff_start",
+        "hip_falloff_end",
+        "ads_falloff_start",
+        "ads_falloff_end",
+        "mag_size",
+        "reserve_size",
+    ];
+
+    fn row(&self, stat: usize) -> [f64; 11] {
+        [
+            self.reload_time[stat],
+            self.ammo_time[stat],
+            self.ready_time[stat],
+            self.stow_time[stat],
+            self.ads_time[stat],
+            self.hip_falloff_start[stat],
+            self.hip_falloff_end[stat],
+            self.ads_falloff_start[stat],
+            self.ads_falloff_end[stat],
+            self.mag_size[stat] as f64,
+            self.reserve_size[stat] as f64,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatCurveSet {
+    ///no perks that depend on calculation input
+    pub base: StatCurves,
+    ///with every perk at its current value
+    pub dynamic: StatCurves,
+}
+impl StatCurveSet {
+    ///One row per stat value, base columns first then the same columns prefixed with `dynamic_`
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("stat");
+        for prefix in ["", "dynamic_"] {
+            for column in StatCurves::COLUMNS {
+                write!(out, ",{}{}", prefix, column).unwrap();
+            }
+        }
+        out.push('\n');
+        for stat in 0..STAT_CURVE_POINTS {
+            write!(out, "{}", stat).unwrap();
+            for value in self
+                .base
+                .row(stat)
+                .iter()
+                .chain(self.dynamic.row(stat).iter())
+            {
+                write!(out, ",{}", value).unwrap();
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl Weapon {
+    fn with_stat(&self, stat: StatHashes, value: i32) -> Weapon {
+        let mut weapon = self.clone();
+        weapon.stats.insert(
+            stat.into(),
+            Stat {
+                base_value: value,
+                part_value: 0,
+                perk_value: 0,
+            },
+        );
+        weapon
+    }
+
+    pub fn calc_stat_curves(&self, _dynamic_traits: bool, _pvp: bool) -> StatCurves {
+        let mut out = StatCurves::default();
+        for value in 0..STAT_CURVE_POINTS as i32 {
+            let weapon = self.with_stat(StatHashes::RELOAD, value);
+            let calc_input = _dynamic_traits.then(|| weapon.static_calc_input());
+            let reload = weapon.calc_reload_time(calc_input, None, _pvp);
+            out.reload_time.push(reload.reload_time);
+            out.ammo_time.push(reload.ammo_time);
+
+            let weapon = self.with_stat(StatHashes::HANDLING, value);
+            let calc_input = _dynamic_traits.then(|| weapon.static_calc_input());
+            let handling = weapon.calc_handling_times(calc_input, None, _pvp);
+            out.ready_time.push(handling.ready_time);
+            out.stow_time.push(handling.stow_time);
+            out.ads_time.push(handling.ads_time);
+
+            let weapon = self.with_stat(StatHashes::RANGE, value);
+            let calc_input = _dynamic_traits.then(|| weapon.static_calc_input());
+            let range = weapon.calc_range_falloff(calc_input, None, _pvp);
+            out.hip_falloff_start.push(range.hip_falloff_start);
+            out.hip_falloff_end.push(range.hip_falloff_end);
+            out.ads_falloff_start.push(range.ads_falloff_start);
+            out.ads_falloff_end.push(range.ads_falloff_end);
+
+            let weapon = self.with_stat(StatHashes::MAGAZINE, value);
+            let calc_input = _dynamic_traits.then(|| weapon.static_calc_input());
+            out.mag_size
+                .push(weapon.calc_ammo_sizes(calc_input, None, _pvp).mag_size);
+
+            let weapon = self.with_stat(StatHashes::INVENTORY_SIZE, value);
+            let calc_input = _dynamic_traits.then(|| weapon.static_calc_input());
+            out.reserve_size
+                .push(weapon.calc_ammo_sizes(calc_input, None, _pvp).reserve_size);
+        }
+        out
+    }
+
+    pub fn calc_stat_curve_set(&self, _pvp: bool) -> StatCurveSet {
+        StatCurveSet {
+            base: self.calc_stat_curves(false, _pvp),
+            dynamic: self.calc_stat_curves(true, _pvp),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Swords: light combos, heavy attacks, guarding and swing ammo.
+//!
+//! A sword's firing data is its light swing, `burst_delay` being the time between swings at
+//! 50 Swing Speed. Everything else about the frame comes from the `sword_frames` section of
+//! weapon_formulas.json, see [`get_sword_frame`]. Heavy attacks spend sword energy, which
+//! Charge Rate refills, and every swing that connects costs ammo. The shipped frames are
+//! unmeasured estimates, a sword without a frame is treated like a gun.
+use std::collections::HashMap;
+
+use super::{
+    dps_calc::{calc_extra_dmg, calc_refund, ExtraDamageBuffInfo},
+    Stat, Weapon,
+};
+use crate::{
+    activity::combatant_scaling::get_combatant_scaling,
+    d2_enums::{Seconds, StatHashes, WeaponType},
+    enemies::Enemy,
+    formulas::get_sword_frame,
+    perks::{
+        get_dmg_modifier, get_extra_damage, get_firing_modifier, get_refund_modifier,
+        lib::FiringModifierResponse,
+    },
+    types::rs_types::DpsResponse,
+};
+
+///sword energy when full, a heavy attack needs all of it
+const MAX_ENERGY: f64 = 100.0;
+///light and heavy attacks both use one ammo when they connect
+const AMMO_PER_SWING: i32 = 1;
+///keeps the simulation finite for swords that refund ammo
+const MAX_SWINGS: i32 = 500;
+
+fn sword_stat(weapon: &Weapon, stat: StatHashes) -> f64 {
+    weapon
+        .stats
+        .get(&stat.into())
+        .unwrap_or(&Stat::new())
+        .perk_val()
+        .clamp(0, 100) as f64
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SwordProfile {
+    pub light_time: Seconds,
+    pub combo: i32,
+    pub finisher_scale: f64,
+    pub heavy_scale: f64,
+    pub heavy_time: Seconds,
+    ///sword energy per second
+    pub energy_regen: f64,
+    pub ammo_per_swing: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GuardResponse {
+    ///damage reduction while guarding, 0.0-1.0
+    pub resistance: f64,
+    ///guard drained per point of damage blocked, lower is better
+    pub efficiency: f64,
+    ///seconds the guard can be held
+    pub endurance: Seconds,
+}
+
+impl Weapon {
+    ///None for anything that isn't a sword or a sword without frame data
+    pub fn calc_sword_profile(&self, _modifiers: &FiringModifierResponse) -> Option<SwordProfile> {
+        if self.weapon_type != WeaponType::SWORD {
+            return None;
+        }
+        let frame = get_sword_frame(self.raw_intrinsic_hash(), self.hash)?;
+        let swing_scale = frame
+            .swing_speed
+            .solve_at(sword_stat(self, StatHashes::SWING_SPEED));
+        let light_time = (self.firing_data.burst_delay + _modifiers.burst_delay_add)
+            * _modifiers.burst_delay_scale
+            * swing_scale;
+        Some(SwordProfile {
+            light_time: light_time.max(0.0),
+            combo: frame.combo,
+            finisher_scale: frame.finisher_scale,
+            heavy_scale: frame.heavy_scale,
+            heavy_time: frame.heavy_time * _modifiers.burst_delay_scale * swing_scale,
+            energy_regen: frame
+                .energy_regen
+                .solve_at(sword_stat(self, StatHashes::CHARGE_RATE)),
+            ammo_per_swing: AMMO_PER_SWING,
+        })
+    }
+
+    ///None for anything that isn't a sword or a sword without frame data
+    pub fn calc_guard(&self) -> Option<GuardResponse> {
+        if self.weapon_type != WeaponType::SWORD {
+            return None;
+        }
+        let frame = get_sword_frame(self.raw_intrinsic_hash(), self.hash)?;
+        Some(GuardResponse {
+            resistance: frame
+                .guard_resistance
+                .solve_at(sword_stat(self, StatHashes::GUARD_RESISTANCE))
+                .clamp(0.0, 1.0),
+            efficiency: frame
+                .guard_efficiency
+                .solve_at(sword_stat(self, StatHashes::GUARD_EFFICIENCY))
+                .max(0.0),
+            endurance: self.calc_shield_duration(),
+        })
+    }
+}
+
+///Swings until the sword runs out of ammo, heavy attacks are used whenever the energy is full
+///and a combo has just finished. Perks see each swing the way guns see each shot
+pub fn calc_sword_dps(weapon: &Weapon, _enemy: &Enemy, _pl_dmg_mult: f64) -> DpsResponse {
+    let mut pers_calc_data: HashMap<String, f64> = HashMap::new();
+    let perks = weapon.list_perks();
+    let damage_profile = weapon.get_damage_profile(false);
+    let combatant_mult = get_combatant_scaling(weapon, _enemy.type_).total();
+    let base_ammo = weapon.calc_ammo_sizes(None, None, false).mag_size;
+    let mut ammo = weapon
+        .calc_ammo_sizes(
+            Some(weapon.static_calc_input()),
+            Some(&mut pers_calc_data),
+            false,
+        )
+        .mag_size;
+
+    let mut total_damage = 0.0_f64;
+    let mut total_time = 0.0_f64;
+    let mut time_damage_data: Vec<(f64, f64)> = Vec::new();
+    let mut swings = 0_i32;
+    let mut combo_swing = 0_i32;
+    let mut energy = MAX_ENERGY;
+
+    while ammo > 0 && swings < MAX_SWINGS {
+        let mut calc_input = weapon.sparse_calc_input(swings, total_time);
+        calc_input.enemy_type = &_enemy.type_;
+        calc_input.base_mag = base_ammo as f64;
+        calc_input.curr_mag = ammo as f64;
+        calc_input.shots_fired_this_mag = swings as f64;
+        calc_input.time_this_mag = total_time;
+        let dmg_mods = get_dmg_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let firing_mods =
+            get_firing_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let profile = weapon
+            .calc_sword_profile(&firing_mods)
+            .expect("only swords are simulated here");
+
+        let heavy = combo_swing == 0 && energy >= MAX_ENERGY;
+        let (swing_scale, swing_time) = if heavy {
+            (profile.heavy_scale, profile.heavy_time)
+        } else if combo_swing == profile.combo - 1 {
+            (profile.finisher_scale, profile.light_time)
+        } else {
+            (1.0, profile.light_time)
+        };
+        let dmg = (damage_profile.impact_dmg * dmg_mods.impact_dmg_scale
+            + damage_profile.explosion_dmg * dmg_mods.explosive_dmg_scale)
+            * swing_scale
+            * _pl_dmg_mult
+            * combatant_mult
+            * weapon.damage_mods.pve;
+        total_damage += dmg;
+        time_damage_data.push((total_time, dmg));
+
+        swings += 1;
+        ammo -= profile.ammo_per_swing;
+        let refunds = get_refund_modifier(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        ammo += calc_refund(swings, refunds).0;
+
+        let extra_dmg_responses =
+            get_extra_damage(perks.clone(), &calc_input, false, &mut pers_calc_data);
+        let buffs = ExtraDamageBuffInfo {
+            pl_buff: _pl_dmg_mult,
+            impact_buff: dmg_mods.impact_dmg_scale,
+            explosive_buff: dmg_mods.explosive_dmg_scale,
+            pve_buff: weapon.damage_mods.pve,
+            crit_buff: damage_profile.crit_mult * dmg_mods.crit_scale,
+            combatant_buff: combatant_mult,
+        };
+        let extra = calc_extra_dmg(total_time, extra_dmg_responses, buffs);
+        total_damage += extra.extra_dmg;
+        total_time += extra.extra_time;
+        time_damage_data.extend(extra.extra_time_dmg);
+
+        if heavy {
+            energy -= MAX_ENERGY;
+        } else {
+            combo_swing = (combo_swing + 1) % profile.combo;
+        }
+        total_time += swing_time;
+        energy = (energy + profile.energy_regen * swing_time).min(MAX_ENERGY);
+    }
+
+    time_damage_data.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    DpsResponse {
+        dps_per_mag: vec![total_damage / total_time],
+        time_damage_data,
+        total_damage,
+        total_time,
+        total_shots: swings,
+        reload_time: 0.0,
+        melee_damage: 0.0,
+    }
+}
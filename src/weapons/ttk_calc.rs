@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use serde::Serialize;
 
 use crate::{
+    activity::armor_stats::{pvp_health, RESILIENCE_HEALTH},
     d2_enums::WeaponType,
     logging::extern_log,
     perks::{get_dmg_modifier, get_firing_modifier, lib::CalculationInput},
@@ -15,10 +16,6 @@ fn ceil(x: f64) -> f64 {
     x.ceil()
 }
 
-const RESILIENCE_VALUES: [f64; 11] = [
-    215.001, 216.001, 217.001, 218.001, 219.001, 220.001, 222.001, 224.001, 226.001, 228.01, 230.00,
-];
-
 #[derive(Debug, Clone, Serialize)]
 pub struct OptimalKillData {
     pub headshots: i32,
@@ -43,7 +40,46 @@ pub struct ResillienceSummary {
     pub optimal_ttk: OptimalKillData,
 }
 
+///Time to kill at every resilience tier
+///Just `_resilience` when there is one, every tier from 0 to 10 otherwise
+pub fn resilience_tiers(_resilience: Option<i32>) -> Vec<i32> {
+    match _resilience {
+        Some(tier) => vec![tier],
+        None => (0..RESILIENCE_HEALTH.len() as i32).collect(),
+    }
+}
+
 pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
+    calc_ttk_resisted(_weapon, _overshield, 0.0, 0.0, &resilience_tiers(None))
+}
+
+//time between the killing shot being fired and its damage landing
+fn kill_landing_delay(
+    _weapon: &Weapon,
+    _distance: f64,
+    _calc_input: CalculationInput,
+    _cached_data: &mut HashMap<String, f64>,
+) -> f64 {
+    let damage_profile = _weapon.get_damage_profile(true);
+    let explosion_delay = if damage_profile.explosion_dmg > 0.0 {
+        damage_profile.damage_delay
+    } else {
+        0.0
+    };
+    _weapon.calc_travel_time(_distance, Some(_calc_input), true, Some(_cached_data))
+        + explosion_delay
+}
+
+///Time to kill a target that takes `_damage_resistance` less damage, like a guardian behind a
+///glaive shield, standing `_distance` meters away. Projectiles only kill once they land.
+///Health comes from each of `_resilience_tiers`
+pub fn calc_ttk_resisted(
+    _weapon: &Weapon,
+    _overshield: f64,
+    _damage_resistance: f64,
+    _distance: f64,
+    _resilience_tiers: &[i32],
+) -> Vec<ResillienceSummary> {
     let mut ttk_data: Vec<ResillienceSummary> = Vec::new();
     let mut persistent_data: HashMap<String, f64> = HashMap::new();
 
@@ -51,12 +87,12 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
     let impact_dmg = tmp_dmg_prof.impact_dmg;
     let explosion_dmg = tmp_dmg_prof.explosion_dmg;
     let mut crit_mult = tmp_dmg_prof.crit_mult;
-    // let damage_delay = tmp_dmg_prof.damage_delay;
     if _weapon.weapon_type == WeaponType::SHOTGUN && _weapon.firing_data.burst_size == 12 {
         crit_mult = 1.0; // shawty has no crits
     }
 
-    for (i, health) in RESILIENCE_VALUES.iter().enumerate() {
+    for tier in _resilience_tiers {
+        let health = &(pvp_health(*tier) / (1.0 - _damage_resistance.clamp(0.0, 0.99)));
         let mut opt_damage_dealt = 0.0_f64;
         let mut opt_time_taken = 0.0_f64;
         let mut opt_bullets_fired = 0.0_f64;
@@ -100,15 +136,21 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
             let head_diff = ((impact_dmg * dmg_mods.impact_dmg_scale) * critical_multiplier)
                 - (impact_dmg * dmg_mods.impact_dmg_scale);
 
-            let shot_burst_delay = (_weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
-                * firing_mods.burst_delay_scale;
+            let shot_burst_delay = match _weapon.calc_sword_profile(&firing_mods) {
+                Some(sword) => sword.light_time,
+                None => {
+                    (_weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
+                        * firing_mods.burst_delay_scale
+                }
+            };
             let shot_inner_burst_delay =
                 _weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
             let shot_burst_size =
                 _weapon.firing_data.burst_size as f64 + firing_mods.burst_size_add;
 
-            let mut shot_delay = if opt_bullets_hit % shot_burst_size > 0.0 && opt_bullets_hit > 0.0
-            {
+            let mut shot_delay = if let Some(charge) = _weapon.calc_charge_profile(&firing_mods) {
+                charge.delay_before(opt_bullets_hit)
+            } else if opt_bullets_hit % shot_burst_size > 0.0 && opt_bullets_hit > 0.0 {
                 shot_inner_burst_delay
             } else if opt_bullets_hit == 0.0 {
                 0.0
@@ -116,13 +158,6 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                 shot_burst_delay
             };
 
-            if _weapon.hash == 4289226715 { // vex mythoclast
-            } else if _weapon.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-                shot_delay *= 1.95;
-            } else if _weapon.weapon_type == WeaponType::FUSIONRIFLE {
-                shot_delay *= 1.45;
-            }
-
             let ammo_fired = if _weapon.firing_data.one_ammo {
                 opt_bullets_hit / shot_burst_size
             } else {
@@ -134,9 +169,27 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                     .mag_size
                     .into()
             {
-                shot_delay += _weapon
-                    .calc_reload_time(Some(calc_input.clone()), Some(&mut persistent_data), true)
-                    .reload_time;
+                //an empty glaive melees its next shot in instead of reloading
+                if let Some(glaive) = _weapon.calc_glaive_profile(&firing_mods) {
+                    let (time, damage, killed) = glaive.melee_recharge(
+                        impact_dmg * dmg_mods.impact_dmg_scale,
+                        health - opt_damage_dealt,
+                    );
+                    opt_damage_dealt += damage;
+                    if killed {
+                        opt_time_taken += time;
+                        break;
+                    }
+                    shot_delay += time;
+                } else {
+                    shot_delay += _weapon
+                        .calc_reload_time(
+                            Some(calc_input.clone()),
+                            Some(&mut persistent_data),
+                            true,
+                        )
+                        .reload_time;
+                }
                 mag_expended += ammo_fired;
             }
 
@@ -155,6 +208,8 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
             if (opt_damage_dealt + body_damage + head_diff) >= *health {
                 opt_headshots += 1;
                 opt_damage_dealt += body_damage + head_diff;
+                opt_time_taken +=
+                    kill_landing_delay(_weapon, _distance, calc_input, &mut persistent_data);
                 break;
             } else {
                 opt_headshots += 1;
@@ -224,15 +279,21 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
             let body_damage = (impact_dmg * dmg_mods.impact_dmg_scale)
                 + (explosion_dmg * dmg_mods.explosive_dmg_scale);
 
-            let shot_burst_delay = (_weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
-                * firing_mods.burst_delay_scale;
+            let shot_burst_delay = match _weapon.calc_sword_profile(&firing_mods) {
+                Some(sword) => sword.light_time,
+                None => {
+                    (_weapon.firing_data.burst_delay + firing_mods.burst_delay_add)
+                        * firing_mods.burst_delay_scale
+                }
+            };
             let shot_inner_burst_delay =
                 _weapon.firing_data.inner_burst_delay * firing_mods.inner_burst_scale;
             let shot_burst_size =
                 _weapon.firing_data.burst_size as f64 + firing_mods.burst_size_add;
 
-            let mut shot_delay = if bdy_bullets_hit % shot_burst_size > 0.0 && bdy_bullets_hit > 0.0
-            {
+            let mut shot_delay = if let Some(charge) = _weapon.calc_charge_profile(&firing_mods) {
+                charge.delay_before(bdy_bullets_hit)
+            } else if bdy_bullets_hit % shot_burst_size > 0.0 && bdy_bullets_hit > 0.0 {
                 shot_inner_burst_delay
             } else if bdy_bullets_hit == 0.0 {
                 0.0
@@ -240,13 +301,6 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                 shot_burst_delay
             };
 
-            if _weapon.hash == 4289226715 { //vex mythoclast
-            } else if _weapon.weapon_type == WeaponType::LINEARFUSIONRIFLE {
-                shot_delay *= 1.95;
-            } else if _weapon.weapon_type == WeaponType::FUSIONRIFLE {
-                shot_delay *= 1.45;
-            }
-
             let ammo_fired = if _weapon.firing_data.one_ammo {
                 bdy_bullets_hit / shot_burst_size
             } else {
@@ -258,9 +312,27 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
                     .mag_size
                     .into()
             {
-                shot_delay += _weapon
-                    .calc_reload_time(Some(calc_input.clone()), Some(&mut persistent_data), true)
-                    .reload_time;
+                //an empty glaive melees its next shot in instead of reloading
+                if let Some(glaive) = _weapon.calc_glaive_profile(&firing_mods) {
+                    let (time, damage, killed) = glaive.melee_recharge(
+                        impact_dmg * dmg_mods.impact_dmg_scale,
+                        health - bdy_damage_dealt,
+                    );
+                    bdy_damage_dealt += damage;
+                    if killed {
+                        bdy_time_taken += time;
+                        break;
+                    }
+                    shot_delay += time;
+                } else {
+                    shot_delay += _weapon
+                        .calc_reload_time(
+                            Some(calc_input.clone()),
+                            Some(&mut persistent_data),
+                            true,
+                        )
+                        .reload_time;
+                }
                 mag_expended += ammo_fired;
             }
 
@@ -273,6 +345,8 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
             };
 
             if (bdy_damage_dealt + body_damage) >= *health {
+                bdy_time_taken +=
+                    kill_landing_delay(_weapon, _distance, calc_input, &mut persistent_data);
                 break;
             } else {
                 bdy_damage_dealt += body_damage;
@@ -283,7 +357,7 @@ pub fn calc_ttk(_weapon: &Weapon, _overshield: f64) -> Vec<ResillienceSummary> {
             bodyshots: bdy_bullets_hit as i32,
         };
         ttk_data.push(ResillienceSummary {
-            value: i as i32,
+            value: *tier,
             body_ttk,
             optimal_ttk,
         });
@@ -295,4 +369,20 @@ impl Weapon {
     pub fn calc_ttk(&self, _overshield: f64) -> Vec<ResillienceSummary> {
         calc_ttk(self, _overshield)
     }
+
+    pub fn calc_ttk_resisted(
+        &self,
+        _overshield: f64,
+        _damage_resistance: f64,
+        _distance: f64,
+        _resilience_tiers: &[i32],
+    ) -> Vec<ResillienceSummary> {
+        calc_ttk_resisted(
+            self,
+            _overshield,
+            _damage_resistance,
+            _distance,
+            _resilience_tiers,
+        )
+    }
 }
@@ -1,29 +1,29 @@
 use std::collections::HashMap;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{
     d2_enums::{AmmoType, BungieHash, DamageType, WeaponType},
     database,
+    formulas::{get_reserve_formula, get_weapon_formulas},
     perks::{enhanced_check, Perk},
-    types::rs_types::{
-        AmmoFormula, DamageMods, DataPointers, HandlingFormula, RangeFormula, ReloadFormula,
-        StatQuadraticFormula, WeaponPath,
-    },
+    types::rs_types::{ItemData, StatGroup},
 };
 
-use super::{FiringData, Weapon};
+use super::{Stat, Weapon};
 
-fn get_data_pointers(
-    _weapon_type_id: u8,
-    intrinsic_hash: BungieHash,
-    weapon_hash: BungieHash,
-) -> Option<DataPointers> {
-    let pointer_map: HashMap<WeaponPath, DataPointers> = HashMap::from(database::DATA_POINTERS);
-    if let Some(weapon) = pointer_map.get(&WeaponPath(_weapon_type_id as u32, weapon_hash)) {
-        return Some(*weapon);
-    }
-    pointer_map
-        .get(&WeaponPath(_weapon_type_id as u32, intrinsic_hash))
-        .cloned()
+pub fn get_item_data(hash: BungieHash) -> Option<ItemData> {
+    database::ITEM_DATA
+        .binary_search_by_key(&hash, |x| x.hash)
+        .ok()
+        .map(|i| database::ITEM_DATA[i])
+}
+
+pub fn get_stat_group(hash: BungieHash) -> Option<StatGroup> {
+    database::STAT_GROUP_DATA
+        .binary_search_by_key(&hash, |x| x.hash)
+        .ok()
+        .map(|i| database::STAT_GROUP_DATA[i])
 }
 
 impl Weapon {
@@ -34,21 +34,9 @@ impl Weapon {
         _ammo_type_id: u32,
         _damage_type_id: u32,
     ) -> Option<Weapon> {
-        let data_pointer_result = get_data_pointers(_weapon_type_id, _intrinsic_hash, _hash);
-
-        let data_pointer = data_pointer_result?;
-
-        let range_formula: RangeFormula = database::RANGE_DATA[data_pointer.r];
-
-        let handling_formula: HandlingFormula = database::HANDLING_DATA[data_pointer.h];
-
-        let reload_formula: ReloadFormula = database::RELOAD_DATA[data_pointer.rl];
-
-        let damage_mods: DamageMods = database::SCALAR_DATA[data_pointer.s];
-
-        let firing_data: FiringData = database::FIRING_DATA[data_pointer.f];
-
-        let ammo_formula: AmmoFormula = database::AMMO_DATA[data_pointer.a];
+        let formulas = get_weapon_formulas(_weapon_type_id, _intrinsic_hash, _hash)?;
+        //a reserve id without a curve would silently give the weapon no reserves
+        get_reserve_formula(formulas.ammo.reserve_id)?;
 
         let weapon_type = WeaponType::from(_weapon_type_id as u32);
         let ammo_type = AmmoType::from(_ammo_type_id);
@@ -80,16 +68,124 @@ impl Weapon {
                 ),
             ]),
             stats: HashMap::new(),
+            investment_stats: HashMap::new(),
+            stat_group: None,
             perk_value_map: HashMap::from([(intrinsic_alias, 0), (0, 0)]),
-            damage_mods,
-            ammo_formula,
-            firing_data,
-            handling_formula,
-            reload_formula,
-            range_formula,
+            damage_mods: formulas.scalars,
+            ammo_formula: formulas.ammo,
+            firing_data: formulas.firing,
+            handling_formula: formulas.handling,
+            reload_formula: formulas.reload,
+            range_formula: formulas.range,
             ammo_type,
             damage_type,
             weapon_type,
         })
     }
+
+    ///Builds a weapon from its manifest hash alone using the item table baked in at build time
+    pub fn from_item_hash(_hash: u32) -> Option<Weapon> {
+        Weapon::from_item_data(&get_item_data(_hash)?)
+    }
+
+    pub fn from_item_data(_item: &ItemData) -> Option<Weapon> {
+        let mut weapon = Weapon::generate_weapon(
+            _item.hash,
+            _item.weapon_type,
+            _item.intrinsic_hash,
+            _item.ammo_type,
+            _item.damage_type,
+        )?;
+        //items whose stat group didn't make it into the snapshot fall back to the manifest's own values
+        if let Some(stat_group) = get_stat_group(_item.stat_group) {
+            weapon
+                .set_investment_stats(stat_group, _item.investment_stats.iter().cloned().collect());
+        } else {
+            weapon.set_stats(
+                _item
+                    .display_stats
+                    .iter()
+                    .map(|(hash, value)| (*hash, Stat::from(*value)))
+                    .collect(),
+            );
+        }
+        Some(weapon)
+    }
+}
+
+///A perk as it's stored in corpora and saved rolls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkConfig {
+    pub hash: u32,
+    ///toggle or stack count
+    #[serde(default)]
+    pub value: u32,
+    #[serde(default)]
+    pub stat_buffs: HashMap<u32, i32>,
+}
+impl PerkConfig {
+    pub fn to_perk(&self) -> Perk {
+        let (hash, enhanced) = enhanced_check(self.hash);
+        Perk {
+            stat_buffs: self.stat_buffs.clone(),
+            enhanced,
+            value: self.value,
+            hash,
+            raw_hash: self.hash,
+        }
+    }
+}
+
+///Everything `setWeapon`, `setStats` and `addTrait` take, in one place so a weapon can be
+///described as data and rebuilt later
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeaponConfig {
+    pub hash: u32,
+    pub weapon_type: u8,
+    pub intrinsic_hash: u32,
+    pub ammo_type: u32,
+    pub damage_type: u32,
+    ///displayed stats, or investment stats when `stat_group` is set
+    #[serde(default)]
+    pub stats: HashMap<u32, i32>,
+    #[serde(default)]
+    pub stat_group: Option<u32>,
+    #[serde(default)]
+    pub perks: Vec<PerkConfig>,
+}
+impl WeaponConfig {
+    pub fn build(&self) -> Result<Weapon, String> {
+        let mut weapon = Weapon::generate_weapon(
+            self.hash,
+            self.weapon_type,
+            self.intrinsic_hash,
+            self.ammo_type,
+            self.damage_type,
+        )
+        .ok_or_else(|| {
+            format!(
+                "no weapon data for type {} with intrinsic {}",
+                self.weapon_type, self.intrinsic_hash
+            )
+        })?;
+        match self.stat_group {
+            Some(hash) => {
+                let stat_group =
+                    get_stat_group(hash).ok_or_else(|| format!("unknown stat group {}", hash))?;
+                weapon.set_investment_stats(stat_group, self.stats.clone());
+            }
+            None => weapon.set_stats(
+                self.stats
+                    .iter()
+                    .map(|(hash, value)| (*hash, Stat::from(*value)))
+                    .collect(),
+            ),
+        }
+        for perk in &self.perks {
+            weapon.add_perk(perk.to_perk());
+        }
+        Ok(weapon)
+    }
 }